@@ -1,6 +1,6 @@
 use node_template_runtime::{
-	AccountId, AuraConfig, BalancesConfig, GenesisConfig, GrandpaConfig, Signature, SudoConfig,
-	SystemConfig, WASM_BINARY,
+	pallet_logistics::Roles, AccountId, AuraConfig, BalancesConfig, GenesisConfig, GrandpaConfig,
+	LogisticsModuleConfig, Signature, SudoConfig, SystemConfig, WASM_BINARY,
 };
 use sc_service::ChainType;
 use sp_consensus_aura::sr25519::AuthorityId as AuraId;
@@ -152,5 +152,9 @@ fn testnet_genesis(
 			key: Some(root_key),
 		},
 		transaction_payment: Default::default(),
+		logistics_module: LogisticsModuleConfig {
+			// Let every endowed account play every part in the logistics network.
+			organizations: vec![([0; 32], Roles::all(), endowed_accounts)],
+		},
 	}
 }
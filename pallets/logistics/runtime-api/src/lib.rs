@@ -0,0 +1,23 @@
+//! Runtime API definition for the logistics pallet.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+use pallet_logistics::{CustodyRecord, Facility, FacilityId};
+use sp_std::vec::Vec;
+
+sp_api::decl_runtime_apis! {
+	/// Read access to shipment records for auditors and other off-chain clients.
+	pub trait LogisticsApi<AccountId, BlockNumber>
+	where
+		AccountId: Codec,
+		BlockNumber: Codec,
+	{
+		/// The latest custody hops kept for `shipment_id`, oldest first, starting with the shipper
+		/// registering it. Holders of older hops are in the pallet's `FormerCustodians`.
+		fn custody_history(shipment_id: u64) -> Vec<CustodyRecord<AccountId, BlockNumber>>;
+
+		/// The registered facility `facility_id`, e.g. to resolve a shipment's destination.
+		fn facility(facility_id: FacilityId) -> Option<Facility<AccountId>>;
+	}
+}
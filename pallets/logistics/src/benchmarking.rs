@@ -1,19 +1,746 @@
-//! Benchmarking setup for pallet-template
+//! Benchmarking setup for pallet-logistics
 
 use super::*;
+use crate::{
+	delivery::{ConsigneeSignature, DeliveryClass, ProofOfDelivery},
+	devices::{DevicePayload, Submission},
+	manifest::{Dimensions, HandlingFlags, HazmatClass, ManifestItem},
+	telemetry::{ConditionRanges, Range, Reading},
+};
 
 #[allow(unused)]
 use crate::Pallet as Logistics;
-use frame_benchmarking::v1::{benchmarks, whitelisted_caller};
+use frame_benchmarking::v1::{account, benchmarks, whitelisted_caller, BenchmarkError};
+use frame_support::{
+	traits::{Currency, EnsureOrigin, Get, ReservableCurrency},
+	weights::Weight,
+	BoundedVec,
+};
 use frame_system::RawOrigin;
+use sp_runtime::{
+	app_crypto::{sr25519, RuntimePublic},
+	key_types,
+	traits::{Bounded, One, Saturating, Zero},
+	RuntimeAppPublic,
+};
+use sp_std::{vec, vec::Vec};
+
+const SEED: u32 = 0;
+
+/// An account acting for an organization that holds every role.
+fn member<T: Config>(who: T::AccountId) -> T::AccountId {
+	let organization_id = Logistics::<T>::do_register_organization([0; 32], Roles::all())
+		.expect("organization ids are not exhausted");
+	Logistics::<T>::do_add_member(organization_id, &who).expect("account is not a member yet");
+	who
+}
+
+/// Carriage fee paid for every benchmarked shipment.
+fn fee<T: Config>() -> BalanceOf<T> {
+	T::Currency::minimum_balance().saturating_mul(100u32.into())
+}
+
+/// Give `who` enough to pay carriage fees.
+fn fund<T: Config>(who: &T::AccountId) {
+	T::Currency::make_free_balance_be(who, BalanceOf::<T>::max_value() / 2u32.into());
+}
+
+fn coords() -> Coords {
+	Coords::new(-33_856_800, 151_215_300)
+		.expect("within range")
+		.with_altitude(25)
+		.with_accuracy(5)
+}
+
+/// A manifest with as many lines as the runtime allows.
+fn full_manifest<T: Config>() -> ManifestOf<T> {
+	let item = ManifestItem { sku_hash: [1; 32], lot_hash: Some([2; 32]), quantity: 1 };
+	Manifest {
+		items: BoundedVec::truncate_from(vec![item; T::MaxManifestItems::get() as usize]),
+		gross_weight: 1_000,
+		dimensions: Dimensions { length: 300, width: 200, height: 100 },
+		declared_value: 10_000,
+		handling: HandlingFlags {
+			fragile: true,
+			perishable: true,
+			hazmat: Some(HazmatClass::Miscellaneous),
+		},
+	}
+}
+
+/// Register a facility owned by `owner` and return its id.
+fn register_facility<T: Config>(owner: &T::AccountId) -> FacilityId {
+	let facility_id = NextFacilityId::<T>::get();
+	Logistics::<T>::register_facility(
+		RawOrigin::Signed(owner.clone()).into(),
+		[0; 32],
+		coords(),
+		FacilityKind::Depot,
+	)
+	.expect("facility ids are not exhausted");
+	facility_id
+}
+
+/// Ranges for every condition, narrow enough for [`reading`] to breach all of them.
+fn ranges() -> ConditionRanges {
+	ConditionRanges {
+		temperature: Some(Range { min: 200, max: 800 }),
+		humidity: Some(Range { min: 300, max: 600 }),
+		shock: Some(Range { min: 0, max: 300 }),
+	}
+}
+
+fn reading() -> Reading {
+	Reading { temperature: Some(-1_800), humidity: Some(950), shock: Some(1_200) }
+}
+
+/// Account every benchmarked shipment is meant for, made a member the first time.
+fn consignee<T: Config>() -> T::AccountId {
+	let consignee = account("consignee", 0, SEED);
+	if Members::<T>::contains_key(&consignee) {
+		consignee
+	} else {
+		member::<T>(consignee)
+	}
+}
+
+/// Create a shipment owned and held by `shipper`, which can be returned to its origin.
+fn create_shipment<T: Config>(shipper: &T::AccountId, shipment_id: u64) {
+	let origin = register_facility::<T>(shipper);
+	let destination = register_facility::<T>(shipper);
+	fund::<T>(shipper);
+	Logistics::<T>::begin_transit(
+		RawOrigin::Signed(shipper.clone()).into(),
+		shipment_id,
+		coords(),
+		Some(origin),
+		destination,
+		consignee::<T>(),
+		full_manifest::<T>(),
+		fee::<T>(),
+		None,
+	)
+	.expect("shipment id is unused");
+}
+
+/// Create a shipment and hand it from `shipper` to `carrier`.
+fn shipment_with_carrier<T: Config>(
+	shipper: &T::AccountId,
+	carrier: &T::AccountId,
+	shipment_id: u64,
+) {
+	create_shipment::<T>(shipper, shipment_id);
+	Logistics::<T>::offer_handoff(
+		RawOrigin::Signed(shipper.clone()).into(),
+		shipment_id,
+		carrier.clone(),
+	)
+	.expect("shipper holds the shipment");
+	Logistics::<T>::shipment_received(
+		RawOrigin::Signed(carrier.clone()).into(),
+		shipment_id,
+		coords(),
+	)
+	.expect("handoff was offered to the carrier");
+}
+
+/// Create `count` shipments owned and held by `shipper`, numbered after `container_id`.
+fn create_contents<T: Config>(
+	shipper: &T::AccountId,
+	container_id: u64,
+	count: u32,
+) -> BoundedVec<u64, T::MaxContainerSize> {
+	let shipment_ids = (1..=count as u64).map(|i| container_id + i).collect::<Vec<_>>();
+	for &shipment_id in &shipment_ids {
+		create_shipment::<T>(shipper, shipment_id);
+	}
+	BoundedVec::truncate_from(shipment_ids)
+}
+
+/// Create a container held by `shipper` with `count` shipments loaded in it.
+fn loaded_container<T: Config>(
+	shipper: &T::AccountId,
+	container_id: u64,
+	count: u32,
+) -> BoundedVec<u64, T::MaxContainerSize> {
+	create_shipment::<T>(shipper, container_id);
+	let shipment_ids = create_contents::<T>(shipper, container_id, count);
+	Logistics::<T>::load_shipments(
+		RawOrigin::Signed(shipper.clone()).into(),
+		container_id,
+		shipment_ids.clone(),
+	)
+	.expect("shipper holds the container and its contents");
+	shipment_ids
+}
+
+/// Register a new device for the organization of `member`, and sign a payload carrying its
+/// first `submission` with it.
+fn device_payload<T: Config>(
+	member: &T::AccountId,
+	submission: Submission,
+) -> (DevicePayloadOf<T>, DeviceSignatureOf<T>) {
+	let mut device = T::DeviceId::generate_pair(None);
+	Logistics::<T>::register_device(RawOrigin::Signed(member.clone()).into(), device.clone())
+		.expect("device is new");
+	let payload = DevicePayload { device: device.clone(), nonce: 0, submission };
+	let genesis = frame_system::Pallet::<T>::block_hash(T::BlockNumber::zero());
+	let signature = device.sign(&payload.message(&genesis)).expect("device key was just generated");
+	(payload, signature)
+}
+
+/// Pad the custody history of `shipment_id` up to `len` hops.
+fn fill_custody_history<T: Config>(shipment_id: u64, holder: &T::AccountId, len: u32) {
+	while (CustodyHistory::<T>::get(shipment_id).len() as u32) < len {
+		CustodyHistory::<T>::try_append(
+			shipment_id,
+			CustodyRecord {
+				holder: holder.clone(),
+				coords: coords(),
+				block: frame_system::Pallet::<T>::block_number(),
+				timestamp: 0,
+			},
+		)
+		.expect("custody history is below its bound");
+	}
+}
+
+/// Record `count` hops of `shipment_id` with a different custodian each.
+fn add_custodians<T: Config>(shipment_id: u64, count: u32) {
+	for i in 0..count {
+		let len = CustodyHistory::<T>::get(shipment_id).len() as u32;
+		fill_custody_history::<T>(shipment_id, &account("custodian", i, SEED), len + 1);
+	}
+}
+
+/// Create a shipment, hand it from `shipper` to `carrier` and have the shipper claim it was lost.
+fn claimed_shipment<T: Config>(shipper: &T::AccountId, carrier: &T::AccountId, shipment_id: u64) {
+	shipment_with_carrier::<T>(shipper, carrier, shipment_id);
+	Logistics::<T>::file_claim(
+		RawOrigin::Signed(shipper.clone()).into(),
+		shipment_id,
+		carrier.clone(),
+		ClaimKind::Lost,
+		[1; 32],
+	)
+	.expect("shipper can claim against the carrier");
+}
+
+/// A signature of [`consignee`] acknowledging they received `shipment_id` at `block`, by a key
+/// they just set.
+fn consignee_signature<T: Config>(
+	shipment_id: u64,
+	block: T::BlockNumber,
+) -> ConsigneeSignatureOf<T> {
+	let signer = sr25519::Public::generate_pair(key_types::DUMMY, None);
+	let message = ConsigneeSignatureOf::<T>::message(shipment_id, &block);
+	let signature = signer.sign(key_types::DUMMY, &message).expect("key was just generated");
+	ConsigneeKeys::<T>::insert(consignee::<T>(), signer);
+	ConsigneeSignature { signer, block, signature }
+}
+
+/// Insert a shipment that was delivered in block `delivered_on` and queue it for pruning.
+fn delivered_shipment<T: Config>(shipment_id: u64, delivered_on: T::BlockNumber) {
+	let shipper: T::AccountId = account("shipper", 0, SEED);
+
+	let mut shipment =
+		Shipment::<T>::new(shipment_id, shipper.clone(), coords(), None, 0, Some(consignee::<T>()));
+	shipment.status = ShipmentStatus::Delivered;
+	shipment.received_on = delivered_on;
+	Shipments::<T>::insert(shipment_id, shipment);
+	fill_custody_history::<T>(shipment_id, &shipper, 1);
+
+	let deposit = Logistics::<T>::deposit_for(&full_manifest::<T>());
+	fund::<T>(&shipper);
+	T::Currency::reserve(&shipper, deposit).expect("shipper was just funded");
+	Deposits::<T>::insert(shipment_id, deposit);
+
+	Logistics::<T>::queue_for_pruning(shipment_id, delivered_on);
+}
 
 benchmarks! {
-	do_something {
-		let s in 0 .. 100;
-		let caller: T::AccountId = whitelisted_caller();
-	}: _(RawOrigin::Signed(caller), s)
+	begin_transit {
+		let caller = member::<T>(whitelisted_caller());
+		let origin = Some(register_facility::<T>(&caller));
+		let dest = register_facility::<T>(&caller);
+		fund::<T>(&caller);
+		let fee = fee::<T>();
+		let deadline = Some(Deadline::Block(T::BlockNumber::max_value()));
+		let consignee = consignee::<T>();
+		let manifest = full_manifest::<T>();
+	}: _(
+		RawOrigin::Signed(caller.clone()),
+		0,
+		coords(),
+		origin,
+		dest,
+		consignee.clone(),
+		manifest,
+		fee,
+		deadline
+	)
+	verify {
+		assert_eq!(Shipments::<T>::get(0).map(|s| s.shipped_by), Some(caller));
+		assert_eq!(Shipments::<T>::get(0).and_then(|s| s.consignee), Some(consignee));
+		assert_eq!(Shipments::<T>::get(0).and_then(|s| s.origin), origin);
+		assert_eq!(Escrows::<T>::get(0), Some(fee));
+		assert!(Deadlines::<T>::contains_key(0));
+	}
+
+	amend_manifest {
+		let caller = member::<T>(whitelisted_caller());
+		create_shipment::<T>(&caller, 0);
+		let mut manifest = full_manifest::<T>();
+		manifest.gross_weight = 2_000;
+	}: _(RawOrigin::Signed(caller), 0, manifest)
+	verify {
+		assert_eq!(Manifests::<T>::get(0).map(|m| m.gross_weight), Some(2_000));
+	}
+
+	// The caller takes custody of a container with `c` shipments loaded in it.
+	shipment_received {
+		let c in 0 .. T::MaxContainerSize::get();
+
+		let shipper = member::<T>(account("shipper", 0, SEED));
+		let caller = member::<T>(whitelisted_caller());
+		let shipment_ids = loaded_container::<T>(&shipper, 0, c);
+		for shipment_id in shipment_ids.iter().copied().chain([0]) {
+			fill_custody_history::<T>(shipment_id, &shipper, T::MaxCustodyHops::get() - 1);
+		}
+		Logistics::<T>::offer_handoff(RawOrigin::Signed(shipper).into(), 0, caller.clone())?;
+	}: _(RawOrigin::Signed(caller.clone()), 0, coords())
+	verify {
+		assert_eq!(Shipments::<T>::get(0).map(|s| s.received_by), Some(caller.clone()));
+		assert!(!PendingHandoffs::<T>::contains_key(0));
+		for shipment_id in shipment_ids {
+			let holder = Shipments::<T>::get(shipment_id).map(|s| s.received_by);
+			assert_eq!(holder, Some(caller.clone()));
+		}
+	}
+
+	offer_handoff {
+		let caller = member::<T>(whitelisted_caller());
+		let carrier = member::<T>(account("carrier", 0, SEED));
+		create_shipment::<T>(&caller, 0);
+	}: _(RawOrigin::Signed(caller), 0, carrier.clone())
+	verify {
+		assert_eq!(PendingHandoffs::<T>::get(0).map(|h| h.to), Some(carrier));
+	}
+
+	cancel_handoff {
+		let caller = member::<T>(whitelisted_caller());
+		let carrier = member::<T>(account("carrier", 0, SEED));
+		create_shipment::<T>(&caller, 0);
+		Logistics::<T>::offer_handoff(RawOrigin::Signed(caller.clone()).into(), 0, carrier)?;
+	}: _(RawOrigin::Signed(caller), 0)
+	verify {
+		assert!(!PendingHandoffs::<T>::contains_key(0));
+	}
+
+	cancel_shipment {
+		let caller = member::<T>(whitelisted_caller());
+		let carrier = member::<T>(account("carrier", 0, SEED));
+		create_shipment::<T>(&caller, 0);
+		Logistics::<T>::offer_handoff(RawOrigin::Signed(caller.clone()).into(), 0, carrier)?;
+	}: _(RawOrigin::Signed(caller), 0)
+	verify {
+		assert_eq!(Shipments::<T>::get(0).map(|s| s.status), Some(ShipmentStatus::Cancelled));
+		assert!(!PendingHandoffs::<T>::contains_key(0));
+		assert!(!Escrows::<T>::contains_key(0));
+	}
+
+	return_to_sender {
+		let caller = member::<T>(whitelisted_caller());
+		let carrier = member::<T>(account("carrier", 0, SEED));
+		shipment_with_carrier::<T>(&caller, &carrier, 0);
+	}: _(RawOrigin::Signed(caller), 0)
+	verify {
+		assert_eq!(Shipments::<T>::get(0).map(|s| s.returning), Some(true));
+	}
+
+	// Closing a shipment without an origin facility refunds the carriage fee, which costs more
+	// than sending it back.
+	reject_shipment {
+		let shipper = member::<T>(account("shipper", 0, SEED));
+		let carrier = member::<T>(account("carrier", 0, SEED));
+		let consignee = consignee::<T>();
+		shipment_with_carrier::<T>(&shipper, &carrier, 0);
+		Shipments::<T>::mutate(0, |shipment| {
+			if let Some(shipment) = shipment {
+				shipment.origin = None;
+			}
+		});
+	}: _(RawOrigin::Signed(consignee), 0)
+	verify {
+		assert_eq!(Shipments::<T>::get(0).map(|s| s.status), Some(ShipmentStatus::Rejected));
+		assert!(!Escrows::<T>::contains_key(0));
+	}
+
+	// The caller splits a shipment with a deadline and a full custody history into `n` parts.
+	split_shipment {
+		let n in 2 .. T::MaxSplitParts::get();
+
+		let shipper = member::<T>(account("shipper", 0, SEED));
+		let caller = member::<T>(whitelisted_caller());
+		shipment_with_carrier::<T>(&shipper, &caller, 0);
+		fill_custody_history::<T>(0, &caller, T::MaxCustodyHops::get());
+		Deadlines::<T>::insert(0, Deadline::Block(T::BlockNumber::max_value()));
+		let destination = register_facility::<T>(&caller);
+		let manifest = full_manifest::<T>();
+		let parts = (1..=n as u64)
+			.map(|shipment_id| SplitPart { shipment_id, destination, manifest: manifest.clone() })
+			.collect::<Vec<_>>();
+		let parts = BoundedVec::truncate_from(parts);
+	}: _(RawOrigin::Signed(caller), 0, parts)
+	verify {
+		assert_eq!(Shipments::<T>::get(0).map(|s| s.status), Some(ShipmentStatus::Split));
+		assert_eq!(SplitFrom::<T>::get(n as u64), Some(0));
+	}
+
+	// Losing a shipment is the most expensive status, as it refunds the carriage fee. The caller
+	// loses a container with `c` shipments loaded in it, which are lost with it.
+	update_status {
+		let c in 0 .. T::MaxContainerSize::get();
+
+		let shipper = member::<T>(account("shipper", 0, SEED));
+		let caller = member::<T>(whitelisted_caller());
+		let shipment_ids = loaded_container::<T>(&shipper, 0, c);
+		Logistics::<T>::offer_handoff(RawOrigin::Signed(shipper).into(), 0, caller.clone())?;
+		Logistics::<T>::shipment_received(RawOrigin::Signed(caller.clone()).into(), 0, coords())?;
+	}: _(RawOrigin::Signed(caller), 0, ShipmentStatus::Lost)
+	verify {
+		assert_eq!(Shipments::<T>::get(0).map(|s| s.status), Some(ShipmentStatus::Lost));
+		assert!(!Escrows::<T>::contains_key(0));
+		for shipment_id in shipment_ids {
+			assert_eq!(
+				Shipments::<T>::get(shipment_id).map(|s| s.status),
+				Some(ShipmentStatus::Lost)
+			);
+			assert!(!ContainedIn::<T>::contains_key(shipment_id));
+		}
+	}
+
+	// `h` custodians share the fee. The shipper's and the delivery hop leave room for `h - 1`
+	// custodians before the caller. The consignee signs and evidence is full.
+	shipment_delivered {
+		let h in 1 .. T::MaxCustodyHops::get() - 2;
+
+		let shipper = member::<T>(account("shipper", 0, SEED));
+		let caller = member::<T>(whitelisted_caller());
+		create_shipment::<T>(&shipper, 0);
+		add_custodians::<T>(0, h - 1);
+		Logistics::<T>::offer_handoff(RawOrigin::Signed(shipper).into(), 0, caller.clone())?;
+		Logistics::<T>::shipment_received(RawOrigin::Signed(caller.clone()).into(), 0, coords())?;
+		DefaultGeofenceRadius::<T>::put(100);
+		let consignee = consignee_signature::<T>(0, frame_system::Pallet::<T>::block_number());
+		let evidence = BoundedVec::truncate_from(vec![[3; 32]; T::MaxEvidence::get() as usize]);
+	}: _(RawOrigin::Signed(caller), 0, coords(), Some(consignee), evidence)
+	verify {
+		assert_eq!(Shipments::<T>::get(0).map(|s| s.status), Some(ShipmentStatus::Delivered));
+		assert_eq!(
+			Deliveries::<T>::get(0).map(|d| d.class),
+			Some(DeliveryClass::ConfirmedByConsignee)
+		);
+		assert_eq!(PruneCursor::<T>::get().tail, 1);
+		assert!(!Escrows::<T>::contains_key(0));
+	}
+
+	reap_abandoned {
+		let shipper = member::<T>(account("shipper", 0, SEED));
+		let caller = member::<T>(whitelisted_caller());
+		shipment_with_carrier::<T>(&shipper, &caller, 0);
+		let carrier = member::<T>(account("carrier", 0, SEED));
+		Logistics::<T>::offer_handoff(RawOrigin::Signed(caller.clone()).into(), 0, carrier)?;
+
+		let now = frame_system::Pallet::<T>::block_number();
+		frame_system::Pallet::<T>::set_block_number(now + T::AbandonmentTimeout::get());
+	}: _(RawOrigin::Signed(caller), 0)
+	verify {
+		assert!(!Shipments::<T>::contains_key(0));
+		assert!(!Deposits::<T>::contains_key(0));
+	}
+
+	confirm_receipt {
+		let consignee = consignee::<T>();
+		delivered_shipment::<T>(0, frame_system::Pallet::<T>::block_number());
+		let delivery = ProofOfDelivery {
+			class: DeliveryClass::CourierAsserted,
+			consignee: None,
+			evidence: Default::default(),
+		};
+		Deliveries::<T>::insert(0, delivery);
+	}: _(RawOrigin::Signed(consignee), 0)
+	verify {
+		assert_eq!(
+			Deliveries::<T>::get(0).map(|d| d.class),
+			Some(DeliveryClass::ConfirmedByConsignee)
+		);
+	}
+
+	set_consignee_key {
+		let caller = member::<T>(whitelisted_caller());
+		let key = sr25519::Public::generate_pair(key_types::DUMMY, None);
+	}: _(RawOrigin::Signed(caller.clone()), Some(key))
+	verify {
+		assert_eq!(ConsigneeKeys::<T>::get(caller), Some(key));
+	}
+
+	prune_delivered {
+		let n in 0 .. 1_000;
+
+		let delivered_on: T::BlockNumber = One::one();
+		for shipment_id in 0 .. n as u64 {
+			delivered_shipment::<T>(shipment_id, delivered_on);
+		}
+
+		// A shipment still within its retention period, which ends the run after one more read.
+		let now = delivered_on.saturating_add(T::DeliveredRetention::get());
+		delivered_shipment::<T>(n as u64, now);
+	}: {
+		Logistics::<T>::prune_delivered(now, Weight::MAX);
+	}
+	verify {
+		assert_eq!(PruneCursor::<T>::get().head, n as u64);
+		assert_eq!(Shipments::<T>::count(), 1);
+	}
+
+	// Every shipment on the agenda has a timestamp deadline that has not passed yet, so each is
+	// rescheduled.
+	process_deadlines {
+		let n in 0 .. T::MaxDeadlinesPerBlock::get();
+
+		let shipper: T::AccountId = account("shipper", 0, SEED);
+		let now = frame_system::Pallet::<T>::block_number();
+		for shipment_id in 0 .. n as u64 {
+			let shipment =
+				Shipment::<T>::new(shipment_id, shipper.clone(), coords(), None, 0, None);
+			Shipments::<T>::insert(shipment_id, shipment);
+			Deadlines::<T>::insert(shipment_id, Deadline::Timestamp(u64::MAX));
+			DeadlineAgenda::<T>::try_append(now, shipment_id).expect("agenda is below its bound");
+		}
+		DeadlineCursor::<T>::put(now);
+	}: {
+		Logistics::<T>::process_deadlines(now, Weight::MAX);
+	}
+	verify {
+		assert_eq!(DeadlineCursor::<T>::get(), Some(now + One::one()));
+		assert_eq!(DeadlineAgenda::<T>::decode_len(now), None);
+	}
+
+	set_condition_ranges {
+		let caller = member::<T>(whitelisted_caller());
+		create_shipment::<T>(&caller, 0);
+	}: _(RawOrigin::Signed(caller), 0, ranges())
+	verify {
+		assert_eq!(AcceptableRanges::<T>::get(0), Some(ranges()));
+	}
+
+	// Every value in the reading is outside its range.
+	record_reading {
+		let caller = member::<T>(whitelisted_caller());
+		create_shipment::<T>(&caller, 0);
+		AcceptableRanges::<T>::insert(0, ranges());
+	}: _(RawOrigin::Signed(caller), 0, reading())
+	verify {
+		assert_eq!(Telemetry::<T>::get(0).breaches, 3);
+	}
+
+	load_shipments {
+		let n in 1 .. T::MaxContainerSize::get();
+
+		let caller = member::<T>(whitelisted_caller());
+		create_shipment::<T>(&caller, 0);
+		let shipment_ids = create_contents::<T>(&caller, 0, n);
+	}: _(RawOrigin::Signed(caller), 0, shipment_ids.clone())
+	verify {
+		assert_eq!(Contents::<T>::get(0), shipment_ids);
+	}
+
+	unload_shipments {
+		let n in 1 .. T::MaxContainerSize::get();
+
+		let caller = member::<T>(whitelisted_caller());
+		let shipment_ids = loaded_container::<T>(&caller, 0, n);
+	}: _(RawOrigin::Signed(caller), 0, shipment_ids.clone())
+	verify {
+		assert!(!Contents::<T>::contains_key(0));
+		for shipment_id in shipment_ids {
+			assert!(!ContainedIn::<T>::contains_key(shipment_id));
+		}
+	}
+
+	register_facility {
+		let caller = member::<T>(whitelisted_caller());
+	}: _(RawOrigin::Signed(caller.clone()), [1; 32], coords(), FacilityKind::Hub)
+	verify {
+		assert_eq!(Facilities::<T>::get(0).map(|f| f.owner), Some(caller));
+		assert_eq!(NextFacilityId::<T>::get(), 1);
+	}
+
+	update_facility {
+		let caller = member::<T>(whitelisted_caller());
+		let facility_id = register_facility::<T>(&caller);
+	}: _(RawOrigin::Signed(caller), facility_id, [1; 32], coords(), FacilityKind::Warehouse)
+	verify {
+		assert_eq!(
+			Facilities::<T>::get(facility_id).map(|f| f.kind),
+			Some(FacilityKind::Warehouse)
+		);
+	}
+
+	set_facility_status {
+		let caller = member::<T>(whitelisted_caller());
+		let facility_id = register_facility::<T>(&caller);
+	}: _(RawOrigin::Signed(caller), facility_id, FacilityStatus::Suspended)
+	verify {
+		assert_eq!(
+			Facilities::<T>::get(facility_id).map(|f| f.status),
+			Some(FacilityStatus::Suspended)
+		);
+	}
+
+	set_facility_geofence {
+		let caller = member::<T>(whitelisted_caller());
+		let facility_id = register_facility::<T>(&caller);
+	}: _(RawOrigin::Signed(caller), facility_id, Some(250))
+	verify {
+		assert_eq!(FacilityGeofence::<T>::get(facility_id), Some(250));
+	}
+
+	set_default_geofence {
+		let origin =
+			T::AdminOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+	}: _<T::RuntimeOrigin>(origin, Some(500))
+	verify {
+		assert_eq!(DefaultGeofenceRadius::<T>::get(), Some(500));
+	}
+
+	register_organization {
+		let origin =
+			T::AdminOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		let organization_id = NextOrganizationId::<T>::get();
+	}: _<T::RuntimeOrigin>(origin, [1; 32], Roles::CARRIER)
+	verify {
+		assert!(Organizations::<T>::contains_key(organization_id));
+	}
+
+	set_organization_roles {
+		let origin =
+			T::AdminOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		let organization_id = Logistics::<T>::do_register_organization([1; 32], Roles::CARRIER)?;
+	}: _<T::RuntimeOrigin>(origin, organization_id, Roles::all())
+	verify {
+		assert_eq!(Organizations::<T>::get(organization_id).map(|o| o.roles), Some(Roles::all()));
+	}
+
+	add_member {
+		let origin =
+			T::AdminOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		let organization_id = Logistics::<T>::do_register_organization([1; 32], Roles::CARRIER)?;
+		let who: T::AccountId = account("member", 0, SEED);
+	}: _<T::RuntimeOrigin>(origin, organization_id, who.clone())
+	verify {
+		assert_eq!(Members::<T>::get(who), Some(organization_id));
+	}
+
+	remove_member {
+		let origin =
+			T::AdminOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		let who = member::<T>(account("member", 0, SEED));
+	}: _<T::RuntimeOrigin>(origin, who.clone())
+	verify {
+		assert!(!Members::<T>::contains_key(who));
+	}
+
+	file_claim {
+		let caller = member::<T>(whitelisted_caller());
+		let carrier = member::<T>(account("carrier", 0, SEED));
+		shipment_with_carrier::<T>(&caller, &carrier, 0);
+		// The accused is found at the very end of a full custody history.
+		let history = CustodyHistory::<T>::get(0).len() as u32;
+		add_custodians::<T>(0, T::MaxCustodyHops::get() - history - 1);
+		let accused: T::AccountId = account("accused", 0, SEED);
+		fill_custody_history::<T>(0, &accused, T::MaxCustodyHops::get());
+	}: _(RawOrigin::Signed(caller), 0, accused.clone(), ClaimKind::Damaged, [1; 32])
+	verify {
+		assert_eq!(Claims::<T>::get(0).map(|c| c.accused), Some(accused));
+	}
+
+	respond_to_claim {
+		let shipper = member::<T>(account("shipper", 0, SEED));
+		let caller = member::<T>(whitelisted_caller());
+		fund::<T>(&caller);
+		claimed_shipment::<T>(&shipper, &caller, 0);
+	}: _(RawOrigin::Signed(caller), 0, [2; 32])
+	verify {
+		assert_eq!(Claims::<T>::get(0).and_then(|c| c.response), Some([2; 32]));
+	}
+
+	// Upholding an answered claim against a shipment on its way touches the most state.
+	resolve_claim {
+		let origin =
+			T::ArbiterOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		let shipper = member::<T>(account("shipper", 0, SEED));
+		let carrier = member::<T>(account("carrier", 0, SEED));
+		fund::<T>(&carrier);
+		claimed_shipment::<T>(&shipper, &carrier, 0);
+		Logistics::<T>::respond_to_claim(RawOrigin::Signed(carrier).into(), 0, [2; 32])?;
+	}: _<T::RuntimeOrigin>(origin, 0, true)
+	verify {
+		assert_eq!(Shipments::<T>::get(0).map(|s| s.status), Some(ShipmentStatus::Lost));
+		assert!(!Claims::<T>::contains_key(0));
+	}
+
+	register_device {
+		let caller = member::<T>(whitelisted_caller());
+		let device = T::DeviceId::generate_pair(None);
+	}: _(RawOrigin::Signed(caller.clone()), device.clone())
+	verify {
+		assert!(Devices::<T>::contains_key(&device));
+		assert_eq!(Members::<T>::get(&caller).map(DeviceCounts::<T>::get), Some(1));
+	}
+
+	deregister_device {
+		let caller = member::<T>(whitelisted_caller());
+		let device = T::DeviceId::generate_pair(None);
+		Logistics::<T>::register_device(RawOrigin::Signed(caller.clone()).into(), device.clone())?;
+	}: _(RawOrigin::Signed(caller), device.clone())
+	verify {
+		assert!(!Devices::<T>::contains_key(&device));
+	}
+
+	// Same as `update_status`, scanned by a device of the holder's organization.
+	submit_scan {
+		let c in 0 .. T::MaxContainerSize::get();
+
+		let shipper = member::<T>(account("shipper", 0, SEED));
+		let holder = member::<T>(account("holder", 0, SEED));
+		let shipment_ids = loaded_container::<T>(&shipper, 0, c);
+		Logistics::<T>::offer_handoff(RawOrigin::Signed(shipper).into(), 0, holder.clone())?;
+		Logistics::<T>::shipment_received(RawOrigin::Signed(holder.clone()).into(), 0, coords())?;
+		let scan = Submission::Scan { shipment_id: 0, status: ShipmentStatus::Lost };
+		let (payload, signature) = device_payload::<T>(&holder, scan);
+	}: submit_from_device(RawOrigin::None, payload, signature)
+	verify {
+		assert_eq!(Shipments::<T>::get(0).map(|s| s.status), Some(ShipmentStatus::Lost));
+		for shipment_id in shipment_ids {
+			assert_eq!(
+				Shipments::<T>::get(shipment_id).map(|s| s.status),
+				Some(ShipmentStatus::Lost)
+			);
+		}
+	}
+
+	// Same as `record_reading`, read by a device of the holder's organization.
+	submit_reading {
+		let holder = member::<T>(account("holder", 0, SEED));
+		create_shipment::<T>(&holder, 0);
+		AcceptableRanges::<T>::insert(0, ranges());
+		let submission = Submission::Reading { shipment_id: 0, reading: reading() };
+		let (payload, signature) = device_payload::<T>(&holder, submission);
+	}: submit_from_device(RawOrigin::None, payload, signature)
 	verify {
-		assert_eq!(Something::<T>::get(), Some(s));
+		assert_eq!(Telemetry::<T>::get(0).breaches, 3);
 	}
 
 	impl_benchmark_test_suite!(Logistics, crate::mock::new_test_ext(), crate::mock::Test);
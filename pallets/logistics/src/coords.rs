@@ -0,0 +1,107 @@
+//! Geodetic coordinates used to record where a shipment changed hands.
+
+use codec::{Decode, Encode, MaxEncodedLen};
+use scale_info::TypeInfo;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+use sp_runtime::{traits::IntegerSquareRoot, RuntimeDebug};
+
+/// Number of fixed-point units in one degree.
+pub const MICRODEGREES_PER_DEGREE: i32 = 1_000_000;
+
+/// Largest valid latitude, in microdegrees.
+pub const MAX_LATITUDE: i32 = 90 * MICRODEGREES_PER_DEGREE;
+
+/// Largest valid longitude, in microdegrees.
+pub const MAX_LONGITUDE: i32 = 180 * MICRODEGREES_PER_DEGREE;
+
+/// Length of one microdegree of arc on the mean Earth radius (6 371 008.8 m), in micrometres.
+const MICROMETRES_PER_MICRODEGREE: u128 = 111_195;
+
+/// A WGS 84 position.
+///
+/// Latitude and longitude are fixed-point microdegrees (about 11 cm of arc), positive north and
+/// east. Values built with [`Coords::new`] are always in range; values decoded from an extrinsic
+/// are not, so calls check them with [`Coords::is_valid`].
+#[derive(Clone, Eq, PartialEq, RuntimeDebug, Encode, Decode, TypeInfo, MaxEncodedLen)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct Coords {
+	lat: i32,
+	lng: i32,
+	altitude: Option<i32>,
+	accuracy: Option<u32>,
+}
+
+impl Coords {
+	/// A position at `lat`, `lng` microdegrees, or `None` if either is out of range.
+	pub fn new(lat: i32, lng: i32) -> Option<Self> {
+		let coords = Coords { lat, lng, altitude: None, accuracy: None };
+		coords.is_valid().then_some(coords)
+	}
+
+	/// The same position at `metres` above the WGS 84 ellipsoid.
+	pub fn with_altitude(self, metres: i32) -> Self {
+		Coords { altitude: Some(metres), ..self }
+	}
+
+	/// The same position, known to within a radius of `metres`.
+	pub fn with_accuracy(self, metres: u32) -> Self {
+		Coords { accuracy: Some(metres), ..self }
+	}
+
+	/// Latitude in microdegrees, in `-90°..=90°`.
+	pub fn lat(&self) -> i32 {
+		self.lat
+	}
+
+	/// Longitude in microdegrees, in `-180°..=180°`.
+	pub fn lng(&self) -> i32 {
+		self.lng
+	}
+
+	/// Height above the WGS 84 ellipsoid in metres, if known.
+	pub fn altitude(&self) -> Option<i32> {
+		self.altitude
+	}
+
+	/// Radius in metres the true position lies within, if known.
+	pub fn accuracy(&self) -> Option<u32> {
+		self.accuracy
+	}
+
+	/// Whether latitude and longitude are within their valid ranges.
+	pub fn is_valid(&self) -> bool {
+		(-MAX_LATITUDE..=MAX_LATITUDE).contains(&self.lat) &&
+			(-MAX_LONGITUDE..=MAX_LONGITUDE).contains(&self.lng)
+	}
+
+	/// Ground distance to `other` in metres, ignoring altitude.
+	///
+	/// Uses the equirectangular approximation, which is within 0.5% of the great-circle distance
+	/// for the few hundred kilometres a single hop covers and degrades gracefully beyond that.
+	pub fn distance_to(&self, other: &Coords) -> u32 {
+		let d_lat = (self.lat as i64 - other.lat as i64).unsigned_abs() as u128;
+		let mut d_lng = (self.lng as i64 - other.lng as i64).unsigned_abs() as u128;
+		// Take the short way round across the antimeridian.
+		let full_turn = 2 * MAX_LONGITUDE as u128;
+		if d_lng > full_turn / 2 {
+			d_lng = full_turn - d_lng;
+		}
+
+		let mean_lat = (self.lat as i64 + other.lat as i64) / 2;
+		let x = d_lng * cos_micro(mean_lat) / MICRODEGREES_PER_DEGREE as u128;
+		let arc = (x * x + d_lat * d_lat).integer_sqrt();
+
+		(arc * MICROMETRES_PER_MICRODEGREE / 1_000_000).min(u32::MAX as u128) as u32
+	}
+}
+
+/// Cosine of an angle in microdegrees within `-90°..=90°`, scaled by one million.
+///
+/// Bhaskara I's rational approximation, accurate to within 0.002 and exact at 0° and 90°.
+fn cos_micro(angle: i64) -> u128 {
+	let x = angle.unsigned_abs().min(MAX_LATITUDE as u64) as u128;
+	let half_turn_sq = (MAX_LATITUDE as u128) * (MAX_LATITUDE as u128) * 4;
+	let x2 = x * x;
+	(half_turn_sq - 4 * x2) * MICRODEGREES_PER_DEGREE as u128 / (half_turn_sq + x2)
+}
@@ -0,0 +1,74 @@
+//! What a shipment was handed over with at its destination.
+
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::{
+	traits::Get, BoundedVec, CloneNoBound, EqNoBound, PartialEqNoBound, RuntimeDebugNoBound,
+};
+use scale_info::TypeInfo;
+use sp_runtime::{app_crypto::sr25519, traits::Verify, RuntimeDebug};
+use sp_std::vec::Vec;
+
+/// Hash of a piece of evidence kept off chain, such as a photo of the shipment on the doorstep
+/// or an image of a handwritten signature.
+pub type EvidenceHash = [u8; 32];
+
+/// Prefix of the message a consignee signs, so that the signature cannot stand for anything
+/// else.
+const CONTEXT: &[u8] = b"logistics/delivery";
+
+/// Key a consignee signs for deliveries made to them with.
+pub type ConsigneeKey = sr25519::Public;
+
+/// Who vouches for a delivery.
+#[derive(Clone, Copy, Eq, PartialEq, RuntimeDebug, Encode, Decode, TypeInfo, MaxEncodedLen)]
+pub enum DeliveryClass {
+	/// The consignee signed for the shipment.
+	ConfirmedByConsignee,
+	/// The courier alone says the shipment was handed over.
+	CourierAsserted,
+}
+
+/// A consignee's signature acknowledging they received a shipment.
+#[derive(Clone, Eq, PartialEq, RuntimeDebug, Encode, Decode, TypeInfo)]
+pub struct ConsigneeSignature<BlockNumber> {
+	/// Key the consignee signed with.
+	pub signer: sr25519::Public,
+	/// Block the consignee signed at.
+	pub block: BlockNumber,
+	/// Signature over [`ConsigneeSignature::message`].
+	pub signature: sr25519::Signature,
+}
+
+impl<BlockNumber: Encode> ConsigneeSignature<BlockNumber> {
+	/// What a consignee signs to acknowledge receiving `shipment_id` at `block`: the SCALE
+	/// encoding of `(b"logistics/delivery", shipment_id, block)`.
+	pub fn message(shipment_id: u64, block: &BlockNumber) -> Vec<u8> {
+		(CONTEXT, shipment_id, block).encode()
+	}
+
+	/// Whether this is a valid acknowledgement of receiving `shipment_id`.
+	pub fn is_valid_for(&self, shipment_id: u64) -> bool {
+		self.signature
+			.verify(&Self::message(shipment_id, &self.block)[..], &self.signer)
+	}
+}
+
+/// How a shipment was handed over at its destination, holding at most `MaxEvidence` evidence
+/// hashes.
+#[derive(
+	CloneNoBound,
+	EqNoBound,
+	PartialEqNoBound,
+	RuntimeDebugNoBound,
+	Encode,
+	Decode,
+	TypeInfo,
+	MaxEncodedLen,
+)]
+#[scale_info(skip_type_params(MaxEvidence))]
+pub struct ProofOfDelivery<MaxEvidence: Get<u32>> {
+	pub class: DeliveryClass,
+	/// Key the consignee signed for the shipment with, if they did.
+	pub consignee: Option<sr25519::Public>,
+	pub evidence: BoundedVec<EvidenceHash, MaxEvidence>,
+}
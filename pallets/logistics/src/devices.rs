@@ -0,0 +1,68 @@
+//! Scanners and trackers that report on their own, signing what they submit with a key of their
+//! own rather than paying for transactions from an account.
+
+use codec::{Decode, Encode, MaxEncodedLen};
+use scale_info::TypeInfo;
+use sp_runtime::{KeyTypeId, RuntimeDebug};
+use sp_std::vec::Vec;
+
+use crate::{telemetry::Reading, ShipmentStatus};
+
+/// Key type of device keys.
+pub const KEY_TYPE: KeyTypeId = KeyTypeId(*b"lgdv");
+
+/// Prefix of the message a device signs, so that the signature cannot stand for anything else.
+const CONTEXT: &[u8] = b"logistics/device";
+
+/// Device keys for runtimes, see `Config::DeviceId`.
+pub mod sr25519 {
+	mod app_sr25519 {
+		use sp_runtime::app_crypto::{app_crypto, sr25519};
+		app_crypto!(sr25519, crate::devices::KEY_TYPE);
+	}
+
+	/// Public key a device is registered under.
+	pub type DeviceId = app_sr25519::Public;
+	/// Signature of a device over a [`DevicePayload::message`](super::DevicePayload::message).
+	pub type DeviceSignature = app_sr25519::Signature;
+}
+
+/// What a device saw.
+#[derive(Clone, Eq, PartialEq, RuntimeDebug, Encode, Decode, TypeInfo, MaxEncodedLen)]
+pub enum Submission {
+	/// The shipment was scanned into `status`, as with `update_status`.
+	Scan { shipment_id: u64, status: ShipmentStatus },
+	/// The shipment's sensors read `reading`, as with `record_reading`.
+	Reading { shipment_id: u64, reading: Reading },
+}
+
+/// A submission as signed by the device that made it.
+#[derive(Clone, Eq, PartialEq, RuntimeDebug, Encode, Decode, TypeInfo, MaxEncodedLen)]
+pub struct DevicePayload<DeviceId> {
+	pub device: DeviceId,
+	/// Number of submissions the device made before this one, so that each payload can only
+	/// be submitted once and in order.
+	pub nonce: u64,
+	pub submission: Submission,
+}
+
+impl<DeviceId: Encode> DevicePayload<DeviceId> {
+	/// What a device signs to submit the payload to the chain with `genesis_hash`: the SCALE
+	/// encoding of `(b"logistics/device", genesis_hash, payload)`.
+	pub fn message<Hash: Encode>(&self, genesis_hash: &Hash) -> Vec<u8> {
+		(CONTEXT, genesis_hash, self).encode()
+	}
+}
+
+/// How much a device has submitted.
+#[derive(
+	Clone, Copy, Default, Eq, PartialEq, RuntimeDebug, Encode, Decode, TypeInfo, MaxEncodedLen,
+)]
+pub struct DeviceUsage<BlockNumber> {
+	/// Nonce the device's next payload has to carry.
+	pub nonce: u64,
+	/// First block of the rate limiting window `submissions` were made in.
+	pub window_start: BlockNumber,
+	/// Number of submissions made in that window.
+	pub submissions: u32,
+}
@@ -12,12 +12,49 @@ mod tests;
 #[cfg(feature = "runtime-benchmarks")]
 mod benchmarking;
 
+pub mod coords;
+pub mod delivery;
+pub mod devices;
+pub mod manifest;
+pub mod migrations;
+pub mod roles;
+pub mod telemetry;
+pub mod weights;
+pub use coords::Coords;
+pub use manifest::Manifest;
+pub use roles::Roles;
+pub use weights::WeightInfo;
+
+pub(crate) const LOG_TARGET: &str = "runtime::logistics";
+
 #[frame_support::pallet]
 pub mod pallet {
-	use frame_support::pallet_prelude::*;
+	use frame_support::{
+		pallet_prelude::*,
+		storage::with_storage_layer,
+		traits::{BalanceStatus, Currency, Imbalance, OnUnbalanced, ReservableCurrency, UnixTime},
+	};
 	use frame_system::pallet_prelude::*;
+	use sp_runtime::{
+		traits::{One, SaturatedConversion, Saturating, Zero},
+		RuntimeAppPublic,
+	};
+	use sp_std::vec::Vec;
+
+	use crate::{
+		delivery::{
+			ConsigneeKey, ConsigneeSignature, DeliveryClass, EvidenceHash, ProofOfDelivery,
+		},
+		devices::{DevicePayload, DeviceUsage, Submission},
+		telemetry::{Aggregates, Condition, ConditionRanges, Reading},
+		Coords, Manifest, Roles, WeightInfo, LOG_TARGET,
+	};
+
+	/// The current storage version.
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(8);
 
 	#[pallet::pallet]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(_);
 
 	// Configure the pallet by specifying the parameters and types on which it depends.
@@ -25,63 +62,737 @@ pub mod pallet {
 	pub trait Config: frame_system::Config {
 		/// Because this pallet emits events, it depends on the runtime's definition of an event.
 		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+		/// Weight information for extrinsics and hooks in this pallet.
+		type WeightInfo: WeightInfo;
+
+		/// Number of blocks a custody handoff offer stays open before it lapses.
+		#[pallet::constant]
+		type HandoffTimeout: Get<Self::BlockNumber>;
+
+		/// Source of wall-clock time for custody records.
+		type TimeProvider: UnixTime;
+
+		/// Maximum number of custody hops kept for a single shipment, and of accounts whose hops
+		/// made way for newer ones. See `CustodyHistory`.
+		#[pallet::constant]
+		type MaxCustodyHops: Get<u32>;
+
+		/// Maximum number of shipments that can be loaded in a single container.
+		#[pallet::constant]
+		type MaxContainerSize: Get<u32>;
+
+		/// Maximum number of shipments a single shipment can be split into.
+		#[pallet::constant]
+		type MaxSplitParts: Get<u32>;
+
+		/// Maximum number of evidence hashes a shipment can be delivered with.
+		#[pallet::constant]
+		type MaxEvidence: Get<u32>;
+
+		/// Number of blocks a delivered shipment stays queryable before it becomes eligible for
+		/// pruning. Pruning itself only happens with spare block weight, see `on_idle`.
+		#[pallet::constant]
+		type DeliveredRetention: Get<Self::BlockNumber>;
+
+		/// Origin allowed to change network-wide settings such as the default geofence.
+		type AdminOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+		/// Maximum number of lines in a shipment's manifest.
+		#[pallet::constant]
+		type MaxManifestItems: Get<u32>;
+
+		/// Currency carriage fees and storage deposits are paid in.
+		type Currency: ReservableCurrency<Self::AccountId>;
+
+		/// Who is paid the carriage fee once a shipment is delivered.
+		#[pallet::constant]
+		type FeeDistribution: Get<FeeDistribution>;
+
+		/// Part of the storage deposit every shipment holds regardless of its size.
+		#[pallet::constant]
+		type DepositBase: Get<BalanceOf<Self>>;
+
+		/// Part of the storage deposit held for each byte a shipment takes up in storage.
+		#[pallet::constant]
+		type DepositPerByte: Get<BalanceOf<Self>>;
+
+		/// Number of blocks a shipment that is not delivered can go without changing hands or
+		/// being scanned before anyone can reap it and have its deposit slashed.
+		#[pallet::constant]
+		type AbandonmentTimeout: Get<Self::BlockNumber>;
+
+		/// Handler for the deposits slashed from abandoned shipments.
+		type Slash: OnUnbalanced<NegativeImbalanceOf<Self>>;
+
+		/// Origin that resolves claims.
+		type ArbiterOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+		/// Bond reserved from whoever files or answers a claim. The side the arbiter rules
+		/// against forfeits it to the other.
+		#[pallet::constant]
+		type ClaimBond: Get<BalanceOf<Self>>;
+
+		/// Maximum number of deadlines falling due in a single block. Deadlines that do not fit
+		/// are moved to one of the next blocks.
+		#[pallet::constant]
+		type MaxDeadlinesPerBlock: Get<u32>;
+
+		/// Expected time between blocks in milliseconds, used to schedule deadlines given as a
+		/// timestamp.
+		#[pallet::constant]
+		type ExpectedBlockTime: Get<u64>;
+
+		/// Public key of a device submitting scans and readings without an account, e.g.
+		/// [`crate::devices::sr25519::DeviceId`].
+		type DeviceId: Member + Parameter + RuntimeAppPublic + MaxEncodedLen;
+
+		/// Maximum number of submissions a device can make per `DeviceRateWindow`.
+		#[pallet::constant]
+		type MaxDeviceSubmissions: Get<u32>;
+
+		/// Maximum number of devices an organization can have registered at once, which bounds
+		/// the unsigned submissions its members can have included for free.
+		#[pallet::constant]
+		type MaxDevicesPerOrganization: Get<u32>;
+
+		/// Length in blocks of the windows device submissions are rate limited over.
+		#[pallet::constant]
+		type DeviceRateWindow: Get<Self::BlockNumber>;
+
+		/// Transaction pool priority of device scans. Readings get half of it, so that scans
+		/// are not crowded out by telemetry.
+		#[pallet::constant]
+		type DeviceSubmissionPriority: Get<TransactionPriority>;
+
+		/// Number of readings in each window telemetry aggregates are kept over. A shipment's
+		/// aggregates cover between one and two windows of its latest readings.
+		#[pallet::constant]
+		type TelemetryWindow: Get<u32>;
 	}
 
-	#[derive(Clone, Eq, PartialEq, RuntimeDebug, Encode, Decode, TypeInfo, MaxEncodedLen)]
-	pub struct Coords {
-		lat: u32,
-		lng: u32,
+	pub type DevicePayloadOf<T> = DevicePayload<<T as Config>::DeviceId>;
+	pub type DeviceSignatureOf<T> = <<T as Config>::DeviceId as RuntimeAppPublic>::Signature;
+	pub type DeviceUsageOf<T> = DeviceUsage<<T as frame_system::Config>::BlockNumber>;
+
+	pub type BalanceOf<T> =
+		<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+	pub type NegativeImbalanceOf<T> = <<T as Config>::Currency as Currency<
+		<T as frame_system::Config>::AccountId,
+	>>::NegativeImbalance;
+
+	/// How the carriage fee of a delivered shipment is shared out.
+	#[derive(Clone, Copy, Eq, PartialEq, RuntimeDebug, Encode, Decode, TypeInfo, MaxEncodedLen)]
+	pub enum FeeDistribution {
+		/// The carrier that delivers the shipment gets the whole fee.
+		DeliveringCarrier,
+		/// Every custodian after the shipper gets an equal share, however many legs they carried,
+		/// with any remainder going to the delivering carrier.
+		PerLeg,
+	}
+
+	/// Where a shipment is in its lifecycle.
+	#[derive(Clone, Copy, Eq, PartialEq, RuntimeDebug, Encode, Decode, TypeInfo, MaxEncodedLen)]
+	pub enum ShipmentStatus {
+		/// Registered by the shipper, not yet handed to a carrier.
+		Created,
+		/// With a carrier between facilities.
+		InTransit,
+		/// Scanned in at a warehouse, hub or depot.
+		AtFacility,
+		/// On the final leg to the destination.
+		OutForDelivery,
+		/// A delivery attempt was made and did not succeed.
+		DeliveryFailed,
+		/// Handed over at the destination.
+		Delivered,
+		/// Brought back to the shipper.
+		Returned,
+		/// Declared lost by its custodian, or found lost on a claim.
+		Lost,
+		/// Withdrawn by the shipper before it left their hands.
+		Cancelled,
+		/// Found damaged on a claim.
+		Damaged,
+		/// Broken up into several shipments, which carry on in its place.
+		Split,
+		/// Refused by its consignee while it had no origin facility to go back to, or after a
+		/// courier asserted its delivery.
+		Rejected,
+	}
+
+	impl ShipmentStatus {
+		/// Whether the shipment has reached the end of its lifecycle and can no longer change,
+		/// other than by a claim against it being upheld.
+		pub fn is_final(&self) -> bool {
+			use ShipmentStatus::*;
+
+			matches!(self, Delivered | Returned | Lost | Cancelled | Damaged | Split | Rejected)
+		}
+
+		/// Whether moving from this status to `next` is a legal lifecycle transition.
+		///
+		/// Accepting a handoff always moves a shipment (back) into `InTransit`, so every
+		/// non-final status can transition there.
+		pub fn can_transition_to(&self, next: &ShipmentStatus) -> bool {
+			use ShipmentStatus::*;
+
+			match (self, next) {
+				(Created, InTransit | Cancelled) => true,
+				(
+					InTransit,
+					InTransit | AtFacility | OutForDelivery | Delivered | Returned | Lost | Split |
+					Rejected,
+				) => true,
+				(
+					AtFacility,
+					InTransit | OutForDelivery | Delivered | Returned | Lost | Split | Rejected,
+				) => true,
+				(
+					OutForDelivery,
+					InTransit | DeliveryFailed | Delivered | Returned | Lost | Rejected,
+				) => true,
+				(DeliveryFailed, InTransit | AtFacility | OutForDelivery | Lost | Rejected) => true,
+				_ => false,
+			}
+		}
+
+		/// Roles allowed to move a shipment into this status.
+		pub fn set_by(&self) -> Roles {
+			use ShipmentStatus::*;
+
+			match self {
+				Created | Returned | Cancelled => Roles::SHIPPER,
+				InTransit | Lost | Split => Roles::HANDLERS,
+				AtFacility => Roles::WAREHOUSE_OPERATOR | Roles::CUSTOMS,
+				OutForDelivery | DeliveryFailed | Delivered => Roles::CARRIER,
+				// Only ever set by resolving a claim, or by the consignee rejecting the shipment.
+				Damaged | Rejected => Roles::empty(),
+			}
+		}
 	}
 
 	#[derive(Clone, Eq, PartialEq, RuntimeDebug, Encode, Decode, TypeInfo, MaxEncodedLen)]
 	#[scale_info(skip_type_params(T))]
 	pub struct Shipment<T: Config> {
-		id: u64,
-		shipped_by: T::AccountId,
-		received_by: T::AccountId,
-		received_at: Coords,
-		received_on: T::BlockNumber,
-		destination: u64,
-		delivered: bool,
+		pub id: u64,
+		pub shipped_by: T::AccountId,
+		pub received_by: T::AccountId,
+		pub received_at: Coords,
+		pub received_on: T::BlockNumber,
+		pub destination: FacilityId,
+		pub status: ShipmentStatus,
+		/// Facility the shipment left from, which it can be returned to.
+		pub origin: Option<FacilityId>,
+		/// Whether the shipment is on its way back to `destination`, which was its origin.
+		pub returning: bool,
+		/// Account the shipment is meant for, which alone can confirm its receipt or reject it.
+		/// `None` for shipments created before consignees were recorded.
+		pub consignee: Option<T::AccountId>,
 	}
 
 	impl<T: Config> Shipment<T> {
 		pub fn new(
 			shipment_id: u64,
 			shipped_by: T::AccountId,
-			received_by: T::AccountId,
 			received_at: Coords,
-			destination: u64,
+			origin: Option<FacilityId>,
+			destination: FacilityId,
+			consignee: Option<T::AccountId>,
 		) -> Self {
 			Shipment {
 				id: shipment_id,
-				shipped_by,
-				received_by,
+				shipped_by: shipped_by.clone(),
+				received_by: shipped_by,
 				received_at,
 				received_on: frame_system::Pallet::<T>::block_number(),
 				destination,
-				delivered: false,
+				status: ShipmentStatus::Created,
+				origin,
+				returning: false,
+				consignee,
+			}
+		}
+
+		/// Move the shipment to `next`, returning the status it left.
+		pub fn transition(&mut self, next: ShipmentStatus) -> Result<ShipmentStatus, Error<T>> {
+			ensure!(!self.status.is_final(), Error::<T>::ShipmentNotInTransit);
+			ensure!(self.status.can_transition_to(&next), Error::<T>::InvalidStatusTransition);
+
+			Ok(core::mem::replace(&mut self.status, next))
+		}
+	}
+
+	pub type ManifestOf<T> = Manifest<<T as Config>::MaxManifestItems>;
+
+	pub type ConsigneeSignatureOf<T> = ConsigneeSignature<<T as frame_system::Config>::BlockNumber>;
+	pub type ProofOfDeliveryOf<T> = ProofOfDelivery<<T as Config>::MaxEvidence>;
+
+	/// One of the shipments a shipment is split into.
+	#[derive(Clone, Eq, PartialEq, RuntimeDebug, Encode, Decode, TypeInfo, MaxEncodedLen)]
+	#[scale_info(skip_type_params(T))]
+	pub struct SplitPart<T: Config> {
+		/// Id of the new shipment.
+		pub shipment_id: u64,
+		pub destination: FacilityId,
+		/// What this part of the split shipment contains.
+		pub manifest: ManifestOf<T>,
+	}
+
+	/// An open offer from the current holder to hand a shipment over to `to`.
+	#[derive(Clone, Eq, PartialEq, RuntimeDebug, Encode, Decode, TypeInfo, MaxEncodedLen)]
+	#[scale_info(skip_type_params(T))]
+	pub struct Handoff<T: Config> {
+		pub from: T::AccountId,
+		pub to: T::AccountId,
+		pub expires_on: T::BlockNumber,
+	}
+
+	/// One hop in a shipment's chain of custody.
+	#[derive(Clone, Eq, PartialEq, RuntimeDebug, Encode, Decode, TypeInfo, MaxEncodedLen)]
+	pub struct CustodyRecord<AccountId, BlockNumber> {
+		/// Account that took custody.
+		pub holder: AccountId,
+		/// Where custody was taken.
+		pub coords: Coords,
+		/// Block in which custody was taken.
+		pub block: BlockNumber,
+		/// Unix time in milliseconds at which custody was taken.
+		pub timestamp: u64,
+	}
+
+	pub type CustodyRecordOf<T> = CustodyRecord<
+		<T as frame_system::Config>::AccountId,
+		<T as frame_system::Config>::BlockNumber,
+	>;
+
+	/// When a shipment was promised to be delivered by.
+	#[derive(Clone, Copy, Eq, PartialEq, RuntimeDebug, Encode, Decode, TypeInfo, MaxEncodedLen)]
+	pub enum Deadline<BlockNumber> {
+		/// Delivered in this block or earlier.
+		Block(BlockNumber),
+		/// Delivered at this Unix time in milliseconds or earlier.
+		Timestamp(u64),
+	}
+
+	impl<BlockNumber: PartialOrd> Deadline<BlockNumber> {
+		/// Whether the deadline has passed in `block`, at Unix time `timestamp`.
+		pub fn has_passed(&self, block: BlockNumber, timestamp: u64) -> bool {
+			match self {
+				Deadline::Block(due) => block > *due,
+				Deadline::Timestamp(due) => timestamp > *due,
+			}
+		}
+	}
+
+	pub type DeadlineOf<T> = Deadline<<T as frame_system::Config>::BlockNumber>;
+
+	/// A delivery that missed its deadline.
+	#[derive(Clone, Eq, PartialEq, RuntimeDebug, Encode, Decode, TypeInfo, MaxEncodedLen)]
+	pub struct SlaBreach<BlockNumber> {
+		/// The deadline that was missed.
+		pub deadline: Deadline<BlockNumber>,
+		/// Block in which the shipment was delivered.
+		pub delivered_on: BlockNumber,
+		/// Unix time in milliseconds at which the shipment was delivered.
+		pub delivered_at: u64,
+	}
+
+	pub type SlaBreachOf<T> = SlaBreach<<T as frame_system::Config>::BlockNumber>;
+
+	/// What went wrong with a shipment, according to a claim.
+	#[derive(Clone, Copy, Eq, PartialEq, RuntimeDebug, Encode, Decode, TypeInfo, MaxEncodedLen)]
+	pub enum ClaimKind {
+		Lost,
+		Damaged,
+	}
+
+	impl From<ClaimKind> for ShipmentStatus {
+		fn from(kind: ClaimKind) -> Self {
+			match kind {
+				ClaimKind::Lost => ShipmentStatus::Lost,
+				ClaimKind::Damaged => ShipmentStatus::Damaged,
 			}
 		}
 	}
 
+	/// A dispute over a shipment, awaiting the arbiter.
+	#[derive(Clone, Eq, PartialEq, RuntimeDebug, Encode, Decode, TypeInfo, MaxEncodedLen)]
+	pub struct Claim<AccountId, Balance, BlockNumber> {
+		/// Account that filed the claim.
+		pub claimant: AccountId,
+		/// Custodian held responsible.
+		pub accused: AccountId,
+		pub kind: ClaimKind,
+		/// Hash of the claimant's evidence, which is kept off chain.
+		pub evidence: [u8; 32],
+		/// Hash of the accused's response, once they answered.
+		pub response: Option<[u8; 32]>,
+		/// Bond each side puts up.
+		pub bond: Balance,
+		pub filed_on: BlockNumber,
+	}
+
+	pub type ClaimOf<T> = Claim<
+		<T as frame_system::Config>::AccountId,
+		BalanceOf<T>,
+		<T as frame_system::Config>::BlockNumber,
+	>;
+
+	/// Identifier of a registered facility.
+	pub type FacilityId = u64;
+
+	/// What a facility is used for.
+	#[derive(Clone, Copy, Eq, PartialEq, RuntimeDebug, Encode, Decode, TypeInfo, MaxEncodedLen)]
+	pub enum FacilityKind {
+		/// Stores goods for longer periods.
+		Warehouse,
+		/// Sorts and transfers shipments between carriers.
+		Hub,
+		/// Dispatches shipments on their final leg.
+		Depot,
+	}
+
+	/// Whether a facility is currently handling shipments.
+	#[derive(Clone, Copy, Eq, PartialEq, RuntimeDebug, Encode, Decode, TypeInfo, MaxEncodedLen)]
+	pub enum FacilityStatus {
+		/// Open for business.
+		Operational,
+		/// Temporarily not handling shipments.
+		Suspended,
+		/// Permanently closed; no new shipments can be addressed to it.
+		Closed,
+	}
+
+	/// A warehouse, hub or depot shipments can be addressed to.
+	#[derive(Clone, Eq, PartialEq, RuntimeDebug, Encode, Decode, TypeInfo, MaxEncodedLen)]
+	pub struct Facility<AccountId> {
+		/// Account that registered the facility and may update it.
+		pub owner: AccountId,
+		/// Hash of the facility's human-readable name, which is kept off chain.
+		pub name_hash: [u8; 32],
+		/// Where the facility is.
+		pub coords: Coords,
+		pub kind: FacilityKind,
+		pub status: FacilityStatus,
+	}
+
+	pub type FacilityOf<T> = Facility<<T as frame_system::Config>::AccountId>;
+
+	/// Identifier of a registered organization.
+	pub type OrganizationId = u64;
+
+	/// A company or agency taking part in the logistics network.
+	#[derive(Clone, Eq, PartialEq, RuntimeDebug, Encode, Decode, TypeInfo, MaxEncodedLen)]
+	pub struct Organization {
+		/// Hash of the organization's legal name, which is kept off chain.
+		pub name_hash: [u8; 32],
+		/// What the organization's members are allowed to do.
+		pub roles: Roles,
+	}
+
+	/// Bounds of the live entries of a storage-backed FIFO queue.
+	#[derive(
+		Clone, Copy, Default, Eq, PartialEq, RuntimeDebug, Encode, Decode, TypeInfo, MaxEncodedLen,
+	)]
+	pub struct QueueCursor {
+		/// Position of the oldest entry still in the queue.
+		pub head: u64,
+		/// Position the next entry will be written to.
+		pub tail: u64,
+	}
+
+	/// Number of blocks after its due block a deadline can be moved to when agendas are full.
+	const MAX_AGENDA_SHIFT: u32 = 10;
+
 	// The pallet's runtime storage items.
 	// https://docs.substrate.io/main-docs/build/runtime-storage/
 	#[pallet::storage]
 	pub type Shipments<T> = CountedStorageMap<_, Blake2_128Concat, u64, Shipment<T>>;
 
 	#[pallet::storage]
-	pub type DeliveredLog<T> = StorageValue<_, BoundedVec<u64, ConstU32<100>>, ValueQuery>;
+	pub type PendingHandoffs<T> = StorageMap<_, Blake2_128Concat, u64, Handoff<T>>;
+
+	/// Shipments loaded in each container. A container is a shipment like any other, which the
+	/// shipments it carries move with until they are unloaded.
+	#[pallet::storage]
+	pub type Contents<T: Config> =
+		StorageMap<_, Blake2_128Concat, u64, BoundedVec<u64, T::MaxContainerSize>, ValueQuery>;
+
+	/// Container each loaded shipment is in.
+	#[pallet::storage]
+	pub type ContainedIn<T> = StorageMap<_, Blake2_128Concat, u64, u64>;
+
+	/// Ranges the shipper requires each shipment to be kept within.
+	#[pallet::storage]
+	pub type AcceptableRanges<T> = StorageMap<_, Blake2_128Concat, u64, ConditionRanges>;
+
+	/// Rolling aggregates over the latest sensor readings reported for each shipment.
+	#[pallet::storage]
+	pub type Telemetry<T> = StorageMap<_, Blake2_128Concat, u64, Aggregates, ValueQuery>;
+
+	/// Shipment each shipment created by a split was split from.
+	#[pallet::storage]
+	pub type SplitFrom<T> = StorageMap<_, Blake2_128Concat, u64, u64>;
+
+	/// How each delivered or returned shipment was handed over.
+	#[pallet::storage]
+	pub type Deliveries<T: Config> = StorageMap<_, Blake2_128Concat, u64, ProofOfDeliveryOf<T>>;
+
+	/// Key each account signs for shipments delivered to it with, see `set_consignee_key`.
+	#[pallet::storage]
+	pub type ConsigneeKeys<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, ConsigneeKey>;
+
+	/// Declared contents of each shipment.
+	#[pallet::storage]
+	pub type Manifests<T: Config> = StorageMap<_, Blake2_128Concat, u64, ManifestOf<T>>;
+
+	/// Carriage fee reserved from the shipper of each shipment until it is settled.
+	#[pallet::storage]
+	pub type Escrows<T: Config> = StorageMap<_, Blake2_128Concat, u64, BalanceOf<T>>;
+
+	/// Storage deposit reserved from the shipper of each shipment until it is pruned or reaped.
+	#[pallet::storage]
+	pub type Deposits<T: Config> = StorageMap<_, Blake2_128Concat, u64, BalanceOf<T>>;
+
+	/// Chain of custody of each shipment, oldest hop first. Once it holds `MaxCustodyHops` hops,
+	/// the oldest one after the shipper registering the shipment is dropped for every new hop,
+	/// and its holder is kept in `FormerCustodians` unless they held a later hop as well.
+	#[pallet::storage]
+	pub type CustodyHistory<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		u64,
+		BoundedVec<CustodyRecordOf<T>, T::MaxCustodyHops>,
+		ValueQuery,
+	>;
+
+	/// Accounts that held each shipment but whose hops were all dropped from its
+	/// `CustodyHistory`, in the order they were dropped. Claims can still be made against them
+	/// and they are still paid for carrying it.
+	#[pallet::storage]
+	pub type FormerCustodians<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		u64,
+		BoundedVec<T::AccountId, T::MaxCustodyHops>,
+		ValueQuery,
+	>;
+
+	/// Delivered shipments awaiting pruning as `(delivered_on, shipment_id)`, keyed by their
+	/// position in delivery order.
+	#[pallet::storage]
+	pub type PruneQueue<T: Config> = StorageMap<_, Twox64Concat, u64, (T::BlockNumber, u64)>;
+
+	#[pallet::storage]
+	pub type PruneCursor<T> = StorageValue<_, QueueCursor, ValueQuery>;
+
+	/// Promised delivery deadline of each shipment that has one.
+	#[pallet::storage]
+	pub type Deadlines<T: Config> = StorageMap<_, Blake2_128Concat, u64, DeadlineOf<T>>;
+
+	/// Shipments whose deadline may have passed by each block, checked by `on_idle`.
+	#[pallet::storage]
+	pub type DeadlineAgenda<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		T::BlockNumber,
+		BoundedVec<u64, T::MaxDeadlinesPerBlock>,
+		ValueQuery,
+	>;
+
+	/// Next block whose deadline agenda has not been checked, or `None` before any deadline was
+	/// ever scheduled.
+	#[pallet::storage]
+	pub type DeadlineCursor<T: Config> = StorageValue<_, T::BlockNumber>;
+
+	/// Open claim against each shipment. Claims outlive the shipment if it is pruned before they
+	/// are resolved.
+	#[pallet::storage]
+	pub type Claims<T: Config> = StorageMap<_, Blake2_128Concat, u64, ClaimOf<T>>;
+
+	/// Kind of the claim that was upheld against each shipment, kept until the shipment is
+	/// pruned.
+	#[pallet::storage]
+	pub type UpheldClaims<T: Config> = StorageMap<_, Blake2_128Concat, u64, ClaimKind>;
+
+	/// Shipments that were delivered after their deadline, kept until the shipment is pruned.
+	#[pallet::storage]
+	pub type SlaBreaches<T: Config> = StorageMap<_, Blake2_128Concat, u64, SlaBreachOf<T>>;
+
+	/// Block each shipment's status was last scanned in, if it ever was.
+	#[pallet::storage]
+	pub type LastScanned<T: Config> = StorageMap<_, Blake2_128Concat, u64, T::BlockNumber>;
+
+	/// Registered facilities, which shipment destinations refer to.
+	#[pallet::storage]
+	pub type Facilities<T: Config> = StorageMap<_, Twox64Concat, FacilityId, FacilityOf<T>>;
+
+	/// Identifier the next registered facility will get.
+	#[pallet::storage]
+	pub type NextFacilityId<T> = StorageValue<_, FacilityId, ValueQuery>;
+
+	/// Radius in metres around a facility within which deliveries to it must be reported, for
+	/// facilities that do not set their own. Deliveries are not geofenced while this is unset.
+	#[pallet::storage]
+	pub type DefaultGeofenceRadius<T> = StorageValue<_, u32>;
+
+	/// Per-facility overrides of `DefaultGeofenceRadius`.
+	#[pallet::storage]
+	pub type FacilityGeofence<T> = StorageMap<_, Twox64Concat, FacilityId, u32>;
+
+	/// Organizations registered by `AdminOrigin`.
+	#[pallet::storage]
+	pub type Organizations<T> = StorageMap<_, Twox64Concat, OrganizationId, Organization>;
+
+	/// Identifier the next registered organization will get.
+	#[pallet::storage]
+	pub type NextOrganizationId<T> = StorageValue<_, OrganizationId, ValueQuery>;
+
+	/// The organization each account acts for. An account belongs to at most one.
+	#[pallet::storage]
+	pub type Members<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, OrganizationId>;
+
+	/// The organization each registered device reports for.
+	#[pallet::storage]
+	pub type Devices<T: Config> = StorageMap<_, Blake2_128Concat, T::DeviceId, OrganizationId>;
+
+	/// Number of devices registered to each organization that has any.
+	#[pallet::storage]
+	pub type DeviceCounts<T> = StorageMap<_, Twox64Concat, OrganizationId, u32, ValueQuery>;
+
+	/// Nonce and rate limiting window of every device that ever submitted. Kept once a device is
+	/// deregistered, so that its payloads cannot be replayed if it is registered again.
+	#[pallet::storage]
+	pub type DeviceUsages<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::DeviceId, DeviceUsageOf<T>, ValueQuery>;
+
+	#[pallet::genesis_config]
+	pub struct GenesisConfig<T: Config> {
+		/// Organizations to register as `(name_hash, roles, members)`.
+		pub organizations: Vec<([u8; 32], Roles, Vec<T::AccountId>)>,
+	}
+
+	#[cfg(feature = "std")]
+	impl<T: Config> Default for GenesisConfig<T> {
+		fn default() -> Self {
+			Self { organizations: Vec::new() }
+		}
+	}
+
+	#[pallet::genesis_build]
+	impl<T: Config> GenesisBuild<T> for GenesisConfig<T> {
+		fn build(&self) {
+			for (name_hash, roles, members) in &self.organizations {
+				let organization_id = Pallet::<T>::do_register_organization(*name_hash, *roles)
+					.expect("genesis organizations are valid");
+				for who in members {
+					Pallet::<T>::do_add_member(organization_id, who)
+						.expect("genesis members belong to a single organization");
+				}
+			}
+		}
+	}
 
 	// Pallets use events to inform users when important changes are made.
 	// https://docs.substrate.io/main-docs/build/events-errors/
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
 	pub enum Event<T: Config> {
+		/// Shipper created a new shipment [shipment_id, shipped_by, destination]
+		ShipmentCreated { shipment_id: u64, shipped_by: T::AccountId, destination: FacilityId },
 		/// Shipment received [shipment_id, shipped_by, received_by]
 		ShipmentReceived { shipment_id: u64, received_by: T::AccountId, received_at: Coords },
-		/// Shipment has been delivered [shipment_id]
-		ShipmentDelivered { shipment_id: u64 },
+		/// Shipment has been delivered, signed for by the consignee or not [shipment_id, class]
+		ShipmentDelivered { shipment_id: u64, class: DeliveryClass },
+		/// Consignee confirmed they received a delivered shipment [shipment_id, consignee]
+		ReceiptConfirmed { shipment_id: u64, consignee: T::AccountId },
+		/// Account set or cleared the key it signs for deliveries with [who, key]
+		ConsigneeKeySet { who: T::AccountId, key: Option<ConsigneeKey> },
+		/// Current holder offered the shipment to the next custodian [shipment_id, from, to]
+		HandoffOffered {
+			shipment_id: u64,
+			from: T::AccountId,
+			to: T::AccountId,
+			expires_on: T::BlockNumber,
+		},
+		/// Pending handoff offer was withdrawn [shipment_id]
+		HandoffCancelled { shipment_id: u64 },
+		/// Shipment moved to a new lifecycle status [shipment_id, from, to]
+		ShipmentStatusChanged { shipment_id: u64, from: ShipmentStatus, to: ShipmentStatus },
+		/// Shipper corrected the manifest before handing the shipment off [shipment_id]
+		ManifestAmended { shipment_id: u64 },
+		/// Custodian was paid their share of the carriage fee [shipment_id, to, amount]
+		FeePaid { shipment_id: u64, to: T::AccountId, amount: BalanceOf<T> },
+		/// Carriage fee went back to the shipper [shipment_id, to, amount]
+		FeeRefunded { shipment_id: u64, to: T::AccountId, amount: BalanceOf<T> },
+		/// Storage deposit went back to the shipper once the shipment was pruned or a claim on it
+		/// upheld [shipment_id, to, amount]
+		DepositReleased { shipment_id: u64, to: T::AccountId, amount: BalanceOf<T> },
+		/// Shipment was removed after going nowhere for too long [shipment_id, slashed]
+		ShipmentAbandoned { shipment_id: u64, slashed: BalanceOf<T> },
+		/// Shipment has not been delivered by its deadline [shipment_id, deadline]
+		ShipmentOverdue { shipment_id: u64, deadline: DeadlineOf<T> },
+		/// Shipper withdrew the shipment before handing it off [shipment_id]
+		ShipmentCancelled { shipment_id: u64 },
+		/// Shipment was sent back to its origin facility [shipment_id, destination]
+		ShipmentReturning { shipment_id: u64, destination: FacilityId },
+		/// Consignee refused the shipment, sending it back to its origin facility, or closing it
+		/// as `Rejected` if it has none or was already delivered [shipment_id, destination]
+		ShipmentRejected { shipment_id: u64, destination: Option<FacilityId> },
+		/// Shipment arrived back at its origin facility [shipment_id]
+		ShipmentReturned { shipment_id: u64 },
+		/// Shipment was broken up into new shipments and closed [shipment_id, children]
+		ShipmentSplit { shipment_id: u64, children: BoundedVec<u64, T::MaxSplitParts> },
+		/// Shipper set the ranges a shipment has to be kept within [shipment_id, ranges]
+		ConditionRangesSet { shipment_id: u64, ranges: ConditionRanges },
+		/// Sensors travelling with a shipment were read [shipment_id, reading]
+		ReadingRecorded { shipment_id: u64, reading: Reading },
+		/// A reading fell outside the range the shipment has to be kept within
+		/// [shipment_id, condition, value]
+		ConditionBreached { shipment_id: u64, condition: Condition, value: i32 },
+		/// Shipment was loaded in a container and now moves with it [shipment_id, container_id]
+		ShipmentLoaded { shipment_id: u64, container_id: u64 },
+		/// Shipment was taken out of a container [shipment_id, container_id]
+		ShipmentUnloaded { shipment_id: u64, container_id: u64 },
+		/// Shipment was delivered after its deadline [shipment_id, deadline]
+		SlaBreached { shipment_id: u64, deadline: DeadlineOf<T> },
+		/// Claim was filed against a custodian [shipment_id, claimant, accused, kind]
+		ClaimFiled {
+			shipment_id: u64,
+			claimant: T::AccountId,
+			accused: T::AccountId,
+			kind: ClaimKind,
+		},
+		/// Accused custodian answered a claim [shipment_id]
+		ClaimAnswered { shipment_id: u64 },
+		/// Arbiter ruled on a claim [shipment_id, upheld]
+		ClaimResolved { shipment_id: u64, upheld: bool },
+		/// New facility registered [facility_id, owner, kind]
+		FacilityRegistered { facility_id: FacilityId, owner: T::AccountId, kind: FacilityKind },
+		/// Facility name, location or kind changed [facility_id]
+		FacilityUpdated { facility_id: FacilityId },
+		/// Facility changed its operating status [facility_id, status]
+		FacilityStatusChanged { facility_id: FacilityId, status: FacilityStatus },
+		/// Default geofence radius changed, `None` disables the default [radius]
+		DefaultGeofenceSet { radius: Option<u32> },
+		/// Facility set or cleared its own geofence radius [facility_id, radius]
+		FacilityGeofenceSet { facility_id: FacilityId, radius: Option<u32> },
+		/// New organization registered [organization_id, roles]
+		OrganizationRegistered { organization_id: OrganizationId, roles: Roles },
+		/// Organization was granted a different set of roles [organization_id, roles]
+		OrganizationRolesChanged { organization_id: OrganizationId, roles: Roles },
+		/// Account now acts for an organization [organization_id, who]
+		MemberAdded { organization_id: OrganizationId, who: T::AccountId },
+		/// Account no longer acts for an organization [organization_id, who]
+		MemberRemoved { organization_id: OrganizationId, who: T::AccountId },
+		/// Device now reports for an organization [device, organization_id]
+		DeviceRegistered { device: T::DeviceId, organization_id: OrganizationId },
+		/// Device no longer reports for an organization [device, organization_id]
+		DeviceDeregistered { device: T::DeviceId, organization_id: OrganizationId },
+		/// Device submission was included but had no effect, its nonce is used up all the same
+		/// [device, nonce, error]
+		DeviceSubmissionFailed { device: T::DeviceId, nonce: u64, error: DispatchError },
 	}
 
 	// Errors inform users that something went wrong.
@@ -93,18 +804,126 @@ pub mod pallet {
 		DuplicateShipment,
 		/// Cannot modify shipment that has been delivered
 		ShipmentNotInTransit,
-		/// Delivered log is full
-		DeliveredLogOverflow,
+		/// Only the current holder of a shipment can do this
+		NotCurrentHolder,
+		/// Shipment has no pending handoff offer
+		NoPendingOffer,
+		/// Pending handoff offer was made to another account
+		NotOfferedRecipient,
+		/// Pending handoff offer has expired
+		HandoffExpired,
+		/// Cannot offer a shipment to its current holder
+		CannotHandoffToSelf,
+		/// Shipment cannot move from its current status to the requested one
+		InvalidStatusTransition,
+		/// Shipment has been held by too many accounts that are no longer in its custody history
+		TooManyCustodians,
+		/// Latitude or longitude is out of range
+		InvalidCoordinates,
+		/// Consignee signature is not over this shipment, or was not made while the courier
+		/// held it
+		InvalidConsigneeSignature,
+		/// Consignee signature is not by the key the shipment's consignee signs with
+		NotConsigneeKey,
+		/// No facility found for supplied id
+		FacilityDoesNotExist,
+		/// Only the owner of a facility can do this
+		NotFacilityOwner,
+		/// Facility is closed and cannot receive shipments
+		FacilityClosed,
+		/// No more facility ids are available
+		FacilityIdOverflow,
+		/// Reported position is too far from the destination facility
+		OutsideGeofence,
+		/// No organization found for supplied id
+		OrganizationDoesNotExist,
+		/// No more organization ids are available
+		OrganizationIdOverflow,
+		/// Roles include bits that do not name a role
+		InvalidRoles,
+		/// Account already acts for an organization
+		AlreadyMember,
+		/// Account does not act for any organization
+		NotMember,
+		/// Signer's organization does not hold a role allowed to do this
+		MissingRole,
+		/// Manifest is empty, has an empty line or no weight or size
+		InvalidManifest,
+		/// Only the shipper can do this
+		NotShipper,
+		/// Manifest can no longer change once the shipment has been offered to a carrier
+		ManifestLocked,
+		/// Shipment reached a final status, or changed hands or was scanned too recently to be
+		/// reaped
+		NotAbandoned,
+		/// Deadline has already passed
+		DeadlinePassed,
+		/// Too many deadlines fall due around the same block
+		DeadlineAgendaFull,
+		/// Only the shipper or the consignee can file a claim
+		NotClaimant,
+		/// Accused never had custody of the shipment
+		NotCustodian,
+		/// Shipment already has an open claim
+		ClaimExists,
+		/// Shipment has no open claim
+		NoClaim,
+		/// A claim against the shipment was already upheld, or it was cancelled before leaving the
+		/// shipper or split, in which case claims are made against its parts
+		NothingToClaim,
+		/// Only the accused custodian can answer a claim
+		NotAccused,
+		/// Claim was already answered
+		AlreadyAnswered,
+		/// Shipment has no origin facility to return to
+		NoOriginFacility,
+		/// Shipment is already on its way back to its origin
+		AlreadyReturning,
+		/// Only the shipment's consignee can do this
+		NotConsignee,
+		/// Consignee does not act for an organization holding the consignee role
+		InvalidConsignee,
+		/// Shipment has not been delivered
+		NotDelivered,
+		/// Consignee already confirmed receiving the shipment
+		AlreadyConfirmed,
+		/// Shipment is loaded in a container and can only move with it
+		ShipmentInContainer,
+		/// Container still has shipments loaded in it
+		ContainerNotEmpty,
+		/// Container cannot hold any more shipments
+		ContainerFull,
+		/// Containers cannot be loaded in containers, nor in themselves
+		NestedContainer,
+		/// Shipment is not loaded in this container
+		NotInContainer,
+		/// Shipment has a pending handoff offer
+		HandoffPending,
+		/// A shipment has to be split into at least two parts
+		InvalidSplit,
+		/// A range has its minimum above its maximum
+		InvalidRanges,
+		/// Reading has no values, or a humidity above 100%
+		InvalidReading,
+		/// Device is already registered
+		DeviceAlreadyRegistered,
+		/// No device registered under this key
+		DeviceNotRegistered,
+		/// Device reports for another organization
+		NotDeviceOrganization,
+		/// Payload does not carry the device's next nonce
+		InvalidNonce,
+		/// Device made all the submissions it can in the current window
+		DeviceRateLimited,
+		/// Organization already has `MaxDevicesPerOrganization` devices registered
+		TooManyDevices,
 	}
 
 	#[pallet::hooks]
 	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
-		fn on_initialize(_n: T::BlockNumber) -> Weight {
-			for shipment_id in DeliveredLog::<T>::get().iter() {
-				Shipments::<T>::remove(shipment_id);
-			}
-			DeliveredLog::<T>::kill();
-			Weight::zero()
+		fn on_idle(n: T::BlockNumber, remaining_weight: Weight) -> Weight {
+			let used = Self::prune_delivered(n, remaining_weight);
+			used.saturating_add(Self::process_deadlines(n, remaining_weight.saturating_sub(used)))
 		}
 	}
 
@@ -113,97 +932,1983 @@ pub mod pallet {
 	// Dispatchable functions must be annotated with a weight and must return a DispatchResult.
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
+		/// Create a new shipment on behalf of the signer, who is recorded as its shipper and
+		/// holds it until it is handed off to a carrier with `offer_handoff`.
+		///
+		/// `destination` must be a registered facility that has not closed, as must
+		/// `origin_facility` if given; the shipment can only be returned to sender if it is. The
+		/// manifest can be corrected with `amend_manifest` until the shipment is first offered to
+		/// a carrier. `consignee` is the account the shipment is meant for, see `confirm_receipt`
+		/// and `reject_shipment`, and has to act for an organization holding the consignee role.
+		///
+		/// `fee` is reserved from the shipper and paid to the carriers once the shipment is
+		/// delivered, see `Config::FeeDistribution`. It is refunded if the shipment never arrives.
+		/// A storage deposit is reserved alongside it, see [`Pallet::deposit_for`].
+		///
+		/// If a `deadline` is given, `ShipmentOverdue` is emitted once it passes without the
+		/// shipment being delivered, and a late delivery is recorded in `SlaBreaches`.
 		#[pallet::call_index(0)]
-		#[pallet::weight(10_000 + T::DbWeight::get().writes(1).ref_time())]
+		#[pallet::weight(T::WeightInfo::begin_transit())]
+		#[allow(clippy::too_many_arguments)]
 		pub fn begin_transit(
 			origin: OriginFor<T>,
 			shipment_id: u64,
-			shipped_by: T::AccountId,
 			received_at: Coords,
-			destination: u64,
+			origin_facility: Option<FacilityId>,
+			destination: FacilityId,
+			consignee: T::AccountId,
+			manifest: ManifestOf<T>,
+			fee: BalanceOf<T>,
+			deadline: Option<DeadlineOf<T>>,
 		) -> DispatchResult {
 			// Check that the extrinsic was signed and get the signer.
 			// This function will return an error if the extrinsic is not signed.
 			// https://docs.substrate.io/main-docs/build/origins/
-			let received_by = ensure_signed(origin)?;
+			let shipped_by = ensure_signed(origin)?;
+			Self::ensure_role(&shipped_by, ShipmentStatus::Created.set_by())?;
 
+			ensure!(received_at.is_valid(), Error::<T>::InvalidCoordinates);
 			ensure!(!Shipments::<T>::contains_key(&shipment_id), Error::<T>::DuplicateShipment);
+			for facility_id in origin_facility.iter().chain([&destination]) {
+				let facility =
+					Facilities::<T>::get(facility_id).ok_or(Error::<T>::FacilityDoesNotExist)?;
+				ensure!(facility.status != FacilityStatus::Closed, Error::<T>::FacilityClosed);
+			}
+			ensure!(manifest.is_valid(), Error::<T>::InvalidManifest);
+			ensure!(
+				Self::roles_of(&consignee).contains(Roles::CONSIGNEE),
+				Error::<T>::InvalidConsignee
+			);
+			if let Some(deadline) = &deadline {
+				let now = frame_system::Pallet::<T>::block_number();
+				ensure!(!deadline.has_passed(now, Self::now_ms()), Error::<T>::DeadlinePassed);
+			}
 
+			if !fee.is_zero() {
+				T::Currency::reserve(&shipped_by, fee)?;
+				Escrows::<T>::insert(shipment_id, fee);
+			}
+			Self::update_deposit(shipment_id, &shipped_by, &manifest)?;
+			Self::record_custody(shipment_id, &shipped_by, &received_at)?;
+			if let Some(deadline) = deadline {
+				Self::schedule_deadline(shipment_id, &deadline)?;
+				Deadlines::<T>::insert(shipment_id, deadline);
+			}
+			Manifests::<T>::insert(shipment_id, manifest);
 			Shipments::<T>::insert(
 				&shipment_id,
 				Shipment::new(
 					shipment_id,
 					shipped_by.clone(),
-					received_by.clone(),
 					received_at.clone(),
+					origin_facility,
 					destination,
+					Some(consignee),
 				),
 			);
 
-			Self::deposit_event(Event::ShipmentReceived { shipment_id, received_by, received_at });
+			Self::deposit_event(Event::ShipmentCreated {
+				shipment_id,
+				shipped_by: shipped_by.clone(),
+				destination,
+			});
+			Self::deposit_event(Event::ShipmentReceived {
+				shipment_id,
+				received_by: shipped_by,
+				received_at,
+			});
 
 			Ok(())
 		}
 
-		#[pallet::call_index(10)]
-		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1,1).ref_time())]
-		pub fn shipment_received(
+		/// Replace the manifest of a shipment the signer shipped and still holds, before it has
+		/// been offered to anyone. The storage deposit is adjusted to the new manifest's size.
+		#[pallet::call_index(1)]
+		#[pallet::weight(T::WeightInfo::amend_manifest())]
+		pub fn amend_manifest(
 			origin: OriginFor<T>,
 			shipment_id: u64,
-			received_at: Coords,
+			manifest: ManifestOf<T>,
 		) -> DispatchResult {
-			let received_by = ensure_signed(origin)?;
+			let who = ensure_signed(origin)?;
+			Self::ensure_role(&who, Roles::SHIPPER)?;
 
-			Shipments::<T>::try_mutate(&shipment_id, |shipment| -> DispatchResult {
-				match shipment {
-					Some(package) if !package.delivered => {
-						package.received_by = received_by.clone();
-						package.received_at = received_at.clone();
-						package.received_on = frame_system::Pallet::<T>::block_number();
-					},
-					Some(package) if package.delivered =>
-						return Err(Error::<T>::ShipmentNotInTransit.into()),
-					_ => return Err(Error::<T>::ShipmentDoesNotExist.into()),
+			let package =
+				Shipments::<T>::get(&shipment_id).ok_or(Error::<T>::ShipmentDoesNotExist)?;
+			ensure!(package.shipped_by == who, Error::<T>::NotShipper);
+			ensure!(
+				package.status == ShipmentStatus::Created &&
+					!PendingHandoffs::<T>::contains_key(&shipment_id),
+				Error::<T>::ManifestLocked
+			);
+			ensure!(manifest.is_valid(), Error::<T>::InvalidManifest);
+
+			Self::update_deposit(shipment_id, &who, &manifest)?;
+			Manifests::<T>::insert(shipment_id, manifest);
+
+			Self::deposit_event(Event::ManifestAmended { shipment_id });
+
+			Ok(())
+		}
+
+		/// Withdraw a shipment the signer has not handed to a carrier yet.
+		///
+		/// The carriage fee is refunded straight away. Like a delivered shipment, a cancelled one
+		/// stays queryable for `DeliveredRetention` blocks, after which it is pruned and its
+		/// storage deposit released.
+		#[pallet::call_index(2)]
+		#[pallet::weight(T::WeightInfo::cancel_shipment())]
+		pub fn cancel_shipment(origin: OriginFor<T>, shipment_id: u64) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::ensure_role(&who, ShipmentStatus::Cancelled.set_by())?;
+			Self::ensure_not_loaded(shipment_id)?;
+			Self::ensure_empty(shipment_id)?;
+
+			let from =
+				Shipments::<T>::try_mutate(&shipment_id, |shipment| -> Result<_, DispatchError> {
+					let package = shipment.as_mut().ok_or(Error::<T>::ShipmentDoesNotExist)?;
+					ensure!(package.shipped_by == who, Error::<T>::NotShipper);
+					let from = package.transition(ShipmentStatus::Cancelled)?;
+
+					Self::queue_for_pruning(shipment_id, frame_system::Pallet::<T>::block_number());
+
+					Ok(from)
+				})?;
+
+			PendingHandoffs::<T>::remove(&shipment_id);
+			Deadlines::<T>::remove(&shipment_id);
+			Self::settle_escrow(shipment_id, &who, ShipmentStatus::Cancelled);
+
+			Self::deposit_event(Event::ShipmentCancelled { shipment_id });
+			Self::deposit_status_change(shipment_id, from, ShipmentStatus::Cancelled);
+
+			Ok(())
+		}
+
+		/// Split a shipment the signer holds into the given parts, e.g. at a cross-dock.
+		///
+		/// Each part becomes a shipment of its own with its own id, destination and manifest, held
+		/// by the signer. It inherits the custody history, origin, deadline, acceptable ranges and
+		/// telemetry of the shipment it was split from, and an equal share of its carriage fee and
+		/// of the shipper's storage deposit for it, so splitting never reserves more of the
+		/// shipper's funds. The split shipment is closed as `Split` and pruned like a delivered
+		/// one.
+		#[pallet::call_index(3)]
+		#[pallet::weight(T::WeightInfo::split_shipment(parts.len() as u32))]
+		pub fn split_shipment(
+			origin: OriginFor<T>,
+			shipment_id: u64,
+			parts: BoundedVec<SplitPart<T>, T::MaxSplitParts>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::ensure_role(&who, ShipmentStatus::Split.set_by())?;
+			Self::ensure_not_loaded(shipment_id)?;
+			Self::ensure_empty(shipment_id)?;
+			ensure!(parts.len() >= 2, Error::<T>::InvalidSplit);
+
+			let mut package =
+				Shipments::<T>::get(&shipment_id).ok_or(Error::<T>::ShipmentDoesNotExist)?;
+			ensure!(package.received_by == who, Error::<T>::NotCurrentHolder);
+			let from = package.transition(ShipmentStatus::Split)?;
+			ensure!(!PendingHandoffs::<T>::contains_key(&shipment_id), Error::<T>::HandoffPending);
+			ensure!(!Claims::<T>::contains_key(&shipment_id), Error::<T>::ClaimExists);
+			for (index, part) in parts.iter().enumerate() {
+				ensure!(
+					!Shipments::<T>::contains_key(&part.shipment_id) &&
+						!parts[..index]
+							.iter()
+							.any(|other| other.shipment_id == part.shipment_id),
+					Error::<T>::DuplicateShipment
+				);
+				let facility = Facilities::<T>::get(part.destination)
+					.ok_or(Error::<T>::FacilityDoesNotExist)?;
+				ensure!(facility.status != FacilityStatus::Closed, Error::<T>::FacilityClosed);
+				ensure!(part.manifest.is_valid(), Error::<T>::InvalidManifest);
+			}
+
+			let now = frame_system::Pallet::<T>::block_number();
+			let history = CustodyHistory::<T>::get(&shipment_id);
+			let former_custodians = FormerCustodians::<T>::get(&shipment_id);
+			let deadline = Deadlines::<T>::take(&shipment_id);
+			let ranges = AcceptableRanges::<T>::get(&shipment_id);
+			let telemetry = Telemetry::<T>::get(&shipment_id);
+			let fee = Escrows::<T>::take(&shipment_id).unwrap_or_else(Zero::zero);
+			let deposit = Deposits::<T>::take(&shipment_id).unwrap_or_else(Zero::zero);
+			// Same number of ids under the same bound.
+			let children = BoundedVec::truncate_from(
+				parts.iter().map(|part| part.shipment_id).collect::<Vec<_>>(),
+			);
+
+			let count = parts.len() as u32;
+			let share = fee / BalanceOf::<T>::from(count);
+			let mut remaining = fee;
+			let deposit_share = deposit / BalanceOf::<T>::from(count);
+			let mut deposit_remaining = deposit;
+			for (index, part) in parts.into_iter().enumerate() {
+				let child_id = part.shipment_id;
+				let last = index as u32 + 1 == count;
+				let amount = if last { remaining } else { share };
+				remaining = remaining.saturating_sub(amount);
+				if !amount.is_zero() {
+					Escrows::<T>::insert(child_id, amount);
 				}
-				Ok(())
-			})?;
+				let held = if last { deposit_remaining } else { deposit_share };
+				deposit_remaining = deposit_remaining.saturating_sub(held);
+				if !held.is_zero() {
+					Deposits::<T>::insert(child_id, held);
+				}
+				CustodyHistory::<T>::insert(child_id, history.clone());
+				if !former_custodians.is_empty() {
+					FormerCustodians::<T>::insert(child_id, former_custodians.clone());
+				}
+				if let Some(deadline) = &deadline {
+					Self::schedule_deadline(child_id, deadline)?;
+					Deadlines::<T>::insert(child_id, deadline);
+				}
+				if let Some(ranges) = ranges {
+					AcceptableRanges::<T>::insert(child_id, ranges);
+				}
+				if telemetry.readings > 0 {
+					Telemetry::<T>::insert(child_id, telemetry);
+				}
+				Manifests::<T>::insert(child_id, part.manifest);
+				SplitFrom::<T>::insert(child_id, shipment_id);
+				Shipments::<T>::insert(
+					child_id,
+					Shipment {
+						id: child_id,
+						shipped_by: package.shipped_by.clone(),
+						received_by: who.clone(),
+						received_at: package.received_at.clone(),
+						received_on: now,
+						destination: part.destination,
+						status: from,
+						origin: package.origin,
+						returning: false,
+						consignee: package.consignee.clone(),
+					},
+				);
 
-			Self::deposit_event(Event::ShipmentReceived { shipment_id, received_by, received_at });
+				Self::deposit_event(Event::ShipmentCreated {
+					shipment_id: child_id,
+					shipped_by: package.shipped_by.clone(),
+					destination: part.destination,
+				});
+			}
+
+			Shipments::<T>::insert(&shipment_id, package);
+			Self::queue_for_pruning(shipment_id, now);
+
+			Self::deposit_event(Event::ShipmentSplit { shipment_id, children });
+			Self::deposit_status_change(shipment_id, from, ShipmentStatus::Split);
 
 			Ok(())
 		}
 
-		#[pallet::call_index(20)]
-		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1,1).ref_time())]
-		pub fn shipment_delivered(
+		/// Accept a handoff offered to the signer. Receiving a container takes custody of every
+		/// shipment loaded in it as well.
+		#[pallet::call_index(10)]
+		#[pallet::weight(T::WeightInfo::shipment_received(T::MaxContainerSize::get()))]
+		pub fn shipment_received(
 			origin: OriginFor<T>,
 			shipment_id: u64,
 			received_at: Coords,
-		) -> DispatchResult {
+		) -> DispatchResultWithPostInfo {
 			let received_by = ensure_signed(origin)?;
+			Self::ensure_role(&received_by, ShipmentStatus::InTransit.set_by())?;
 
-			Shipments::<T>::try_mutate(&shipment_id, |shipment| -> DispatchResult {
-				match shipment {
-					Some(package) if !package.delivered => {
-						package.received_by = received_by;
-						package.received_at = received_at;
-						package.received_on = frame_system::Pallet::<T>::block_number();
-						package.delivered = true;
-					},
-					Some(package) if package.delivered =>
-						return Err(Error::<T>::ShipmentNotInTransit.into()),
-					_ => return Err(Error::<T>::ShipmentDoesNotExist.into()),
-				}
+			ensure!(received_at.is_valid(), Error::<T>::InvalidCoordinates);
 
-				DeliveredLog::<T>::try_append(shipment_id)
-					.map_err(|_| Error::<T>::DeliveredLogOverflow)?;
+			let from = Shipments::<T>::try_mutate(
+				&shipment_id,
+				|shipment| -> Result<ShipmentStatus, DispatchError> {
+					let package = shipment.as_mut().ok_or(Error::<T>::ShipmentDoesNotExist)?;
+					let from = package.transition(ShipmentStatus::InTransit)?;
 
-				Ok(())
-			})?;
+					let handoff = PendingHandoffs::<T>::get(&shipment_id)
+						.ok_or(Error::<T>::NoPendingOffer)?;
+					ensure!(handoff.to == received_by, Error::<T>::NotOfferedRecipient);
+					ensure!(
+						frame_system::Pallet::<T>::block_number() <= handoff.expires_on,
+						Error::<T>::HandoffExpired
+					);
+
+					Self::record_custody(shipment_id, &received_by, &received_at)?;
+					package.received_by = received_by.clone();
+					package.received_at = received_at.clone();
+					package.received_on = frame_system::Pallet::<T>::block_number();
+					Ok(from)
+				},
+			)?;
+
+			PendingHandoffs::<T>::remove(&shipment_id);
+
+			let moved = Self::move_contents(
+				shipment_id,
+				ShipmentStatus::InTransit,
+				Some((&received_by, &received_at)),
+			)?;
 
-			Self::deposit_event(Event::ShipmentDelivered { shipment_id });
+			Self::deposit_event(Event::ShipmentReceived { shipment_id, received_by, received_at });
+			Self::deposit_status_change(shipment_id, from, ShipmentStatus::InTransit);
+
+			Ok(Some(T::WeightInfo::shipment_received(moved)).into())
+		}
+
+		#[pallet::call_index(11)]
+		#[pallet::weight(T::WeightInfo::offer_handoff())]
+		pub fn offer_handoff(
+			origin: OriginFor<T>,
+			shipment_id: u64,
+			to: T::AccountId,
+		) -> DispatchResult {
+			let from = ensure_signed(origin)?;
+			Self::ensure_role(&from, Roles::SHIPPER | Roles::HANDLERS)?;
+			Self::ensure_not_loaded(shipment_id)?;
+
+			let package =
+				Shipments::<T>::get(&shipment_id).ok_or(Error::<T>::ShipmentDoesNotExist)?;
+			ensure!(!package.status.is_final(), Error::<T>::ShipmentNotInTransit);
+			ensure!(package.received_by == from, Error::<T>::NotCurrentHolder);
+			ensure!(to != from, Error::<T>::CannotHandoffToSelf);
+
+			// A fresh offer replaces any previous one, so a lapsed or mistaken offer can simply be
+			// made again.
+			let expires_on =
+				frame_system::Pallet::<T>::block_number().saturating_add(T::HandoffTimeout::get());
+			PendingHandoffs::<T>::insert(
+				&shipment_id,
+				Handoff { from: from.clone(), to: to.clone(), expires_on },
+			);
+
+			Self::deposit_event(Event::HandoffOffered { shipment_id, from, to, expires_on });
 
 			Ok(())
 		}
+
+		#[pallet::call_index(12)]
+		#[pallet::weight(T::WeightInfo::cancel_handoff())]
+		pub fn cancel_handoff(origin: OriginFor<T>, shipment_id: u64) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::ensure_role(&who, Roles::SHIPPER | Roles::HANDLERS)?;
+
+			let handoff =
+				PendingHandoffs::<T>::get(&shipment_id).ok_or(Error::<T>::NoPendingOffer)?;
+			ensure!(handoff.from == who, Error::<T>::NotCurrentHolder);
+
+			PendingHandoffs::<T>::remove(&shipment_id);
+
+			Self::deposit_event(Event::HandoffCancelled { shipment_id });
+
+			Ok(())
+		}
+
+		/// Record a scan that moves the shipment along its lifecycle without changing hands.
+		///
+		/// Only `AtFacility`, `OutForDelivery`, `DeliveryFailed` and `Lost` can be set here;
+		/// creation, handoffs and delivery each have their own call. A lost shipment's carriage fee
+		/// is refunded to the shipper.
+		///
+		/// Scanning a container scans every shipment loaded in it. A lost container is lost with
+		/// its contents.
+		#[pallet::call_index(13)]
+		#[pallet::weight(T::WeightInfo::update_status(T::MaxContainerSize::get()))]
+		pub fn update_status(
+			origin: OriginFor<T>,
+			shipment_id: u64,
+			status: ShipmentStatus,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+
+			let moved =
+				Self::do_update_status(shipment_id, status, Self::roles_of(&who), |holder| {
+					*holder == who
+				})?;
+
+			Ok(Some(T::WeightInfo::update_status(moved)).into())
+		}
+
+		/// Send a shipment the signer shipped back to the facility it left from.
+		///
+		/// Its destination becomes its origin and the custody history carries on. Delivering it
+		/// there closes it as `Returned`, and the carriage fee is paid out to the carriers as for
+		/// a delivery. Its deadline no longer applies.
+		#[pallet::call_index(14)]
+		#[pallet::weight(T::WeightInfo::return_to_sender())]
+		pub fn return_to_sender(origin: OriginFor<T>, shipment_id: u64) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::ensure_role(&who, ShipmentStatus::Returned.set_by())?;
+
+			let destination = Self::send_back(shipment_id, |package| {
+				ensure!(package.shipped_by == who, Error::<T>::NotShipper);
+				Ok(())
+			})?;
+
+			Self::deposit_event(Event::ShipmentReturning { shipment_id, destination });
+
+			Ok(())
+		}
+
+		/// Refuse a shipment the signer is the consignee of, sending it back to the facility it
+		/// left from as with `return_to_sender`.
+		///
+		/// A shipment that left from no facility is closed as `Rejected` instead, and its
+		/// carriage fee refunded to the shipper. Containers have to be unloaded for that.
+		///
+		/// A delivery that is only `CourierAsserted` can still be rejected until the shipment is
+		/// pruned. The shipment is then closed as `Rejected` where the courier left it. Its
+		/// carriage fee was already paid out, so the consignee has to `file_claim` to recover
+		/// anything.
+		#[pallet::call_index(15)]
+		#[pallet::weight(T::WeightInfo::reject_shipment())]
+		pub fn reject_shipment(origin: OriginFor<T>, shipment_id: u64) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::ensure_role(&who, Roles::CONSIGNEE)?;
+
+			let package =
+				Shipments::<T>::get(&shipment_id).ok_or(Error::<T>::ShipmentDoesNotExist)?;
+			ensure!(package.consignee.as_ref() == Some(&who), Error::<T>::NotConsignee);
+
+			let destination = if package.status == ShipmentStatus::Delivered {
+				let delivery =
+					Deliveries::<T>::get(&shipment_id).ok_or(Error::<T>::NotDelivered)?;
+				ensure!(
+					delivery.class == DeliveryClass::CourierAsserted,
+					Error::<T>::AlreadyConfirmed
+				);
+
+				// Already queued for pruning when it was delivered.
+				Shipments::<T>::mutate(&shipment_id, |shipment| {
+					if let Some(package) = shipment {
+						package.status = ShipmentStatus::Rejected;
+					}
+				});
+				Self::deposit_status_change(
+					shipment_id,
+					ShipmentStatus::Delivered,
+					ShipmentStatus::Rejected,
+				);
+
+				None
+			} else if package.origin.is_some() {
+				Some(Self::send_back(shipment_id, |_| Ok(()))?)
+			} else {
+				Self::ensure_not_loaded(shipment_id)?;
+				Self::ensure_empty(shipment_id)?;
+
+				let from = Shipments::<T>::try_mutate(
+					&shipment_id,
+					|shipment| -> Result<_, DispatchError> {
+						let package = shipment.as_mut().ok_or(Error::<T>::ShipmentDoesNotExist)?;
+						let from = package.transition(ShipmentStatus::Rejected)?;
+
+						Self::queue_for_pruning(
+							shipment_id,
+							frame_system::Pallet::<T>::block_number(),
+						);
+
+						Ok(from)
+					},
+				)?;
+
+				PendingHandoffs::<T>::remove(&shipment_id);
+				Deadlines::<T>::remove(&shipment_id);
+				Self::settle_escrow(shipment_id, &package.shipped_by, ShipmentStatus::Rejected);
+				Self::deposit_status_change(shipment_id, from, ShipmentStatus::Rejected);
+
+				None
+			};
+
+			Self::deposit_event(Event::ShipmentRejected { shipment_id, destination });
+
+			Ok(())
+		}
+
+		/// Hand the shipment over at its destination.
+		///
+		/// If the destination facility is geofenced, `received_at` has to be within its radius.
+		/// The carriage fee is paid out to the carriers. A shipment on its way back to its origin
+		/// is closed as `Returned` rather than `Delivered`. Containers have to be unloaded first.
+		///
+		/// The delivery is `ConfirmedByConsignee` if it comes with the consignee's signature
+		/// over [`ConsigneeSignature::message`], made at a block since the signer took custody
+		/// with the key the consignee set with `set_consignee_key`. Otherwise it is only
+		/// `CourierAsserted` and the consignee can still `confirm_receipt`. Shipments created
+		/// before consignees were recorded are always `CourierAsserted`. Either way the delivery
+		/// can carry the hashes of evidence kept off chain, such as photos.
+		#[pallet::call_index(20)]
+		#[pallet::weight(T::WeightInfo::shipment_delivered(T::MaxCustodyHops::get()))]
+		pub fn shipment_delivered(
+			origin: OriginFor<T>,
+			shipment_id: u64,
+			received_at: Coords,
+			consignee: Option<ConsigneeSignatureOf<T>>,
+			evidence: BoundedVec<EvidenceHash, T::MaxEvidence>,
+		) -> DispatchResultWithPostInfo {
+			let received_by = ensure_signed(origin)?;
+			Self::ensure_role(&received_by, ShipmentStatus::Delivered.set_by())?;
+			Self::ensure_not_loaded(shipment_id)?;
+			Self::ensure_empty(shipment_id)?;
+
+			ensure!(received_at.is_valid(), Error::<T>::InvalidCoordinates);
+
+			let (from, to, shipped_by, signer) =
+				Shipments::<T>::try_mutate(&shipment_id, |shipment| -> Result<_, DispatchError> {
+					let package = shipment.as_mut().ok_or(Error::<T>::ShipmentDoesNotExist)?;
+					let to = if package.returning {
+						ShipmentStatus::Returned
+					} else {
+						ShipmentStatus::Delivered
+					};
+					let from = package.transition(to)?;
+					ensure!(package.received_by == received_by, Error::<T>::NotCurrentHolder);
+					Self::ensure_within_geofence(package.destination, &received_at)?;
+					let signer = match (&consignee, &package.consignee) {
+						(Some(signature), Some(account)) => {
+							let key = ConsigneeKeys::<T>::get(account);
+							ensure!(key == Some(signature.signer), Error::<T>::NotConsigneeKey);
+							ensure!(
+								signature.block >= package.received_on &&
+									signature.block <= frame_system::Pallet::<T>::block_number() &&
+									signature.is_valid_for(shipment_id),
+								Error::<T>::InvalidConsigneeSignature
+							);
+							Some(signature.signer)
+						},
+						// Nobody can sign for a shipment created before consignees were recorded.
+						_ => None,
+					};
+
+					Self::record_custody(shipment_id, &received_by, &received_at)?;
+					package.received_at = received_at;
+					package.received_on = frame_system::Pallet::<T>::block_number();
+
+					Self::queue_for_pruning(shipment_id, package.received_on);
+
+					Ok((from, to, package.shipped_by.clone(), signer))
+				})?;
+
+			PendingHandoffs::<T>::remove(&shipment_id);
+			let paid = Self::settle_escrow(shipment_id, &shipped_by, to);
+
+			let class = if signer.is_some() {
+				DeliveryClass::ConfirmedByConsignee
+			} else {
+				DeliveryClass::CourierAsserted
+			};
+			Deliveries::<T>::insert(
+				&shipment_id,
+				ProofOfDelivery { class, consignee: signer, evidence },
+			);
+
+			if to == ShipmentStatus::Returned {
+				Self::deposit_event(Event::ShipmentReturned { shipment_id });
+			} else {
+				Self::check_sla(shipment_id);
+				Self::deposit_event(Event::ShipmentDelivered { shipment_id, class });
+			}
+			Self::deposit_status_change(shipment_id, from, to);
+
+			Ok(Some(T::WeightInfo::shipment_delivered(paid)).into())
+		}
+
+		/// Remove a shipment that has neither changed hands nor been scanned for
+		/// `AbandonmentTimeout` blocks.
+		///
+		/// The shipper's storage deposit is slashed and any carriage fee refunded. Anyone can reap
+		/// an abandoned shipment; shipments that reached a final status, lost ones included, are
+		/// pruned instead, see `on_idle`. An abandoned container can only be reaped once its
+		/// contents have been, and a shipment with an open claim once the claim is resolved.
+		#[pallet::call_index(21)]
+		#[pallet::weight(T::WeightInfo::reap_abandoned())]
+		pub fn reap_abandoned(origin: OriginFor<T>, shipment_id: u64) -> DispatchResult {
+			ensure_signed(origin)?;
+
+			let package =
+				Shipments::<T>::get(&shipment_id).ok_or(Error::<T>::ShipmentDoesNotExist)?;
+			let last_seen = LastScanned::<T>::get(&shipment_id)
+				.map_or(package.received_on, |scanned| scanned.max(package.received_on));
+			let abandoned_on = last_seen.saturating_add(T::AbandonmentTimeout::get());
+			ensure!(
+				!package.status.is_final() &&
+					abandoned_on <= frame_system::Pallet::<T>::block_number(),
+				Error::<T>::NotAbandoned
+			);
+			Self::ensure_empty(shipment_id)?;
+			// The claim decides who answers for the shipment, and its deposit is kept until then.
+			ensure!(!Claims::<T>::contains_key(&shipment_id), Error::<T>::ClaimExists);
+
+			if let Some(container_id) = ContainedIn::<T>::take(&shipment_id) {
+				let mut contents = Contents::<T>::get(container_id);
+				contents.retain(|loaded| *loaded != shipment_id);
+				Self::set_contents(container_id, contents);
+			}
+			Shipments::<T>::remove(&shipment_id);
+			CustodyHistory::<T>::remove(&shipment_id);
+			FormerCustodians::<T>::remove(&shipment_id);
+			Manifests::<T>::remove(&shipment_id);
+			PendingHandoffs::<T>::remove(&shipment_id);
+			Deadlines::<T>::remove(&shipment_id);
+			LastScanned::<T>::remove(&shipment_id);
+			SplitFrom::<T>::remove(&shipment_id);
+			AcceptableRanges::<T>::remove(&shipment_id);
+			Telemetry::<T>::remove(&shipment_id);
+			Self::settle_escrow(shipment_id, &package.shipped_by, package.status);
+
+			let deposit = Deposits::<T>::take(&shipment_id).unwrap_or_else(Zero::zero);
+			let (imbalance, _) = T::Currency::slash_reserved(&package.shipped_by, deposit);
+			let slashed = imbalance.peek();
+			T::Slash::on_unbalanced(imbalance);
+
+			Self::deposit_event(Event::ShipmentAbandoned { shipment_id, slashed });
+
+			Ok(())
+		}
+
+		/// Confirm, as the shipment's consignee, that a delivered shipment was received, which
+		/// makes its delivery `ConfirmedByConsignee`.
+		///
+		/// This has to happen before the delivered shipment is pruned.
+		#[pallet::call_index(22)]
+		#[pallet::weight(T::WeightInfo::confirm_receipt())]
+		pub fn confirm_receipt(origin: OriginFor<T>, shipment_id: u64) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::ensure_role(&who, Roles::CONSIGNEE)?;
+
+			let package =
+				Shipments::<T>::get(&shipment_id).ok_or(Error::<T>::ShipmentDoesNotExist)?;
+			ensure!(package.consignee.as_ref() == Some(&who), Error::<T>::NotConsignee);
+			ensure!(package.status == ShipmentStatus::Delivered, Error::<T>::NotDelivered);
+
+			Deliveries::<T>::try_mutate(&shipment_id, |delivery| -> DispatchResult {
+				let delivery = delivery.as_mut().ok_or(Error::<T>::NotDelivered)?;
+				ensure!(
+					delivery.class != DeliveryClass::ConfirmedByConsignee,
+					Error::<T>::AlreadyConfirmed
+				);
+				delivery.class = DeliveryClass::ConfirmedByConsignee;
+				Ok(())
+			})?;
+
+			Self::deposit_event(Event::ReceiptConfirmed { shipment_id, consignee: who });
+
+			Ok(())
+		}
+
+		/// Set the key the signer signs for shipments delivered to them with, or clear it.
+		///
+		/// Couriers can only have a delivery confirmed by the consignee with a signature by this
+		/// key, see `shipment_delivered`.
+		#[pallet::call_index(23)]
+		#[pallet::weight(T::WeightInfo::set_consignee_key())]
+		pub fn set_consignee_key(
+			origin: OriginFor<T>,
+			key: Option<ConsigneeKey>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::ensure_role(&who, Roles::CONSIGNEE)?;
+
+			ConsigneeKeys::<T>::set(&who, key);
+
+			Self::deposit_event(Event::ConsigneeKeySet { who, key });
+
+			Ok(())
+		}
+
+		/// Register a facility owned by the signer. It is operational and gets the next free id.
+		#[pallet::call_index(30)]
+		#[pallet::weight(T::WeightInfo::register_facility())]
+		pub fn register_facility(
+			origin: OriginFor<T>,
+			name_hash: [u8; 32],
+			coords: Coords,
+			kind: FacilityKind,
+		) -> DispatchResult {
+			let owner = ensure_signed(origin)?;
+			Self::ensure_role(&owner, Roles::WAREHOUSE_OPERATOR)?;
+
+			ensure!(coords.is_valid(), Error::<T>::InvalidCoordinates);
+
+			let facility_id =
+				NextFacilityId::<T>::try_mutate(|next| -> Result<_, DispatchError> {
+					let facility_id = *next;
+					*next = next.checked_add(1).ok_or(Error::<T>::FacilityIdOverflow)?;
+					Ok(facility_id)
+				})?;
+			Facilities::<T>::insert(
+				facility_id,
+				Facility {
+					owner: owner.clone(),
+					name_hash,
+					coords,
+					kind,
+					status: FacilityStatus::Operational,
+				},
+			);
+
+			Self::deposit_event(Event::FacilityRegistered { facility_id, owner, kind });
+
+			Ok(())
+		}
+
+		/// Correct the name, location or kind of a facility the signer owns.
+		#[pallet::call_index(31)]
+		#[pallet::weight(T::WeightInfo::update_facility())]
+		pub fn update_facility(
+			origin: OriginFor<T>,
+			facility_id: FacilityId,
+			name_hash: [u8; 32],
+			coords: Coords,
+			kind: FacilityKind,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::ensure_role(&who, Roles::WAREHOUSE_OPERATOR)?;
+
+			ensure!(coords.is_valid(), Error::<T>::InvalidCoordinates);
+
+			Facilities::<T>::try_mutate(facility_id, |facility| -> DispatchResult {
+				let facility = facility.as_mut().ok_or(Error::<T>::FacilityDoesNotExist)?;
+				ensure!(facility.owner == who, Error::<T>::NotFacilityOwner);
+				facility.name_hash = name_hash;
+				facility.coords = coords;
+				facility.kind = kind;
+				Ok(())
+			})?;
+
+			Self::deposit_event(Event::FacilityUpdated { facility_id });
+
+			Ok(())
+		}
+
+		/// Open, suspend or close a facility the signer owns.
+		///
+		/// Closing only stops new shipments from being addressed to the facility; shipments
+		/// already on their way are unaffected.
+		#[pallet::call_index(32)]
+		#[pallet::weight(T::WeightInfo::set_facility_status())]
+		pub fn set_facility_status(
+			origin: OriginFor<T>,
+			facility_id: FacilityId,
+			status: FacilityStatus,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::ensure_role(&who, Roles::WAREHOUSE_OPERATOR)?;
+
+			Facilities::<T>::try_mutate(facility_id, |facility| -> DispatchResult {
+				let facility = facility.as_mut().ok_or(Error::<T>::FacilityDoesNotExist)?;
+				ensure!(facility.owner == who, Error::<T>::NotFacilityOwner);
+				facility.status = status;
+				Ok(())
+			})?;
+
+			Self::deposit_event(Event::FacilityStatusChanged { facility_id, status });
+
+			Ok(())
+		}
+
+		/// Set the geofence radius, in metres, of a facility the signer owns. `None` falls back
+		/// to `DefaultGeofenceRadius`.
+		#[pallet::call_index(33)]
+		#[pallet::weight(T::WeightInfo::set_facility_geofence())]
+		pub fn set_facility_geofence(
+			origin: OriginFor<T>,
+			facility_id: FacilityId,
+			radius: Option<u32>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::ensure_role(&who, Roles::WAREHOUSE_OPERATOR)?;
+
+			let facility =
+				Facilities::<T>::get(facility_id).ok_or(Error::<T>::FacilityDoesNotExist)?;
+			ensure!(facility.owner == who, Error::<T>::NotFacilityOwner);
+
+			FacilityGeofence::<T>::set(facility_id, radius);
+
+			Self::deposit_event(Event::FacilityGeofenceSet { facility_id, radius });
+
+			Ok(())
+		}
+
+		/// Set the geofence radius, in metres, of facilities without their own. `None` stops
+		/// geofencing those facilities.
+		#[pallet::call_index(34)]
+		#[pallet::weight(T::WeightInfo::set_default_geofence())]
+		pub fn set_default_geofence(origin: OriginFor<T>, radius: Option<u32>) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+
+			DefaultGeofenceRadius::<T>::set(radius);
+
+			Self::deposit_event(Event::DefaultGeofenceSet { radius });
+
+			Ok(())
+		}
+
+		/// Register an organization holding `roles`. Accounts act for it once added with
+		/// `add_member`.
+		#[pallet::call_index(40)]
+		#[pallet::weight(T::WeightInfo::register_organization())]
+		pub fn register_organization(
+			origin: OriginFor<T>,
+			name_hash: [u8; 32],
+			roles: Roles,
+		) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+
+			let organization_id = Self::do_register_organization(name_hash, roles)?;
+
+			Self::deposit_event(Event::OrganizationRegistered { organization_id, roles });
+
+			Ok(())
+		}
+
+		/// Replace the roles of an organization. Granting no roles at all suspends it.
+		#[pallet::call_index(41)]
+		#[pallet::weight(T::WeightInfo::set_organization_roles())]
+		pub fn set_organization_roles(
+			origin: OriginFor<T>,
+			organization_id: OrganizationId,
+			roles: Roles,
+		) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+
+			ensure!(roles.is_valid(), Error::<T>::InvalidRoles);
+			Organizations::<T>::try_mutate(organization_id, |organization| -> DispatchResult {
+				let organization =
+					organization.as_mut().ok_or(Error::<T>::OrganizationDoesNotExist)?;
+				organization.roles = roles;
+				Ok(())
+			})?;
+
+			Self::deposit_event(Event::OrganizationRolesChanged { organization_id, roles });
+
+			Ok(())
+		}
+
+		/// Let `who` act for an organization.
+		#[pallet::call_index(42)]
+		#[pallet::weight(T::WeightInfo::add_member())]
+		pub fn add_member(
+			origin: OriginFor<T>,
+			organization_id: OrganizationId,
+			who: T::AccountId,
+		) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+
+			Self::do_add_member(organization_id, &who)?;
+
+			Self::deposit_event(Event::MemberAdded { organization_id, who });
+
+			Ok(())
+		}
+
+		/// Stop `who` from acting for their organization.
+		///
+		/// Shipments they hold stay with them; they just cannot move them any further.
+		#[pallet::call_index(43)]
+		#[pallet::weight(T::WeightInfo::remove_member())]
+		pub fn remove_member(origin: OriginFor<T>, who: T::AccountId) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+
+			let organization_id = Members::<T>::take(&who).ok_or(Error::<T>::NotMember)?;
+
+			Self::deposit_event(Event::MemberRemoved { organization_id, who });
+
+			Ok(())
+		}
+
+		/// Claim that a shipment was lost or damaged in the custody of `accused`.
+		///
+		/// The shipper or the consignee can file a claim, on a shipment that is on its way, already
+		/// delivered or scanned as lost by its holder, and puts up `ClaimBond`. Once a claim
+		/// against a shipment was upheld, no other can be filed against it.
+		/// `evidence` is the hash of supporting documents kept off chain.
+		#[pallet::call_index(50)]
+		#[pallet::weight(T::WeightInfo::file_claim())]
+		pub fn file_claim(
+			origin: OriginFor<T>,
+			shipment_id: u64,
+			accused: T::AccountId,
+			kind: ClaimKind,
+			evidence: [u8; 32],
+		) -> DispatchResult {
+			let claimant = ensure_signed(origin)?;
+			Self::ensure_role(&claimant, Roles::SHIPPER | Roles::CONSIGNEE)?;
+			// Shipments in a container move with it, and claims are made against what is in it.
+			Self::ensure_not_loaded(shipment_id)?;
+			Self::ensure_empty(shipment_id)?;
+
+			let package =
+				Shipments::<T>::get(&shipment_id).ok_or(Error::<T>::ShipmentDoesNotExist)?;
+			ensure!(
+				package.shipped_by == claimant || package.consignee.as_ref() == Some(&claimant),
+				Error::<T>::NotClaimant
+			);
+			// A holder scanning the shipment as lost does not close it to claims against them.
+			ensure!(
+				!matches!(
+					package.status,
+					ShipmentStatus::Damaged | ShipmentStatus::Cancelled | ShipmentStatus::Split
+				) && !UpheldClaims::<T>::contains_key(&shipment_id),
+				Error::<T>::NothingToClaim
+			);
+			ensure!(!Claims::<T>::contains_key(&shipment_id), Error::<T>::ClaimExists);
+			ensure!(
+				accused != claimant &&
+					(CustodyHistory::<T>::get(&shipment_id)
+						.iter()
+						.any(|record| record.holder == accused) ||
+						FormerCustodians::<T>::get(&shipment_id).contains(&accused)),
+				Error::<T>::NotCustodian
+			);
+
+			let bond = T::ClaimBond::get();
+			T::Currency::reserve(&claimant, bond)?;
+			Claims::<T>::insert(
+				shipment_id,
+				Claim {
+					claimant: claimant.clone(),
+					accused: accused.clone(),
+					kind,
+					evidence,
+					response: None,
+					bond,
+					filed_on: frame_system::Pallet::<T>::block_number(),
+				},
+			);
+
+			Self::deposit_event(Event::ClaimFiled { shipment_id, claimant, accused, kind });
+
+			Ok(())
+		}
+
+		/// Answer a claim against the signer with the hash of their own evidence, putting up the
+		/// same bond as the claimant.
+		#[pallet::call_index(51)]
+		#[pallet::weight(T::WeightInfo::respond_to_claim())]
+		pub fn respond_to_claim(
+			origin: OriginFor<T>,
+			shipment_id: u64,
+			response: [u8; 32],
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::ensure_role(&who, Roles::SHIPPER | Roles::HANDLERS)?;
+
+			Claims::<T>::try_mutate(&shipment_id, |claim| -> DispatchResult {
+				let claim = claim.as_mut().ok_or(Error::<T>::NoClaim)?;
+				ensure!(claim.accused == who, Error::<T>::NotAccused);
+				ensure!(claim.response.is_none(), Error::<T>::AlreadyAnswered);
+
+				T::Currency::reserve(&who, claim.bond)?;
+				claim.response = Some(response);
+				Ok(())
+			})?;
+
+			Self::deposit_event(Event::ClaimAnswered { shipment_id });
+
+			Ok(())
+		}
+
+		/// Rule on the open claim against a shipment.
+		///
+		/// The losing side forfeits their bond to the winner. An upheld claim marks the shipment
+		/// lost or damaged, refunds any carriage fee still held and releases the shipper's
+		/// deposit.
+		#[pallet::call_index(52)]
+		#[pallet::weight(T::WeightInfo::resolve_claim())]
+		pub fn resolve_claim(
+			origin: OriginFor<T>,
+			shipment_id: u64,
+			upheld: bool,
+		) -> DispatchResult {
+			T::ArbiterOrigin::ensure_origin(origin)?;
+
+			let claim = Claims::<T>::take(&shipment_id).ok_or(Error::<T>::NoClaim)?;
+			let answered = claim.response.is_some();
+			let ((winner, winner_bonded), (loser, loser_bonded)) = if upheld {
+				((&claim.claimant, true), (&claim.accused, answered))
+			} else {
+				((&claim.accused, answered), (&claim.claimant, true))
+			};
+
+			if winner_bonded {
+				T::Currency::unreserve(winner, claim.bond);
+			}
+			if loser_bonded {
+				// Whatever cannot be moved stays reserved with the loser, as it would have been
+				// slashed already.
+				let _ = T::Currency::repatriate_reserved(
+					loser,
+					winner,
+					claim.bond,
+					BalanceStatus::Free,
+				);
+			}
+
+			if upheld {
+				Self::uphold_claim(shipment_id, claim.kind);
+			}
+
+			Self::deposit_event(Event::ClaimResolved { shipment_id, upheld });
+
+			Ok(())
+		}
+
+		/// Set the ranges a shipment the signer shipped has to be kept within, replacing any set
+		/// before. Readings outside them are reported with a `ConditionBreached` event.
+		#[pallet::call_index(70)]
+		#[pallet::weight(T::WeightInfo::set_condition_ranges())]
+		pub fn set_condition_ranges(
+			origin: OriginFor<T>,
+			shipment_id: u64,
+			ranges: ConditionRanges,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::ensure_role(&who, Roles::SHIPPER)?;
+
+			let package =
+				Shipments::<T>::get(&shipment_id).ok_or(Error::<T>::ShipmentDoesNotExist)?;
+			ensure!(package.shipped_by == who, Error::<T>::NotShipper);
+			ensure!(!package.status.is_final(), Error::<T>::ShipmentNotInTransit);
+			ensure!(ranges.is_valid(), Error::<T>::InvalidRanges);
+
+			AcceptableRanges::<T>::insert(shipment_id, ranges);
+
+			Self::deposit_event(Event::ConditionRangesSet { shipment_id, ranges });
+
+			Ok(())
+		}
+
+		/// Report what the sensors travelling with a shipment the signer holds read.
+		///
+		/// The reading is folded into the shipment's rolling telemetry aggregates, and every value
+		/// outside the shipment's acceptable ranges is counted and reported as a breach.
+		#[pallet::call_index(71)]
+		#[pallet::weight(T::WeightInfo::record_reading())]
+		pub fn record_reading(
+			origin: OriginFor<T>,
+			shipment_id: u64,
+			reading: Reading,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			Self::do_record_reading(shipment_id, reading, Self::roles_of(&who), |holder| {
+				*holder == who
+			})
+		}
+
+		/// Load shipments the signer holds into a container they hold, which is itself a
+		/// shipment.
+		///
+		/// Until they are unloaded, the shipments move with the container: they change hands
+		/// when it does and are scanned when it is. Containers cannot be nested, and shipments
+		/// with a pending handoff or an open claim cannot be loaded.
+		#[pallet::call_index(60)]
+		#[pallet::weight(T::WeightInfo::load_shipments(shipment_ids.len() as u32))]
+		pub fn load_shipments(
+			origin: OriginFor<T>,
+			container_id: u64,
+			shipment_ids: BoundedVec<u64, T::MaxContainerSize>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::ensure_role(&who, Roles::SHIPPER | Roles::HANDLERS)?;
+
+			let container =
+				Shipments::<T>::get(&container_id).ok_or(Error::<T>::ShipmentDoesNotExist)?;
+			ensure!(!container.status.is_final(), Error::<T>::ShipmentNotInTransit);
+			ensure!(container.received_by == who, Error::<T>::NotCurrentHolder);
+			ensure!(!ContainedIn::<T>::contains_key(&container_id), Error::<T>::NestedContainer);
+			ensure!(!Claims::<T>::contains_key(&container_id), Error::<T>::ClaimExists);
+
+			let mut contents = Contents::<T>::get(&container_id);
+			for &shipment_id in shipment_ids.iter() {
+				let package =
+					Shipments::<T>::get(&shipment_id).ok_or(Error::<T>::ShipmentDoesNotExist)?;
+				ensure!(!package.status.is_final(), Error::<T>::ShipmentNotInTransit);
+				ensure!(package.received_by == who, Error::<T>::NotCurrentHolder);
+				ensure!(
+					shipment_id != container_id && !Contents::<T>::contains_key(&shipment_id),
+					Error::<T>::NestedContainer
+				);
+				ensure!(
+					!contents.contains(&shipment_id) &&
+						!ContainedIn::<T>::contains_key(&shipment_id),
+					Error::<T>::ShipmentInContainer
+				);
+				ensure!(
+					!PendingHandoffs::<T>::contains_key(&shipment_id),
+					Error::<T>::HandoffPending
+				);
+				ensure!(!Claims::<T>::contains_key(&shipment_id), Error::<T>::ClaimExists);
+
+				contents.try_push(shipment_id).map_err(|_| Error::<T>::ContainerFull)?;
+			}
+
+			Self::set_contents(container_id, contents);
+			for shipment_id in shipment_ids {
+				ContainedIn::<T>::insert(&shipment_id, container_id);
+				Self::deposit_event(Event::ShipmentLoaded { shipment_id, container_id });
+			}
+
+			Ok(())
+		}
+
+		/// Take shipments out of a container the signer holds. They stay with the signer and move
+		/// on their own again.
+		#[pallet::call_index(61)]
+		#[pallet::weight(T::WeightInfo::unload_shipments(shipment_ids.len() as u32))]
+		pub fn unload_shipments(
+			origin: OriginFor<T>,
+			container_id: u64,
+			shipment_ids: BoundedVec<u64, T::MaxContainerSize>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::ensure_role(&who, Roles::SHIPPER | Roles::HANDLERS)?;
+
+			let container =
+				Shipments::<T>::get(&container_id).ok_or(Error::<T>::ShipmentDoesNotExist)?;
+			ensure!(container.received_by == who, Error::<T>::NotCurrentHolder);
+
+			let mut contents = Contents::<T>::get(&container_id);
+			for shipment_id in shipment_ids.iter() {
+				let position = contents
+					.iter()
+					.position(|loaded| loaded == shipment_id)
+					.ok_or(Error::<T>::NotInContainer)?;
+				contents.remove(position);
+			}
+
+			Self::set_contents(container_id, contents);
+			for shipment_id in shipment_ids {
+				ContainedIn::<T>::remove(&shipment_id);
+				Self::deposit_event(Event::ShipmentUnloaded { shipment_id, container_id });
+			}
+
+			Ok(())
+		}
+
+		/// Register a scanner or tracker to report for the signer's organization.
+		///
+		/// The device then submits scans and readings signed with its own key, as unsigned
+		/// transactions, see `submit_from_device`. An organization can have at most
+		/// `MaxDevicesPerOrganization` devices registered.
+		#[pallet::call_index(80)]
+		#[pallet::weight(T::WeightInfo::register_device())]
+		pub fn register_device(origin: OriginFor<T>, device: T::DeviceId) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let organization_id = Members::<T>::get(&who).ok_or(Error::<T>::NotMember)?;
+			Self::ensure_role(&who, Roles::SHIPPER | Roles::HANDLERS)?;
+			ensure!(!Devices::<T>::contains_key(&device), Error::<T>::DeviceAlreadyRegistered);
+			DeviceCounts::<T>::try_mutate(organization_id, |count| -> DispatchResult {
+				ensure!(*count < T::MaxDevicesPerOrganization::get(), Error::<T>::TooManyDevices);
+				*count += 1;
+				Ok(())
+			})?;
+
+			Devices::<T>::insert(&device, organization_id);
+
+			Self::deposit_event(Event::DeviceRegistered { device, organization_id });
+
+			Ok(())
+		}
+
+		/// Stop a device reporting for the signer's organization, e.g. once it is lost.
+		#[pallet::call_index(81)]
+		#[pallet::weight(T::WeightInfo::deregister_device())]
+		pub fn deregister_device(origin: OriginFor<T>, device: T::DeviceId) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let organization_id = Members::<T>::get(&who).ok_or(Error::<T>::NotMember)?;
+			let registered_to =
+				Devices::<T>::get(&device).ok_or(Error::<T>::DeviceNotRegistered)?;
+			ensure!(registered_to == organization_id, Error::<T>::NotDeviceOrganization);
+
+			Devices::<T>::remove(&device);
+			DeviceCounts::<T>::mutate_exists(organization_id, |count| {
+				*count = count.and_then(|count| count.checked_sub(1)).filter(|count| *count > 0);
+			});
+
+			Self::deposit_event(Event::DeviceDeregistered { device, organization_id });
+
+			Ok(())
+		}
+
+		/// Submit a scan or reading made by a registered device, as an unsigned transaction
+		/// carrying the device's signature over [`DevicePayload::message`] for this chain.
+		///
+		/// The device acts for its organization: it needs the organization's roles and can only
+		/// report on shipments one of its members holds. Each payload has to carry the device's
+		/// next nonce, and a device can make at most `MaxDeviceSubmissions` submissions per
+		/// `DeviceRateWindow`. A submission that turns out to be invalid once included still
+		/// uses up its nonce, and is reported with `DeviceSubmissionFailed`.
+		#[pallet::call_index(82)]
+		#[pallet::weight(match payload.submission {
+			Submission::Scan { .. } => T::WeightInfo::submit_scan(T::MaxContainerSize::get()),
+			Submission::Reading { .. } => T::WeightInfo::submit_reading(),
+		})]
+		pub fn submit_from_device(
+			origin: OriginFor<T>,
+			payload: DevicePayloadOf<T>,
+			// Checked by `validate_unsigned` before the transaction is included.
+			_signature: DeviceSignatureOf<T>,
+		) -> DispatchResultWithPostInfo {
+			ensure_none(origin)?;
+
+			let organization_id =
+				Devices::<T>::get(&payload.device).ok_or(Error::<T>::DeviceNotRegistered)?;
+			let mut usage = Self::device_usage(&payload.device);
+			ensure!(payload.nonce == usage.nonce, Error::<T>::InvalidNonce);
+			ensure!(
+				usage.submissions < T::MaxDeviceSubmissions::get(),
+				Error::<T>::DeviceRateLimited
+			);
+			usage.nonce.saturating_inc();
+			usage.submissions.saturating_inc();
+			DeviceUsages::<T>::insert(&payload.device, usage);
+
+			let roles = Organizations::<T>::get(organization_id)
+				.map_or(Roles::empty(), |organization| organization.roles);
+			let holds = |holder: &T::AccountId| Members::<T>::get(holder) == Some(organization_id);
+			// Only the submission is undone if it fails, the nonce stays used.
+			let result = with_storage_layer(|| match payload.submission.clone() {
+				Submission::Scan { shipment_id, status } =>
+					Self::do_update_status(shipment_id, status, roles, holds)
+						.map(T::WeightInfo::submit_scan),
+				Submission::Reading { shipment_id, reading } =>
+					Self::do_record_reading(shipment_id, reading, roles, holds)
+						.map(|()| T::WeightInfo::submit_reading()),
+			});
+
+			match result {
+				Ok(weight) => Ok(Some(weight).into()),
+				Err(error) => {
+					Self::deposit_event(Event::DeviceSubmissionFailed {
+						device: payload.device,
+						nonce: payload.nonce,
+						error,
+					});
+					Ok(().into())
+				},
+			}
+		}
+	}
+
+	#[pallet::validate_unsigned]
+	impl<T: Config> ValidateUnsigned for Pallet<T> {
+		type Call = Call<T>;
+
+		/// Admit device submissions that are signed by a registered device, carry a nonce it
+		/// has not used yet and fit within its rate limit. A payload with a later nonce than the
+		/// device's next one waits in the pool for the payloads before it.
+		fn validate_unsigned(_source: TransactionSource, call: &Self::Call) -> TransactionValidity {
+			if let Call::submit_from_device { payload, signature } = call {
+				let usage = Self::check_device_payload(payload, signature)?;
+
+				let priority = match payload.submission {
+					Submission::Scan { .. } => T::DeviceSubmissionPriority::get(),
+					Submission::Reading { .. } => T::DeviceSubmissionPriority::get() / 2,
+				};
+				let mut validity = ValidTransaction::with_tag_prefix("LogisticsDevice")
+					.priority(priority)
+					.and_provides((&payload.device, payload.nonce))
+					.longevity(T::DeviceRateWindow::get().saturated_into())
+					.propagate(true);
+				if payload.nonce > usage.nonce {
+					validity = validity.and_requires((&payload.device, payload.nonce - 1));
+				}
+				validity.build()
+			} else {
+				InvalidTransaction::Call.into()
+			}
+		}
+
+		/// Only include the payload carrying the device's next nonce.
+		fn pre_dispatch(call: &Self::Call) -> Result<(), TransactionValidityError> {
+			if let Call::submit_from_device { payload, signature } = call {
+				let usage = Self::check_device_payload(payload, signature)?;
+				ensure!(payload.nonce == usage.nonce, InvalidTransaction::Future);
+				Ok(())
+			} else {
+				Err(InvalidTransaction::Call.into())
+			}
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// A registered facility, e.g. to resolve a shipment's destination.
+		pub fn facility(facility_id: FacilityId) -> Option<FacilityOf<T>> {
+			Facilities::<T>::get(facility_id)
+		}
+
+		/// Roles `who` holds through their organization.
+		pub fn roles_of(who: &T::AccountId) -> Roles {
+			Members::<T>::get(who)
+				.and_then(Organizations::<T>::get)
+				.map_or(Roles::empty(), |organization| organization.roles)
+		}
+
+		/// Ensure `who` holds at least one of the `allowed` roles.
+		fn ensure_role(who: &T::AccountId, allowed: Roles) -> DispatchResult {
+			ensure!(Self::roles_of(who).intersects(allowed), Error::<T>::MissingRole);
+			Ok(())
+		}
+
+		/// Ensure `shipment_id` is not loaded in a container, so it can move on its own.
+		fn ensure_not_loaded(shipment_id: u64) -> DispatchResult {
+			ensure!(!ContainedIn::<T>::contains_key(shipment_id), Error::<T>::ShipmentInContainer);
+			Ok(())
+		}
+
+		/// Ensure no shipments are loaded in `shipment_id`.
+		fn ensure_empty(shipment_id: u64) -> DispatchResult {
+			ensure!(!Contents::<T>::contains_key(shipment_id), Error::<T>::ContainerNotEmpty);
+			Ok(())
+		}
+
+		/// What `device` submitted so far, with its submissions counted afresh if a new rate
+		/// limiting window started since its last one.
+		fn device_usage(device: &T::DeviceId) -> DeviceUsageOf<T> {
+			let mut usage = DeviceUsages::<T>::get(device);
+			let now = frame_system::Pallet::<T>::block_number();
+			let window_start = now.saturating_sub(now % T::DeviceRateWindow::get().max(One::one()));
+			if usage.window_start != window_start {
+				usage.window_start = window_start;
+				usage.submissions = 0;
+			}
+			usage
+		}
+
+		/// Check that `payload` is signed for this chain by a registered device and carries a
+		/// nonce the device has not used yet, which still fits within its rate limit counting the
+		/// payloads queued before it. Returns what the device submitted so far.
+		fn check_device_payload(
+			payload: &DevicePayloadOf<T>,
+			signature: &DeviceSignatureOf<T>,
+		) -> Result<DeviceUsageOf<T>, TransactionValidityError> {
+			ensure!(Devices::<T>::contains_key(&payload.device), UnknownTransaction::CannotLookup);
+
+			let usage = Self::device_usage(&payload.device);
+			ensure!(payload.nonce >= usage.nonce, InvalidTransaction::Stale);
+			let queued = payload.nonce - usage.nonce;
+			ensure!(
+				queued.saturating_add(usage.submissions.into()) <
+					T::MaxDeviceSubmissions::get().into(),
+				InvalidTransaction::ExhaustsResources
+			);
+
+			let genesis = frame_system::Pallet::<T>::block_hash(T::BlockNumber::zero());
+			ensure!(
+				payload.device.verify(&payload.message(&genesis), signature),
+				InvalidTransaction::BadProof
+			);
+
+			Ok(usage)
+		}
+
+		/// Scan `shipment_id` into `status` for someone holding `roles`, who has to hold the
+		/// shipment as told by `holds`. Returns the number of shipments moved along with it.
+		fn do_update_status(
+			shipment_id: u64,
+			status: ShipmentStatus,
+			roles: Roles,
+			holds: impl Fn(&T::AccountId) -> bool,
+		) -> Result<u32, DispatchError> {
+			ensure!(
+				matches!(
+					status,
+					ShipmentStatus::AtFacility |
+						ShipmentStatus::OutForDelivery |
+						ShipmentStatus::DeliveryFailed |
+						ShipmentStatus::Lost
+				),
+				Error::<T>::InvalidStatusTransition
+			);
+			ensure!(roles.intersects(status.set_by()), Error::<T>::MissingRole);
+			Self::ensure_not_loaded(shipment_id)?;
+
+			let (from, shipped_by) =
+				Shipments::<T>::try_mutate(&shipment_id, |shipment| -> Result<_, DispatchError> {
+					let package = shipment.as_mut().ok_or(Error::<T>::ShipmentDoesNotExist)?;
+					ensure!(holds(&package.received_by), Error::<T>::NotCurrentHolder);
+					let from = package.transition(status)?;
+					Ok((from, package.shipped_by.clone()))
+				})?;
+			LastScanned::<T>::insert(&shipment_id, frame_system::Pallet::<T>::block_number());
+
+			// A lost shipment is closed like a cancelled one: the fee is refunded straight away and
+			// the deposit once it is pruned.
+			if status.is_final() {
+				PendingHandoffs::<T>::remove(&shipment_id);
+				Self::settle_escrow(shipment_id, &shipped_by, status);
+				Self::queue_for_pruning(shipment_id, frame_system::Pallet::<T>::block_number());
+			}
+
+			let moved = Self::move_contents(shipment_id, status, None)?;
+
+			Self::deposit_status_change(shipment_id, from, status);
+
+			Ok(moved)
+		}
+
+		/// Turn a shipment on its way around, back to the facility it left from, if `authorize`
+		/// lets it. Returns that facility.
+		fn send_back(
+			shipment_id: u64,
+			authorize: impl FnOnce(&Shipment<T>) -> DispatchResult,
+		) -> Result<FacilityId, DispatchError> {
+			let destination =
+				Shipments::<T>::try_mutate(&shipment_id, |shipment| -> Result<_, DispatchError> {
+					let package = shipment.as_mut().ok_or(Error::<T>::ShipmentDoesNotExist)?;
+					authorize(package)?;
+					ensure!(!package.status.is_final(), Error::<T>::ShipmentNotInTransit);
+					// A shipment that never left can simply be cancelled.
+					ensure!(
+						package.status != ShipmentStatus::Created,
+						Error::<T>::InvalidStatusTransition
+					);
+					ensure!(!package.returning, Error::<T>::AlreadyReturning);
+					let origin = package.origin.ok_or(Error::<T>::NoOriginFacility)?;
+
+					package.origin = Some(package.destination);
+					package.destination = origin;
+					package.returning = true;
+
+					Ok(origin)
+				})?;
+
+			Deadlines::<T>::remove(&shipment_id);
+
+			Ok(destination)
+		}
+
+		/// Fold `reading` into the telemetry of `shipment_id` and report any breaches, for
+		/// someone holding `roles` who has to hold the shipment as told by `holds`.
+		fn do_record_reading(
+			shipment_id: u64,
+			reading: Reading,
+			roles: Roles,
+			holds: impl Fn(&T::AccountId) -> bool,
+		) -> DispatchResult {
+			ensure!(roles.intersects(Roles::SHIPPER | Roles::HANDLERS), Error::<T>::MissingRole);
+
+			let package =
+				Shipments::<T>::get(&shipment_id).ok_or(Error::<T>::ShipmentDoesNotExist)?;
+			ensure!(!package.status.is_final(), Error::<T>::ShipmentNotInTransit);
+			ensure!(holds(&package.received_by), Error::<T>::NotCurrentHolder);
+			ensure!(reading.is_valid(), Error::<T>::InvalidReading);
+
+			let breaches = AcceptableRanges::<T>::get(shipment_id)
+				.map(|ranges| ranges.breaches(&reading))
+				.unwrap_or_default();
+
+			Telemetry::<T>::mutate(shipment_id, |telemetry| {
+				telemetry.add(&reading, Self::now_ms(), T::TelemetryWindow::get());
+				telemetry.breaches = telemetry.breaches.saturating_add(breaches.len() as u32);
+			});
+
+			Self::deposit_event(Event::ReadingRecorded { shipment_id, reading });
+			for (condition, value) in breaches {
+				Self::deposit_event(Event::ConditionBreached { shipment_id, condition, value });
+			}
+
+			Ok(())
+		}
+
+		/// Store the shipments loaded in `container_id`, removing the entry once it is empty so
+		/// that only containers with something in them have one.
+		fn set_contents(container_id: u64, contents: BoundedVec<u64, T::MaxContainerSize>) {
+			if contents.is_empty() {
+				Contents::<T>::remove(container_id);
+			} else {
+				Contents::<T>::insert(container_id, contents);
+			}
+		}
+
+		/// Move every shipment loaded in `container_id` into `status` along with the container,
+		/// and into the custody of the given holder at the given position if it changed hands.
+		///
+		/// A container that reached a final status can no longer move, so it is emptied. Returns
+		/// the number of shipments moved.
+		fn move_contents(
+			container_id: u64,
+			status: ShipmentStatus,
+			custody: Option<(&T::AccountId, &Coords)>,
+		) -> Result<u32, DispatchError> {
+			let contents = if status.is_final() {
+				Contents::<T>::take(&container_id)
+			} else {
+				Contents::<T>::get(&container_id)
+			};
+
+			for &shipment_id in contents.iter() {
+				let (from, shipped_by) = Shipments::<T>::try_mutate(
+					&shipment_id,
+					|shipment| -> Result<_, DispatchError> {
+						let package = shipment.as_mut().ok_or(Error::<T>::ShipmentDoesNotExist)?;
+						let from = package.transition(status)?;
+
+						if let Some((holder, coords)) = custody {
+							Self::record_custody(shipment_id, holder, coords)?;
+							package.received_by = holder.clone();
+							package.received_at = coords.clone();
+							package.received_on = frame_system::Pallet::<T>::block_number();
+						}
+
+						Ok((from, package.shipped_by.clone()))
+					},
+				)?;
+				if custody.is_none() {
+					LastScanned::<T>::insert(
+						&shipment_id,
+						frame_system::Pallet::<T>::block_number(),
+					);
+				}
+
+				if status.is_final() {
+					ContainedIn::<T>::remove(&shipment_id);
+					Self::settle_escrow(shipment_id, &shipped_by, status);
+					Self::queue_for_pruning(shipment_id, frame_system::Pallet::<T>::block_number());
+				}
+				if let Some((holder, coords)) = custody {
+					Self::deposit_event(Event::ShipmentReceived {
+						shipment_id,
+						received_by: holder.clone(),
+						received_at: coords.clone(),
+					});
+				}
+				Self::deposit_status_change(shipment_id, from, status);
+			}
+
+			Ok(contents.len() as u32)
+		}
+
+		pub(crate) fn do_register_organization(
+			name_hash: [u8; 32],
+			roles: Roles,
+		) -> Result<OrganizationId, DispatchError> {
+			ensure!(roles.is_valid(), Error::<T>::InvalidRoles);
+
+			let organization_id =
+				NextOrganizationId::<T>::try_mutate(|next| -> Result<_, DispatchError> {
+					let organization_id = *next;
+					*next = next.checked_add(1).ok_or(Error::<T>::OrganizationIdOverflow)?;
+					Ok(organization_id)
+				})?;
+			Organizations::<T>::insert(organization_id, Organization { name_hash, roles });
+
+			Ok(organization_id)
+		}
+
+		pub(crate) fn do_add_member(
+			organization_id: OrganizationId,
+			who: &T::AccountId,
+		) -> DispatchResult {
+			ensure!(
+				Organizations::<T>::contains_key(organization_id),
+				Error::<T>::OrganizationDoesNotExist
+			);
+			ensure!(!Members::<T>::contains_key(who), Error::<T>::AlreadyMember);
+
+			Members::<T>::insert(who, organization_id);
+			Ok(())
+		}
+
+		/// Radius in metres deliveries to `facility_id` must be reported within, if any.
+		pub fn geofence_radius(facility_id: FacilityId) -> Option<u32> {
+			FacilityGeofence::<T>::get(facility_id).or_else(DefaultGeofenceRadius::<T>::get)
+		}
+
+		/// Ensure a delivery reported at `coords` is within the geofence of `destination`.
+		///
+		/// Shipments addressed before the facility registry existed may have no facility to
+		/// check against, and are let through.
+		fn ensure_within_geofence(destination: FacilityId, coords: &Coords) -> DispatchResult {
+			match (Facilities::<T>::get(destination), Self::geofence_radius(destination)) {
+				(Some(facility), Some(radius)) => {
+					ensure!(
+						facility.coords.distance_to(coords) <= radius,
+						Error::<T>::OutsideGeofence
+					);
+					Ok(())
+				},
+				_ => Ok(()),
+			}
+		}
+
+		/// The latest `MaxCustodyHops` hops in the chain of custody of a shipment, oldest hop
+		/// first, always starting with the shipper registering it.
+		pub fn custody_history(shipment_id: u64) -> Vec<CustodyRecordOf<T>> {
+			CustodyHistory::<T>::get(shipment_id).into_inner()
+		}
+
+		/// Append a hop to the custody history of `shipment_id`, making room for it if the
+		/// history is full. Handing a shipment back and forth never stops it from changing hands,
+		/// only running out of room for former custodians does.
+		fn record_custody(
+			shipment_id: u64,
+			holder: &T::AccountId,
+			coords: &Coords,
+		) -> DispatchResult {
+			let record = CustodyRecord {
+				holder: holder.clone(),
+				coords: coords.clone(),
+				block: frame_system::Pallet::<T>::block_number(),
+				timestamp: Self::now_ms(),
+			};
+			let dropped = CustodyHistory::<T>::mutate(shipment_id, |history| {
+				let mut dropped = None;
+				if history.len() >= T::MaxCustodyHops::get() as usize && history.len() > 1 {
+					// The first hop is the shipper registering the shipment, which is kept.
+					dropped = Some(history.remove(1).holder);
+				}
+				// Only fails if not even a single hop is kept.
+				let _ = history.try_push(record);
+				// Whoever still holds a later hop is kept track of by the history itself.
+				dropped.filter(|dropped| !history.iter().any(|record| record.holder == *dropped))
+			});
+
+			if let Some(dropped) = dropped {
+				FormerCustodians::<T>::try_mutate(shipment_id, |former| -> DispatchResult {
+					if !former.contains(&dropped) {
+						former.try_push(dropped).map_err(|_| Error::<T>::TooManyCustodians)?;
+					}
+					Ok(())
+				})?;
+			}
+
+			Ok(())
+		}
+
+		/// Storage deposit held for a shipment with `manifest`: the base deposit plus a per-byte
+		/// deposit for the shipment, its manifest, its telemetry, its proof of delivery, and its
+		/// custody history and former custodians at full length.
+		///
+		/// The history, former custodians and proof of delivery only come after the deposit is
+		/// taken, so they are charged at their bound rather than their current size.
+		pub fn deposit_for(manifest: &ManifestOf<T>) -> BalanceOf<T> {
+			let bytes = Shipment::<T>::max_encoded_len()
+				.saturating_add(manifest.encoded_size())
+				.saturating_add(ConditionRanges::max_encoded_len())
+				.saturating_add(Aggregates::max_encoded_len())
+				.saturating_add(ProofOfDeliveryOf::<T>::max_encoded_len())
+				.saturating_add(
+					BoundedVec::<CustodyRecordOf<T>, T::MaxCustodyHops>::max_encoded_len(),
+				)
+				.saturating_add(BoundedVec::<T::AccountId, T::MaxCustodyHops>::max_encoded_len());
+			let bytes = BalanceOf::<T>::from(bytes.min(u32::MAX as usize) as u32);
+			T::DepositBase::get().saturating_add(T::DepositPerByte::get().saturating_mul(bytes))
+		}
+
+		/// Top up or partly release the deposit `depositor` holds for `shipment_id` so it matches
+		/// `manifest`.
+		fn update_deposit(
+			shipment_id: u64,
+			depositor: &T::AccountId,
+			manifest: &ManifestOf<T>,
+		) -> DispatchResult {
+			let held = Deposits::<T>::get(shipment_id).unwrap_or_else(Zero::zero);
+			let required = Self::deposit_for(manifest);
+			if required > held {
+				T::Currency::reserve(depositor, required - held)?;
+			} else {
+				T::Currency::unreserve(depositor, held - required);
+			}
+			Deposits::<T>::insert(shipment_id, required);
+			Ok(())
+		}
+
+		/// Mark a shipment lost or damaged after a claim of `kind` against it was upheld. The
+		/// shipper gets back what they still hold for it, and it is queued for pruning unless it
+		/// already was.
+		fn uphold_claim(shipment_id: u64, kind: ClaimKind) {
+			let mut package = match Shipments::<T>::get(&shipment_id) {
+				Some(package) => package,
+				None => return,
+			};
+			UpheldClaims::<T>::insert(shipment_id, kind);
+			let status = ShipmentStatus::from(kind);
+			let from = core::mem::replace(&mut package.status, status);
+			let shipped_by = package.shipped_by.clone();
+			Shipments::<T>::insert(&shipment_id, package);
+
+			PendingHandoffs::<T>::remove(&shipment_id);
+			Self::settle_escrow(shipment_id, &shipped_by, status);
+			Self::release_deposit(shipment_id, shipped_by);
+			if !matches!(
+				from,
+				ShipmentStatus::Delivered |
+					ShipmentStatus::Returned |
+					ShipmentStatus::Rejected |
+					ShipmentStatus::Lost
+			) {
+				Self::queue_for_pruning(shipment_id, frame_system::Pallet::<T>::block_number());
+			}
+
+			Self::deposit_status_change(shipment_id, from, status);
+		}
+
+		/// Return the storage deposit held for `shipment_id` to `to`.
+		fn release_deposit(shipment_id: u64, to: T::AccountId) {
+			if let Some(amount) = Deposits::<T>::take(shipment_id) {
+				T::Currency::unreserve(&to, amount);
+				Self::deposit_event(Event::DepositReleased { shipment_id, to, amount });
+			}
+		}
+
+		/// Settle the carriage fee held for a shipment that reached the final `status`: pay it
+		/// out if the shipment was delivered or returned, refund it to `shipper` otherwise.
+		/// Returns the number of custodians paid.
+		pub(crate) fn settle_escrow(
+			shipment_id: u64,
+			shipper: &T::AccountId,
+			status: ShipmentStatus,
+		) -> u32 {
+			let fee = match Escrows::<T>::take(shipment_id) {
+				Some(fee) => fee,
+				None => return 0,
+			};
+
+			if !matches!(status, ShipmentStatus::Delivered | ShipmentStatus::Returned) {
+				T::Currency::unreserve(shipper, fee);
+				Self::deposit_event(Event::FeeRefunded {
+					shipment_id,
+					to: shipper.clone(),
+					amount: fee,
+				});
+				return 0
+			}
+
+			let history = CustodyHistory::<T>::get(shipment_id);
+			let mut custodians: Vec<T::AccountId> = Vec::new();
+			if T::FeeDistribution::get() == FeeDistribution::PerLeg {
+				// Custodians whose hops made way for newer ones carried it first.
+				custodians = FormerCustodians::<T>::get(shipment_id).into_inner();
+				// The first hop is the shipper registering the shipment, and the last one is the
+				// delivering carrier confirming delivery of the leg they already hold. Custodians
+				// are paid once, in the order they last held it, so handing a shipment back and
+				// forth earns nothing.
+				for record in history.iter().skip(1) {
+					custodians.retain(|custodian| *custodian != record.holder);
+					custodians.push(record.holder.clone());
+				}
+			}
+			if custodians.is_empty() {
+				custodians.extend(history.last().map(|record| record.holder.clone()));
+			}
+
+			let legs = custodians.len() as u32;
+			let share = fee / BalanceOf::<T>::from(legs.max(1));
+			let mut remaining = fee;
+			let mut unpaid = BalanceOf::<T>::zero();
+			for (leg, custodian) in custodians.into_iter().enumerate() {
+				let amount = if leg as u32 + 1 == legs { remaining } else { share };
+				remaining = remaining.saturating_sub(amount);
+				// Whatever cannot be moved, e.g. because part of the reserve was slashed, is
+				// released back to the shipper below.
+				let missed = T::Currency::repatriate_reserved(
+					shipper,
+					&custodian,
+					amount,
+					BalanceStatus::Free,
+				)
+				.unwrap_or(amount);
+				unpaid = unpaid.saturating_add(missed);
+				Self::deposit_event(Event::FeePaid {
+					shipment_id,
+					to: custodian,
+					amount: amount.saturating_sub(missed),
+				});
+			}
+			if !unpaid.is_zero() {
+				T::Currency::unreserve(shipper, unpaid);
+			}
+
+			legs
+		}
+
+		/// Queue a delivered shipment to be pruned once its retention period has passed.
+		pub(crate) fn queue_for_pruning(shipment_id: u64, delivered_on: T::BlockNumber) {
+			PruneCursor::<T>::mutate(|cursor| {
+				PruneQueue::<T>::insert(cursor.tail, (delivered_on, shipment_id));
+				cursor.tail += 1;
+			});
+		}
+
+		/// Prune delivered shipments whose retention period has passed, oldest first, for as long
+		/// as `limit` allows. Returns the weight used.
+		pub(crate) fn prune_delivered(now: T::BlockNumber, limit: Weight) -> Weight {
+			if T::WeightInfo::prune_delivered(0).any_gt(limit) {
+				return Weight::zero()
+			}
+
+			let retention = T::DeliveredRetention::get();
+			let mut cursor = PruneCursor::<T>::get();
+			let mut pruned = 0u32;
+
+			while cursor.head < cursor.tail &&
+				!T::WeightInfo::prune_delivered(pruned.saturating_add(1)).any_gt(limit)
+			{
+				let entry = PruneQueue::<T>::get(cursor.head);
+				let expired = match entry {
+					Some((delivered_on, _)) => delivered_on.saturating_add(retention) <= now,
+					None => true,
+				};
+
+				// Entries are queued in delivery order, so the first one still within its
+				// retention period ends the run.
+				if !expired {
+					break
+				}
+
+				if let Some((_, shipment_id)) = entry {
+					if let Some(package) = Shipments::<T>::take(shipment_id) {
+						Self::release_deposit(shipment_id, package.shipped_by);
+					}
+					CustodyHistory::<T>::remove(shipment_id);
+					FormerCustodians::<T>::remove(shipment_id);
+					Manifests::<T>::remove(shipment_id);
+					Deadlines::<T>::remove(shipment_id);
+					SlaBreaches::<T>::remove(shipment_id);
+					LastScanned::<T>::remove(shipment_id);
+					UpheldClaims::<T>::remove(shipment_id);
+					Deliveries::<T>::remove(shipment_id);
+					SplitFrom::<T>::remove(shipment_id);
+					AcceptableRanges::<T>::remove(shipment_id);
+					Telemetry::<T>::remove(shipment_id);
+				}
+				PruneQueue::<T>::remove(cursor.head);
+				cursor.head += 1;
+				pruned += 1;
+			}
+
+			if pruned > 0 {
+				PruneCursor::<T>::put(cursor);
+			}
+
+			T::WeightInfo::prune_delivered(pruned)
+		}
+
+		/// Current Unix time in milliseconds.
+		fn now_ms() -> u64 {
+			T::TimeProvider::now().as_millis() as u64
+		}
+
+		/// First block by which `deadline` may have passed. Timestamps are converted assuming
+		/// blocks keep being produced every `ExpectedBlockTime`.
+		fn due_block(deadline: &DeadlineOf<T>) -> T::BlockNumber {
+			match deadline {
+				Deadline::Block(block) => block.saturating_add(One::one()),
+				Deadline::Timestamp(timestamp) => {
+					let remaining = timestamp.saturating_sub(Self::now_ms());
+					let blocks = remaining / T::ExpectedBlockTime::get().max(1) + 1;
+					frame_system::Pallet::<T>::block_number()
+						.saturating_add(blocks.saturated_into())
+				},
+			}
+		}
+
+		/// Put `shipment_id` on the agenda of the block its deadline falls due in, or of one of
+		/// the next `MAX_AGENDA_SHIFT` blocks if that one is full.
+		///
+		/// A deadline that has already passed, as one a split shipment passes on to its parts,
+		/// is put on the agenda of the next block that will still be checked.
+		fn schedule_deadline(shipment_id: u64, deadline: &DeadlineOf<T>) -> DispatchResult {
+			let now = frame_system::Pallet::<T>::block_number();
+			let cursor = match DeadlineCursor::<T>::get() {
+				Some(cursor) => cursor,
+				None => {
+					DeadlineCursor::<T>::put(now);
+					now
+				},
+			};
+
+			let mut due = Self::due_block(deadline).max(now.saturating_add(One::one())).max(cursor);
+			for _ in 0..=MAX_AGENDA_SHIFT {
+				if DeadlineAgenda::<T>::try_append(due, shipment_id).is_ok() {
+					return Ok(())
+				}
+				due = due.saturating_add(One::one());
+			}
+			Err(Error::<T>::DeadlineAgendaFull.into())
+		}
+
+		/// Work through the deadline agenda of every block from `DeadlineCursor` up to `now`,
+		/// one block at a time for as long as `limit` allows, and return the weight used.
+		pub(crate) fn process_deadlines(now: T::BlockNumber, limit: Weight) -> Weight {
+			let block_weight = T::WeightInfo::process_deadlines(T::MaxDeadlinesPerBlock::get());
+			if block_weight.any_gt(limit) {
+				return Weight::zero()
+			}
+
+			let mut next = match DeadlineCursor::<T>::get() {
+				Some(next) => next,
+				None => return T::DbWeight::get().reads(1),
+			};
+			let start = next;
+			let timestamp = Self::now_ms();
+			let mut used = Weight::zero();
+
+			while next <= now && !used.saturating_add(block_weight).any_gt(limit) {
+				let agenda = DeadlineAgenda::<T>::take(next);
+				for &shipment_id in agenda.iter() {
+					Self::check_deadline(shipment_id, now, timestamp);
+				}
+				used.saturating_accrue(T::WeightInfo::process_deadlines(agenda.len() as u32));
+				next = next.saturating_add(One::one());
+			}
+
+			if next != start {
+				DeadlineCursor::<T>::put(next);
+			}
+
+			used.max(T::DbWeight::get().reads(1))
+		}
+
+		/// Emit `ShipmentOverdue` if the deadline of an undelivered shipment has passed, or put it
+		/// back on the agenda if a timestamp deadline was reached sooner than expected.
+		fn check_deadline(shipment_id: u64, now: T::BlockNumber, timestamp: u64) {
+			let undelivered = matches!(
+				Shipments::<T>::get(shipment_id),
+				Some(package) if !package.status.is_final()
+			);
+			let deadline = match Deadlines::<T>::get(shipment_id) {
+				Some(deadline) if undelivered => deadline,
+				_ => return,
+			};
+
+			if deadline.has_passed(now, timestamp) {
+				Self::deposit_event(Event::ShipmentOverdue { shipment_id, deadline });
+			} else if Self::schedule_deadline(shipment_id, &deadline).is_err() {
+				log::warn!(
+					target: LOG_TARGET,
+					"deadline of shipment {} could not be rescheduled",
+					shipment_id
+				);
+			}
+		}
+
+		/// Record an SLA breach if `shipment_id`, which was just delivered, missed its deadline.
+		fn check_sla(shipment_id: u64) {
+			let deadline = match Deadlines::<T>::get(shipment_id) {
+				Some(deadline) => deadline,
+				None => return,
+			};
+			let delivered_on = frame_system::Pallet::<T>::block_number();
+			let delivered_at = Self::now_ms();
+
+			if deadline.has_passed(delivered_on, delivered_at) {
+				SlaBreaches::<T>::insert(
+					shipment_id,
+					SlaBreach { deadline, delivered_on, delivered_at },
+				);
+				Self::deposit_event(Event::SlaBreached { shipment_id, deadline });
+			}
+		}
+
+		/// Emit `ShipmentStatusChanged` unless the status stayed the same, e.g. when a shipment
+		/// that is already in transit is handed to the next carrier.
+		fn deposit_status_change(shipment_id: u64, from: ShipmentStatus, to: ShipmentStatus) {
+			if from != to {
+				Self::deposit_event(Event::ShipmentStatusChanged { shipment_id, from, to });
+			}
+		}
 	}
 }
@@ -0,0 +1,92 @@
+//! What a shipment declares to contain and how it has to be handled.
+
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::{
+	traits::Get, BoundedVec, CloneNoBound, EqNoBound, PartialEqNoBound, RuntimeDebugNoBound,
+};
+use scale_info::TypeInfo;
+use sp_runtime::RuntimeDebug;
+
+/// One line of a manifest.
+#[derive(Clone, Eq, PartialEq, RuntimeDebug, Encode, Decode, TypeInfo, MaxEncodedLen)]
+pub struct ManifestItem {
+	/// Hash of the stock keeping unit.
+	pub sku_hash: [u8; 32],
+	/// Hash of the production lot or batch, for goods that are tracked by lot.
+	pub lot_hash: Option<[u8; 32]>,
+	/// Number of units of the SKU in the shipment.
+	pub quantity: u32,
+}
+
+/// Outer dimensions of a shipment in millimetres.
+#[derive(Clone, Copy, Eq, PartialEq, RuntimeDebug, Encode, Decode, TypeInfo, MaxEncodedLen)]
+pub struct Dimensions {
+	pub length: u32,
+	pub width: u32,
+	pub height: u32,
+}
+
+/// UN dangerous goods class.
+#[derive(Clone, Copy, Eq, PartialEq, RuntimeDebug, Encode, Decode, TypeInfo, MaxEncodedLen)]
+pub enum HazmatClass {
+	Explosives,
+	Gases,
+	FlammableLiquids,
+	FlammableSolids,
+	OxidizingSubstances,
+	ToxicSubstances,
+	RadioactiveMaterial,
+	Corrosives,
+	Miscellaneous,
+}
+
+/// How carefully a shipment has to be handled.
+#[derive(
+	Clone, Copy, Default, Eq, PartialEq, RuntimeDebug, Encode, Decode, TypeInfo, MaxEncodedLen,
+)]
+pub struct HandlingFlags {
+	pub fragile: bool,
+	pub perishable: bool,
+	/// Dangerous goods class, if the shipment contains any.
+	pub hazmat: Option<HazmatClass>,
+}
+
+/// Declared contents of a shipment, holding at most `MaxItems` lines.
+#[derive(
+	CloneNoBound,
+	EqNoBound,
+	PartialEqNoBound,
+	RuntimeDebugNoBound,
+	Encode,
+	Decode,
+	TypeInfo,
+	MaxEncodedLen,
+)]
+#[scale_info(skip_type_params(MaxItems))]
+pub struct Manifest<MaxItems: Get<u32>> {
+	pub items: BoundedVec<ManifestItem, MaxItems>,
+	/// Gross weight in grams, packaging included.
+	pub gross_weight: u32,
+	pub dimensions: Dimensions,
+	/// Declared value of the contents in the smallest unit of the chain's currency.
+	pub declared_value: u128,
+	pub handling: HandlingFlags,
+}
+
+impl<MaxItems: Get<u32>> Manifest<MaxItems> {
+	/// Total number of units across all lines.
+	pub fn item_count(&self) -> u64 {
+		self.items.iter().map(|item| item.quantity as u64).sum()
+	}
+
+	/// Whether the manifest declares something physical: at least one line, no empty lines and
+	/// a non-zero weight and size.
+	pub fn is_valid(&self) -> bool {
+		!self.items.is_empty() &&
+			self.items.iter().all(|item| item.quantity > 0) &&
+			self.gross_weight > 0 &&
+			self.dimensions.length > 0 &&
+			self.dimensions.width > 0 &&
+			self.dimensions.height > 0
+	}
+}
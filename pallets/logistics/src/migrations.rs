@@ -0,0 +1,722 @@
+//! Storage migrations for the logistics pallet.
+
+use super::*;
+use frame_support::{
+	pallet_prelude::*,
+	traits::{GetStorageVersion, OnRuntimeUpgrade},
+};
+#[cfg(feature = "try-runtime")]
+use sp_std::vec::Vec;
+
+/// Replaces the `delivered` flag on every shipment with a [`ShipmentStatus`].
+pub mod v1 {
+	use super::*;
+	use frame_support::storage_alias;
+
+	/// A shipment as stored before the lifecycle state machine was introduced.
+	#[derive(Decode)]
+	pub struct OldShipment<T: Config> {
+		id: u64,
+		shipped_by: T::AccountId,
+		received_by: T::AccountId,
+		received_at: v4::OldCoords,
+		received_on: T::BlockNumber,
+		destination: u64,
+		delivered: bool,
+	}
+
+	impl<T: Config> OldShipment<T> {
+		fn migrate(self) -> v4::OldShipment<T> {
+			v4::OldShipment {
+				id: self.id,
+				shipped_by: self.shipped_by,
+				received_by: self.received_by,
+				received_at: self.received_at,
+				received_on: self.received_on,
+				destination: self.destination,
+				status: if self.delivered {
+					ShipmentStatus::Delivered
+				} else {
+					ShipmentStatus::InTransit
+				},
+			}
+		}
+	}
+
+	/// `Shipments` with the layout written by this migration, which v4 replaced.
+	#[storage_alias]
+	type Shipments<T: Config> = StorageMap<Pallet<T>, Blake2_128Concat, u64, v4::OldShipment<T>>;
+
+	pub struct MigrateToV1<T>(PhantomData<T>);
+
+	impl<T: Config> OnRuntimeUpgrade for MigrateToV1<T> {
+		fn on_runtime_upgrade() -> Weight {
+			let on_chain_version = Pallet::<T>::on_chain_storage_version();
+			if on_chain_version != 0 {
+				log::info!(
+					target: LOG_TARGET,
+					"skipping v1 migration, on-chain storage version is {:?}",
+					on_chain_version
+				);
+				return T::DbWeight::get().reads(1)
+			}
+
+			let mut translated = 0u64;
+			Shipments::<T>::translate::<OldShipment<T>, _>(|_, old| {
+				translated += 1;
+				Some(old.migrate())
+			});
+
+			StorageVersion::new(1).put::<Pallet<T>>();
+			log::info!(target: LOG_TARGET, "migrated {} shipments to v1", translated);
+
+			T::DbWeight::get().reads_writes(translated + 1, translated + 1)
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<Vec<u8>, &'static str> {
+			Ok(crate::Shipments::<T>::count().encode())
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(state: Vec<u8>) -> Result<(), &'static str> {
+			let count: u32 = Decode::decode(&mut &state[..])
+				.map_err(|_| "pre_upgrade state could not be decoded")?;
+			ensure!(
+				crate::Shipments::<T>::count() == count,
+				"shipment count changed during migration"
+			);
+			ensure!(
+				Pallet::<T>::on_chain_storage_version() == 1,
+				"storage version was not bumped to 1"
+			);
+			Ok(())
+		}
+	}
+}
+
+/// Turns `DeliveredLog` from a single list cleared every block into a per-block log that is
+/// pruned after `DeliveredRetention`.
+pub mod v2 {
+	use super::*;
+	use frame_support::storage_alias;
+	use sp_runtime::traits::{One, Saturating};
+
+	mod old {
+		use super::*;
+
+		/// The log of shipments delivered in the previous block, as stored before v2.
+		#[storage_alias]
+		pub type DeliveredLog<T: Config> =
+			StorageValue<Pallet<T>, BoundedVec<u64, ConstU32<100>>, ValueQuery>;
+	}
+
+	/// The log of shipments delivered in each block, as stored from v2 until v3.
+	#[storage_alias]
+	pub type DeliveredLog<T: Config> = StorageMap<
+		Pallet<T>,
+		Twox64Concat,
+		<T as frame_system::Config>::BlockNumber,
+		BoundedVec<u64, ConstU32<100>>,
+		ValueQuery,
+	>;
+
+	pub struct MigrateToV2<T>(PhantomData<T>);
+
+	impl<T: Config> OnRuntimeUpgrade for MigrateToV2<T> {
+		fn on_runtime_upgrade() -> Weight {
+			let on_chain_version = Pallet::<T>::on_chain_storage_version();
+			if on_chain_version != 1 {
+				log::info!(
+					target: LOG_TARGET,
+					"skipping v2 migration, on-chain storage version is {:?}",
+					on_chain_version
+				);
+				return T::DbWeight::get().reads(1)
+			}
+
+			// The old log only ever held the previous block's deliveries, which would have been
+			// pruned in this block's `on_initialize`. File them under that block instead.
+			let delivered = old::DeliveredLog::<T>::take();
+			let delivered_on = frame_system::Pallet::<T>::block_number().saturating_sub(One::one());
+			if !delivered.is_empty() {
+				DeliveredLog::<T>::insert(delivered_on, delivered);
+			}
+
+			StorageVersion::new(2).put::<Pallet<T>>();
+			log::info!(target: LOG_TARGET, "migrated delivered log to v2");
+
+			T::DbWeight::get().reads_writes(2, 3)
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<Vec<u8>, &'static str> {
+			Ok((old::DeliveredLog::<T>::get().len() as u32).encode())
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(state: Vec<u8>) -> Result<(), &'static str> {
+			let count: u32 = Decode::decode(&mut &state[..])
+				.map_err(|_| "pre_upgrade state could not be decoded")?;
+			let delivered_on = frame_system::Pallet::<T>::block_number().saturating_sub(One::one());
+			ensure!(
+				DeliveredLog::<T>::get(delivered_on).len() as u32 == count,
+				"delivered shipments were lost during migration"
+			);
+			ensure!(
+				Pallet::<T>::on_chain_storage_version() == 2,
+				"storage version was not bumped to 2"
+			);
+			Ok(())
+		}
+	}
+}
+
+/// Replaces the per-block `DeliveredLog`, which capped deliveries at 100 per block, with the
+/// uncapped `PruneQueue`.
+pub mod v3 {
+	use super::*;
+	use sp_std::vec::Vec;
+
+	pub struct MigrateToV3<T>(PhantomData<T>);
+
+	impl<T: Config> OnRuntimeUpgrade for MigrateToV3<T> {
+		fn on_runtime_upgrade() -> Weight {
+			let on_chain_version = Pallet::<T>::on_chain_storage_version();
+			if on_chain_version != 2 {
+				log::info!(
+					target: LOG_TARGET,
+					"skipping v3 migration, on-chain storage version is {:?}",
+					on_chain_version
+				);
+				return T::DbWeight::get().reads(1)
+			}
+
+			// The queue is pruned front to back, so it has to be filled in delivery order.
+			let mut delivered: Vec<_> = v2::DeliveredLog::<T>::drain().collect();
+			delivered.sort_by_key(|(delivered_on, _)| *delivered_on);
+
+			let blocks = delivered.len() as u64;
+			let mut cursor = PruneCursor::<T>::get();
+			let first = cursor.tail;
+			for (delivered_on, shipment_ids) in delivered {
+				for shipment_id in shipment_ids {
+					PruneQueue::<T>::insert(cursor.tail, (delivered_on, shipment_id));
+					cursor.tail += 1;
+				}
+			}
+			let queued = cursor.tail - first;
+			PruneCursor::<T>::put(cursor);
+
+			StorageVersion::new(3).put::<Pallet<T>>();
+			log::info!(target: LOG_TARGET, "queued {} delivered shipments for pruning", queued);
+
+			T::DbWeight::get().reads_writes(blocks + 2, blocks + queued + 2)
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<Vec<u8>, &'static str> {
+			let delivered: u64 =
+				v2::DeliveredLog::<T>::iter_values().map(|ids| ids.len() as u64).sum();
+			Ok(delivered.encode())
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(state: Vec<u8>) -> Result<(), &'static str> {
+			let delivered: u64 = Decode::decode(&mut &state[..])
+				.map_err(|_| "pre_upgrade state could not be decoded")?;
+			let cursor = PruneCursor::<T>::get();
+			ensure!(cursor.tail - cursor.head == delivered, "delivered shipments were not queued");
+			ensure!(
+				v2::DeliveredLog::<T>::iter_keys().next().is_none(),
+				"delivered log was not drained"
+			);
+			ensure!(
+				Pallet::<T>::on_chain_storage_version() == 3,
+				"storage version was not bumped to 3"
+			);
+			Ok(())
+		}
+	}
+}
+
+/// Replaces the unsigned `Coords` of every shipment and custody record with signed geodetic
+/// [`Coords`].
+pub mod v4 {
+	use super::*;
+	use frame_support::storage_alias;
+	use sp_std::vec::Vec;
+
+	/// A position as stored before v4.
+	///
+	/// There was no defined way to encode the southern or western hemispheres, so values are read
+	/// as non-negative microdegrees and clamped to the valid range.
+	#[derive(Encode, Decode)]
+	pub struct OldCoords {
+		pub lat: u32,
+		pub lng: u32,
+	}
+
+	impl OldCoords {
+		fn migrate(self) -> Coords {
+			let clamp = |value: u32, max: i32| value.min(max as u32) as i32;
+			Coords::new(
+				clamp(self.lat, crate::coords::MAX_LATITUDE),
+				clamp(self.lng, crate::coords::MAX_LONGITUDE),
+			)
+			.expect("clamped into range; qed")
+		}
+	}
+
+	/// A shipment as stored from v1 until v4.
+	#[derive(Encode, Decode)]
+	pub struct OldShipment<T: Config> {
+		pub id: u64,
+		pub shipped_by: T::AccountId,
+		pub received_by: T::AccountId,
+		pub received_at: OldCoords,
+		pub received_on: T::BlockNumber,
+		pub destination: u64,
+		pub status: ShipmentStatus,
+	}
+
+	impl<T: Config> OldShipment<T> {
+		fn migrate(self) -> v5::OldShipment<T> {
+			v5::OldShipment {
+				id: self.id,
+				shipped_by: self.shipped_by,
+				received_by: self.received_by,
+				received_at: self.received_at.migrate(),
+				received_on: self.received_on,
+				destination: self.destination,
+				status: self.status,
+			}
+		}
+	}
+
+	/// `Shipments` with the layout written by this migration, which v5 replaced.
+	#[storage_alias]
+	type Shipments<T: Config> = StorageMap<Pallet<T>, Blake2_128Concat, u64, v5::OldShipment<T>>;
+
+	/// A custody record as stored before v4.
+	#[derive(Encode, Decode)]
+	pub struct OldCustodyRecord<AccountId, BlockNumber> {
+		holder: AccountId,
+		coords: OldCoords,
+		block: BlockNumber,
+		timestamp: u64,
+	}
+
+	impl<AccountId, BlockNumber> OldCustodyRecord<AccountId, BlockNumber> {
+		fn migrate(self) -> CustodyRecord<AccountId, BlockNumber> {
+			CustodyRecord {
+				holder: self.holder,
+				coords: self.coords.migrate(),
+				block: self.block,
+				timestamp: self.timestamp,
+			}
+		}
+	}
+
+	type OldCustodyHistory<T> = BoundedVec<
+		OldCustodyRecord<
+			<T as frame_system::Config>::AccountId,
+			<T as frame_system::Config>::BlockNumber,
+		>,
+		<T as Config>::MaxCustodyHops,
+	>;
+
+	#[cfg(feature = "try-runtime")]
+	mod old {
+		use super::*;
+
+		#[storage_alias]
+		pub type CustodyHistory<T: Config> =
+			StorageMap<Pallet<T>, Blake2_128Concat, u64, OldCustodyHistory<T>, ValueQuery>;
+	}
+
+	pub struct MigrateToV4<T>(PhantomData<T>);
+
+	impl<T: Config> OnRuntimeUpgrade for MigrateToV4<T> {
+		fn on_runtime_upgrade() -> Weight {
+			let on_chain_version = Pallet::<T>::on_chain_storage_version();
+			if on_chain_version != 3 {
+				log::info!(
+					target: LOG_TARGET,
+					"skipping v4 migration, on-chain storage version is {:?}",
+					on_chain_version
+				);
+				return T::DbWeight::get().reads(1)
+			}
+
+			let mut shipments = 0u64;
+			Shipments::<T>::translate::<OldShipment<T>, _>(|_, old| {
+				shipments += 1;
+				Some(old.migrate())
+			});
+
+			let mut histories = 0u64;
+			CustodyHistory::<T>::translate::<OldCustodyHistory<T>, _>(|_, old| {
+				histories += 1;
+				let records = old.into_iter().map(OldCustodyRecord::migrate).collect::<Vec<_>>();
+				// Same number of records under the same bound.
+				Some(BoundedVec::truncate_from(records))
+			});
+
+			StorageVersion::new(4).put::<Pallet<T>>();
+			log::info!(
+				target: LOG_TARGET,
+				"migrated coordinates of {} shipments and {} custody histories to v4",
+				shipments,
+				histories
+			);
+
+			let migrated = shipments + histories;
+			T::DbWeight::get().reads_writes(migrated + 1, migrated + 1)
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<Vec<u8>, &'static str> {
+			let hops: u64 = old::CustodyHistory::<T>::iter_values()
+				.map(|history| history.len() as u64)
+				.sum();
+			Ok((crate::Shipments::<T>::count(), hops).encode())
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(state: Vec<u8>) -> Result<(), &'static str> {
+			let (count, hops): (u32, u64) = Decode::decode(&mut &state[..])
+				.map_err(|_| "pre_upgrade state could not be decoded")?;
+			ensure!(
+				crate::Shipments::<T>::count() == count,
+				"shipment count changed during migration"
+			);
+			ensure!(
+				Shipments::<T>::iter_values().count() as u32 == count,
+				"shipments could not be decoded after migration"
+			);
+			ensure!(
+				CustodyHistory::<T>::iter_values()
+					.map(|history| history.len() as u64)
+					.sum::<u64>() == hops,
+				"custody records were lost during migration"
+			);
+			ensure!(
+				Pallet::<T>::on_chain_storage_version() == 4,
+				"storage version was not bumped to 4"
+			);
+			Ok(())
+		}
+	}
+}
+
+/// Adds the origin facility and return flag to every shipment. Shipments created before v5 have no
+/// recorded origin and so cannot be returned to sender.
+pub mod v5 {
+	use super::*;
+	use frame_support::storage_alias;
+
+	/// A shipment as stored from v4 until v5.
+	#[derive(Encode, Decode)]
+	pub struct OldShipment<T: Config> {
+		pub id: u64,
+		pub shipped_by: T::AccountId,
+		pub received_by: T::AccountId,
+		pub received_at: Coords,
+		pub received_on: T::BlockNumber,
+		pub destination: u64,
+		pub status: ShipmentStatus,
+	}
+
+	impl<T: Config> OldShipment<T> {
+		fn migrate(self) -> v6::OldShipment<T> {
+			v6::OldShipment {
+				id: self.id,
+				shipped_by: self.shipped_by,
+				received_by: self.received_by,
+				received_at: self.received_at,
+				received_on: self.received_on,
+				destination: self.destination,
+				status: self.status,
+				origin: None,
+				returning: false,
+			}
+		}
+	}
+
+	/// `Shipments` with the layout written by this migration, which v6 replaced.
+	#[storage_alias]
+	type Shipments<T: Config> = StorageMap<Pallet<T>, Blake2_128Concat, u64, v6::OldShipment<T>>;
+
+	pub struct MigrateToV5<T>(PhantomData<T>);
+
+	impl<T: Config> OnRuntimeUpgrade for MigrateToV5<T> {
+		fn on_runtime_upgrade() -> Weight {
+			let on_chain_version = Pallet::<T>::on_chain_storage_version();
+			if on_chain_version != 4 {
+				log::info!(
+					target: LOG_TARGET,
+					"skipping v5 migration, on-chain storage version is {:?}",
+					on_chain_version
+				);
+				return T::DbWeight::get().reads(1)
+			}
+
+			let mut translated = 0u64;
+			Shipments::<T>::translate::<OldShipment<T>, _>(|_, old| {
+				translated += 1;
+				Some(old.migrate())
+			});
+
+			StorageVersion::new(5).put::<Pallet<T>>();
+			log::info!(target: LOG_TARGET, "migrated {} shipments to v5", translated);
+
+			T::DbWeight::get().reads_writes(translated + 1, translated + 1)
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<Vec<u8>, &'static str> {
+			Ok(crate::Shipments::<T>::count().encode())
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(state: Vec<u8>) -> Result<(), &'static str> {
+			let count: u32 = Decode::decode(&mut &state[..])
+				.map_err(|_| "pre_upgrade state could not be decoded")?;
+			ensure!(
+				crate::Shipments::<T>::count() == count,
+				"shipment count changed during migration"
+			);
+			ensure!(
+				Shipments::<T>::iter_values().count() as u32 == count,
+				"shipments could not be decoded after migration"
+			);
+			ensure!(
+				Pallet::<T>::on_chain_storage_version() == 5,
+				"storage version was not bumped to 5"
+			);
+			Ok(())
+		}
+	}
+}
+
+/// Adds the consignee to every shipment. Shipments created before v6 have no recorded consignee,
+/// so no one can confirm their receipt or reject them.
+pub mod v6 {
+	use super::*;
+
+	/// A shipment as stored from v5 until v6.
+	#[derive(Encode, Decode)]
+	pub struct OldShipment<T: Config> {
+		pub id: u64,
+		pub shipped_by: T::AccountId,
+		pub received_by: T::AccountId,
+		pub received_at: Coords,
+		pub received_on: T::BlockNumber,
+		pub destination: u64,
+		pub status: ShipmentStatus,
+		pub origin: Option<FacilityId>,
+		pub returning: bool,
+	}
+
+	impl<T: Config> OldShipment<T> {
+		fn migrate(self) -> Shipment<T> {
+			Shipment {
+				id: self.id,
+				shipped_by: self.shipped_by,
+				received_by: self.received_by,
+				received_at: self.received_at,
+				received_on: self.received_on,
+				destination: self.destination,
+				status: self.status,
+				origin: self.origin,
+				returning: self.returning,
+				consignee: None,
+			}
+		}
+	}
+
+	pub struct MigrateToV6<T>(PhantomData<T>);
+
+	impl<T: Config> OnRuntimeUpgrade for MigrateToV6<T> {
+		fn on_runtime_upgrade() -> Weight {
+			let on_chain_version = Pallet::<T>::on_chain_storage_version();
+			if on_chain_version != 5 {
+				log::info!(
+					target: LOG_TARGET,
+					"skipping v6 migration, on-chain storage version is {:?}",
+					on_chain_version
+				);
+				return T::DbWeight::get().reads(1)
+			}
+
+			let mut translated = 0u64;
+			Shipments::<T>::translate::<OldShipment<T>, _>(|_, old| {
+				translated += 1;
+				Some(old.migrate())
+			});
+
+			StorageVersion::new(6).put::<Pallet<T>>();
+			log::info!(target: LOG_TARGET, "migrated {} shipments to v6", translated);
+
+			T::DbWeight::get().reads_writes(translated + 1, translated + 1)
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<Vec<u8>, &'static str> {
+			Ok(Shipments::<T>::count().encode())
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(state: Vec<u8>) -> Result<(), &'static str> {
+			let count: u32 = Decode::decode(&mut &state[..])
+				.map_err(|_| "pre_upgrade state could not be decoded")?;
+			ensure!(Shipments::<T>::count() == count, "shipment count changed during migration");
+			ensure!(
+				Shipments::<T>::iter_values().count() as u32 == count,
+				"shipments could not be decoded after migration"
+			);
+			ensure!(
+				Pallet::<T>::on_chain_storage_version() == 6,
+				"storage version was not bumped to 6"
+			);
+			Ok(())
+		}
+	}
+}
+
+/// Splits every shipment's telemetry totals into rolling windows. The totals so far become the
+/// previous window, so they age out once two more windows of readings have been reported.
+pub mod v7 {
+	use super::*;
+	use crate::telemetry::{Aggregate, Aggregates, Window};
+
+	/// Telemetry as stored until v7, totalled over every reading.
+	#[derive(Encode, Decode)]
+	pub struct OldAggregates {
+		pub temperature: Aggregate,
+		pub humidity: Aggregate,
+		pub shock: Aggregate,
+		pub readings: u32,
+		pub breaches: u32,
+		pub last_reported_at: u64,
+	}
+
+	impl OldAggregates {
+		fn migrate(self) -> Aggregates {
+			Aggregates {
+				current: Window::default(),
+				previous: Window {
+					temperature: self.temperature,
+					humidity: self.humidity,
+					shock: self.shock,
+					readings: self.readings,
+				},
+				readings: self.readings,
+				breaches: self.breaches,
+				last_reported_at: self.last_reported_at,
+			}
+		}
+	}
+
+	pub struct MigrateToV7<T>(PhantomData<T>);
+
+	impl<T: Config> OnRuntimeUpgrade for MigrateToV7<T> {
+		fn on_runtime_upgrade() -> Weight {
+			let on_chain_version = Pallet::<T>::on_chain_storage_version();
+			if on_chain_version != 6 {
+				log::info!(
+					target: LOG_TARGET,
+					"skipping v7 migration, on-chain storage version is {:?}",
+					on_chain_version
+				);
+				return T::DbWeight::get().reads(1)
+			}
+
+			let mut translated = 0u64;
+			Telemetry::<T>::translate::<OldAggregates, _>(|_, old| {
+				translated += 1;
+				Some(old.migrate())
+			});
+
+			StorageVersion::new(7).put::<Pallet<T>>();
+			log::info!(target: LOG_TARGET, "migrated telemetry of {} shipments to v7", translated);
+
+			T::DbWeight::get().reads_writes(translated + 1, translated + 1)
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<Vec<u8>, &'static str> {
+			Ok((Telemetry::<T>::iter_keys().count() as u32).encode())
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(state: Vec<u8>) -> Result<(), &'static str> {
+			let count: u32 = Decode::decode(&mut &state[..])
+				.map_err(|_| "pre_upgrade state could not be decoded")?;
+			ensure!(
+				Telemetry::<T>::iter_values().count() as u32 == count,
+				"telemetry could not be decoded after migration"
+			);
+			ensure!(
+				Pallet::<T>::on_chain_storage_version() == 7,
+				"storage version was not bumped to 7"
+			);
+			Ok(())
+		}
+	}
+}
+
+/// Counts the devices registered to each organization, which `MaxDevicesPerOrganization` bounds
+/// from v8 on. Organizations that already have more keep them, but cannot register another until
+/// they are below the bound.
+pub mod v8 {
+	use super::*;
+
+	pub struct MigrateToV8<T>(PhantomData<T>);
+
+	impl<T: Config> OnRuntimeUpgrade for MigrateToV8<T> {
+		fn on_runtime_upgrade() -> Weight {
+			let on_chain_version = Pallet::<T>::on_chain_storage_version();
+			if on_chain_version != 7 {
+				log::info!(
+					target: LOG_TARGET,
+					"skipping v8 migration, on-chain storage version is {:?}",
+					on_chain_version
+				);
+				return T::DbWeight::get().reads(1)
+			}
+
+			let mut counted = 0u64;
+			for organization_id in Devices::<T>::iter_values() {
+				counted += 1;
+				DeviceCounts::<T>::mutate(organization_id, |count| *count += 1);
+			}
+
+			StorageVersion::new(8).put::<Pallet<T>>();
+			log::info!(target: LOG_TARGET, "counted {} devices in v8", counted);
+
+			T::DbWeight::get().reads_writes(2 * counted + 1, counted + 1)
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<Vec<u8>, &'static str> {
+			Ok((Devices::<T>::iter_keys().count() as u32).encode())
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(state: Vec<u8>) -> Result<(), &'static str> {
+			let count: u32 = Decode::decode(&mut &state[..])
+				.map_err(|_| "pre_upgrade state could not be decoded")?;
+			ensure!(
+				DeviceCounts::<T>::iter_values().sum::<u32>() == count,
+				"devices were miscounted during migration"
+			);
+			ensure!(
+				Pallet::<T>::on_chain_storage_version() == 8,
+				"storage version was not bumped to 8"
+			);
+			Ok(())
+		}
+	}
+}
@@ -1,8 +1,13 @@
 use crate as pallet_logistics;
-use frame_support::traits::{ConstU16, ConstU64};
+use crate::{FeeDistribution, Roles};
+use frame_support::{
+	parameter_types,
+	traits::{ConstU16, ConstU32, ConstU64, GenesisBuild},
+};
 use sp_core::H256;
+use sp_keystore::{testing::KeyStore, KeystoreExt};
 use sp_runtime::{
-	testing::Header,
+	testing::{Header, UintAuthorityId},
 	traits::{BlakeTwo256, IdentityLookup},
 };
 
@@ -17,6 +22,8 @@ frame_support::construct_runtime!(
 		UncheckedExtrinsic = UncheckedExtrinsic,
 	{
 		System: frame_system,
+		Timestamp: pallet_timestamp,
+		Balances: pallet_balances,
 		LogisticsModule: pallet_logistics,
 	}
 );
@@ -39,7 +46,7 @@ impl frame_system::Config for Test {
 	type BlockHashCount = ConstU64<250>;
 	type Version = ();
 	type PalletInfo = PalletInfo;
-	type AccountData = ();
+	type AccountData = pallet_balances::AccountData<u64>;
 	type OnNewAccount = ();
 	type OnKilledAccount = ();
 	type SystemWeightInfo = ();
@@ -48,11 +55,101 @@ impl frame_system::Config for Test {
 	type MaxConsumers = frame_support::traits::ConstU32<16>;
 }
 
+impl pallet_timestamp::Config for Test {
+	type Moment = u64;
+	type OnTimestampSet = ();
+	type MinimumPeriod = ConstU64<1>;
+	type WeightInfo = ();
+}
+
+impl pallet_balances::Config for Test {
+	type MaxLocks = ();
+	type MaxReserves = ();
+	type ReserveIdentifier = [u8; 8];
+	type Balance = u64;
+	type RuntimeEvent = RuntimeEvent;
+	type DustRemoval = ();
+	type ExistentialDeposit = ConstU64<1>;
+	type AccountStore = System;
+	type WeightInfo = ();
+}
+
+parameter_types! {
+	pub static Distribution: FeeDistribution = FeeDistribution::PerLeg;
+}
+
 impl pallet_logistics::Config for Test {
 	type RuntimeEvent = RuntimeEvent;
+	type WeightInfo = ();
+	type HandoffTimeout = ConstU64<10>;
+	type TimeProvider = Timestamp;
+	type MaxCustodyHops = ConstU32<8>;
+	type MaxContainerSize = ConstU32<3>;
+	type MaxSplitParts = ConstU32<4>;
+	type MaxEvidence = ConstU32<2>;
+	type DeliveredRetention = ConstU64<5>;
+	type AdminOrigin = frame_system::EnsureRoot<u64>;
+	type MaxManifestItems = ConstU32<4>;
+	type Currency = Balances;
+	type FeeDistribution = Distribution;
+	type DepositBase = ConstU64<100>;
+	type DepositPerByte = ConstU64<1>;
+	type AbandonmentTimeout = ConstU64<20>;
+	type Slash = ();
+	type MaxDeadlinesPerBlock = ConstU32<2>;
+	type ExpectedBlockTime = ConstU64<6_000>;
+	type ArbiterOrigin = frame_system::EnsureRoot<u64>;
+	type ClaimBond = ConstU64<50>;
+	type DeviceId = UintAuthorityId;
+	type MaxDeviceSubmissions = ConstU32<3>;
+	type MaxDevicesPerOrganization = ConstU32<2>;
+	type DeviceRateWindow = ConstU64<10>;
+	type DeviceSubmissionPriority = ConstU64<1_000>;
+	type TelemetryWindow = ConstU32<2>;
 }
 
+/// Registers and hands off shipments, in the shipper organization.
+pub const SHIPPER: u64 = 1;
+/// Carrier that also runs its own hubs, in the carrier organization.
+pub const CARRIER: u64 = 2;
+/// Another member of the carrier organization.
+pub const COURIER: u64 = 3;
+/// Member of no organization.
+pub const STRANGER: u64 = 4;
+/// Runs facilities, in the operator organization.
+pub const OPERATOR: u64 = 5;
+/// Whom shipments are meant for, in the consignee organization.
+pub const CONSIGNEE: u64 = 6;
+
+/// Free balance every account above starts with.
+pub const ENDOWMENT: u64 = 1_000_000;
+
 // Build genesis storage according to the mock runtime.
 pub fn new_test_ext() -> sp_io::TestExternalities {
-	frame_system::GenesisConfig::default().build_storage::<Test>().unwrap().into()
+	let mut storage = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	pallet_balances::GenesisConfig::<Test> {
+		balances: [SHIPPER, CARRIER, COURIER, STRANGER, OPERATOR, CONSIGNEE]
+			.into_iter()
+			.map(|who| (who, ENDOWMENT))
+			.collect(),
+	}
+	.assimilate_storage(&mut storage)
+	.unwrap();
+	pallet_logistics::GenesisConfig::<Test> {
+		organizations: vec![
+			([1; 32], Roles::SHIPPER, vec![SHIPPER]),
+			([2; 32], Roles::CARRIER | Roles::WAREHOUSE_OPERATOR, vec![CARRIER, COURIER]),
+			([3; 32], Roles::WAREHOUSE_OPERATOR, vec![OPERATOR]),
+			([4; 32], Roles::CONSIGNEE, vec![CONSIGNEE]),
+		],
+	}
+	.assimilate_storage(&mut storage)
+	.unwrap();
+
+	let mut ext: sp_io::TestExternalities = storage.into();
+	// Benchmarks generate and sign with keys held in the keystore.
+	ext.register_extension(KeystoreExt(std::sync::Arc::new(KeyStore::new())));
+	// Go past genesis block so events get deposited
+	ext.execute_with(|| System::set_block_number(1));
+	ext
 }
@@ -0,0 +1,76 @@
+//! Roles an organization can hold in the logistics network.
+
+use codec::{Decode, Encode, MaxEncodedLen};
+use core::ops::BitOr;
+use scale_info::TypeInfo;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+use sp_runtime::RuntimeDebug;
+
+/// A set of roles, stored as a bitmask.
+#[derive(
+	Clone, Copy, Default, Eq, PartialEq, RuntimeDebug, Encode, Decode, TypeInfo, MaxEncodedLen,
+)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct Roles(u8);
+
+impl Roles {
+	/// Registers shipments and hands them to the first carrier.
+	pub const SHIPPER: Roles = Roles(1 << 0);
+	/// Moves shipments between facilities and delivers them.
+	pub const CARRIER: Roles = Roles(1 << 1);
+	/// Runs warehouses, hubs and depots and scans shipments in and out of them.
+	pub const WAREHOUSE_OPERATOR: Roles = Roles(1 << 2);
+	/// Receives shipments at their destination.
+	pub const CONSIGNEE: Roles = Roles(1 << 3);
+	/// Inspects and holds shipments at borders.
+	pub const CUSTOMS: Roles = Roles(1 << 4);
+
+	/// Every role that can take physical custody of a shipment on its way.
+	pub const HANDLERS: Roles =
+		Roles(Self::CARRIER.0 | Self::WAREHOUSE_OPERATOR.0 | Self::CUSTOMS.0);
+
+	/// No roles at all.
+	pub const fn empty() -> Self {
+		Roles(0)
+	}
+
+	/// Every defined role.
+	pub const fn all() -> Self {
+		Roles(
+			Self::SHIPPER.0 |
+				Self::CARRIER.0 |
+				Self::WAREHOUSE_OPERATOR.0 |
+				Self::CONSIGNEE.0 |
+				Self::CUSTOMS.0,
+		)
+	}
+
+	/// Whether every role in `other` is also in `self`.
+	pub fn contains(&self, other: Roles) -> bool {
+		self.0 & other.0 == other.0
+	}
+
+	/// Whether `self` and `other` have at least one role in common.
+	pub fn intersects(&self, other: Roles) -> bool {
+		self.0 & other.0 != 0
+	}
+
+	/// Whether no roles are set.
+	pub fn is_empty(&self) -> bool {
+		self.0 == 0
+	}
+
+	/// Whether only defined roles are set.
+	pub fn is_valid(&self) -> bool {
+		Self::all().contains(*self)
+	}
+}
+
+impl BitOr for Roles {
+	type Output = Roles;
+
+	fn bitor(self, rhs: Roles) -> Roles {
+		Roles(self.0 | rhs.0)
+	}
+}
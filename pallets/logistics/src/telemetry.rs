@@ -0,0 +1,226 @@
+//! Sensor readings reported for a shipment and the conditions it has to be kept in.
+
+use codec::{Decode, Encode, MaxEncodedLen};
+use scale_info::TypeInfo;
+use sp_runtime::RuntimeDebug;
+use sp_std::vec::Vec;
+
+/// What a sensor measures.
+#[derive(Clone, Copy, Eq, PartialEq, RuntimeDebug, Encode, Decode, TypeInfo, MaxEncodedLen)]
+pub enum Condition {
+	Temperature,
+	Humidity,
+	Shock,
+}
+
+/// What the sensors travelling with a shipment measured at one point in time. Values of sensors
+/// a device does not have are left out.
+#[derive(Clone, Default, Eq, PartialEq, RuntimeDebug, Encode, Decode, TypeInfo, MaxEncodedLen)]
+pub struct Reading {
+	/// Temperature in hundredths of a degree Celsius.
+	pub temperature: Option<i16>,
+	/// Relative humidity in tenths of a percent.
+	pub humidity: Option<u16>,
+	/// Peak acceleration since the previous reading in hundredths of g.
+	pub shock: Option<u16>,
+}
+
+impl Reading {
+	/// Each value in the reading, paired with what it measures.
+	pub fn values(&self) -> impl Iterator<Item = (Condition, i32)> {
+		[
+			(Condition::Temperature, self.temperature.map(i32::from)),
+			(Condition::Humidity, self.humidity.map(i32::from)),
+			(Condition::Shock, self.shock.map(i32::from)),
+		]
+		.into_iter()
+		.filter_map(|(condition, value)| value.map(|value| (condition, value)))
+	}
+
+	/// Whether the reading has at least one value and its humidity is a valid percentage.
+	pub fn is_valid(&self) -> bool {
+		self.values().next().is_some() &&
+			!matches!(self.humidity, Some(humidity) if humidity > 1_000)
+	}
+}
+
+/// Inclusive range of acceptable values.
+#[derive(Clone, Copy, Eq, PartialEq, RuntimeDebug, Encode, Decode, TypeInfo, MaxEncodedLen)]
+pub struct Range<V> {
+	pub min: V,
+	pub max: V,
+}
+
+impl<V: PartialOrd + Copy + Into<i32>> Range<V> {
+	/// Whether `value` is within the range.
+	pub fn contains(&self, value: i32) -> bool {
+		self.min.into() <= value && value <= self.max.into()
+	}
+
+	/// Whether the range is not empty.
+	pub fn is_valid(&self) -> bool {
+		self.min <= self.max
+	}
+}
+
+/// Ranges a shipment has to be kept within. Conditions without a range are not checked.
+#[derive(
+	Clone, Copy, Default, Eq, PartialEq, RuntimeDebug, Encode, Decode, TypeInfo, MaxEncodedLen,
+)]
+pub struct ConditionRanges {
+	/// In hundredths of a degree Celsius.
+	pub temperature: Option<Range<i16>>,
+	/// In tenths of a percent of relative humidity.
+	pub humidity: Option<Range<u16>>,
+	/// In hundredths of g.
+	pub shock: Option<Range<u16>>,
+}
+
+impl ConditionRanges {
+	/// Whether no range is empty.
+	pub fn is_valid(&self) -> bool {
+		self.temperature.iter().all(Range::is_valid) &&
+			self.humidity.iter().all(Range::is_valid) &&
+			self.shock.iter().all(Range::is_valid)
+	}
+
+	/// Values in `reading` that fall outside their range, paired with what they measure.
+	pub fn breaches(&self, reading: &Reading) -> Vec<(Condition, i32)> {
+		reading
+			.values()
+			.filter(|&(condition, value)| {
+				let within = match condition {
+					Condition::Temperature => self.temperature.map(|range| range.contains(value)),
+					Condition::Humidity => self.humidity.map(|range| range.contains(value)),
+					Condition::Shock => self.shock.map(|range| range.contains(value)),
+				};
+				within == Some(false)
+			})
+			.collect()
+	}
+}
+
+/// Lowest, highest and mean of the values read for one condition.
+#[derive(
+	Clone, Copy, Default, Eq, PartialEq, RuntimeDebug, Encode, Decode, TypeInfo, MaxEncodedLen,
+)]
+pub struct Aggregate {
+	pub min: i32,
+	pub max: i32,
+	pub sum: i64,
+	/// Number of values read, `min`, `max` and `sum` are meaningless while it is zero.
+	pub count: u32,
+}
+
+impl Aggregate {
+	/// Fold `value` into the aggregate.
+	pub fn add(&mut self, value: i32) {
+		if self.count == 0 {
+			self.min = value;
+			self.max = value;
+		} else {
+			self.min = self.min.min(value);
+			self.max = self.max.max(value);
+		}
+		self.sum = self.sum.saturating_add(value as i64);
+		self.count = self.count.saturating_add(1);
+	}
+
+	/// The aggregate over the values of both `self` and `other`.
+	pub fn merge(&self, other: &Aggregate) -> Aggregate {
+		if self.count == 0 {
+			return *other
+		}
+		if other.count == 0 {
+			return *self
+		}
+		Aggregate {
+			min: self.min.min(other.min),
+			max: self.max.max(other.max),
+			sum: self.sum.saturating_add(other.sum),
+			count: self.count.saturating_add(other.count),
+		}
+	}
+
+	/// Mean of the values read, rounded towards zero, or `None` if there were none.
+	pub fn mean(&self) -> Option<i32> {
+		(self.count > 0).then(|| (self.sum / self.count as i64) as i32)
+	}
+}
+
+/// Aggregates over a run of consecutive readings.
+#[derive(
+	Clone, Copy, Default, Eq, PartialEq, RuntimeDebug, Encode, Decode, TypeInfo, MaxEncodedLen,
+)]
+pub struct Window {
+	pub temperature: Aggregate,
+	pub humidity: Aggregate,
+	pub shock: Aggregate,
+	/// Number of readings in the window.
+	pub readings: u32,
+}
+
+impl Window {
+	/// Fold `reading` into the window.
+	pub fn add(&mut self, reading: &Reading) {
+		for (condition, value) in reading.values() {
+			match condition {
+				Condition::Temperature => self.temperature.add(value),
+				Condition::Humidity => self.humidity.add(value),
+				Condition::Shock => self.shock.add(value),
+			}
+		}
+		self.readings = self.readings.saturating_add(1);
+	}
+}
+
+/// Rolling aggregates over the latest readings reported for a shipment, which take the same
+/// space however many readings there are.
+///
+/// Readings are folded into `current` until it holds a full window of them, which then becomes
+/// `previous` and makes `current` start over. The aggregates cover both, so between one and two
+/// windows of the latest readings, and an early excursion ages out of `min` and `max` once two
+/// more windows have been read.
+#[derive(
+	Clone, Copy, Default, Eq, PartialEq, RuntimeDebug, Encode, Decode, TypeInfo, MaxEncodedLen,
+)]
+pub struct Aggregates {
+	/// The window readings are being folded into.
+	pub current: Window,
+	/// The last full window before it.
+	pub previous: Window,
+	/// Number of readings reported over the shipment's lifetime.
+	pub readings: u32,
+	/// Number of values that fell outside their range over the shipment's lifetime.
+	pub breaches: u32,
+	/// Unix time in milliseconds of the latest reading.
+	pub last_reported_at: u64,
+}
+
+impl Aggregates {
+	/// Fold `reading`, reported at Unix time `timestamp`, into the aggregates, moving on to a new
+	/// window once the current one holds `window` readings.
+	pub fn add(&mut self, reading: &Reading, timestamp: u64, window: u32) {
+		if self.current.readings >= window.max(1) {
+			self.previous = core::mem::take(&mut self.current);
+		}
+		self.current.add(reading);
+		self.readings = self.readings.saturating_add(1);
+		self.last_reported_at = timestamp;
+	}
+
+	/// Temperatures read over the latest windows.
+	pub fn temperature(&self) -> Aggregate {
+		self.previous.temperature.merge(&self.current.temperature)
+	}
+
+	/// Humidities read over the latest windows.
+	pub fn humidity(&self) -> Aggregate {
+		self.previous.humidity.merge(&self.current.humidity)
+	}
+
+	/// Shocks read over the latest windows.
+	pub fn shock(&self) -> Aggregate {
+		self.previous.shock.merge(&self.current.shock)
+	}
+}
@@ -1,27 +1,3430 @@
-use crate::{mock::*, Error, Event};
-use frame_support::{assert_noop, assert_ok};
+use crate::{
+	delivery::{ConsigneeSignature, DeliveryClass, ProofOfDelivery},
+	devices::{DevicePayload, Submission},
+	manifest::{Dimensions, HandlingFlags, HazmatClass, ManifestItem},
+	migrations,
+	mock::*,
+	telemetry::{Aggregate, Condition, ConditionRanges, Range, Reading},
+	AcceptableRanges, Claim, ClaimKind, Claims, ConsigneeKeys, ContainedIn, Contents, Coords,
+	CustodyHistory, CustodyRecord, Deadline, DeadlineAgenda, Deliveries, Deposits, DeviceCounts,
+	DeviceUsages, Devices, Error, Escrows, Event, Facilities, FacilityKind, FacilityStatus,
+	FeeDistribution, FormerCustodians, LastScanned, ManifestOf, Manifests, Members,
+	PendingHandoffs, PruneCursor, Roles, Shipment, ShipmentStatus, Shipments, SlaBreach,
+	SlaBreaches, SplitFrom, SplitPart, Telemetry, UpheldClaims, WeightInfo,
+};
+use frame_support::{
+	assert_noop, assert_ok,
+	dispatch::{DispatchError, DispatchResultWithPostInfo},
+	pallet_prelude::{Decode, Encode, StorageVersion},
+	traits::{GetStorageVersion, Hooks, OnRuntimeUpgrade},
+	weights::Weight,
+};
+use sp_core::{sr25519, Pair};
+use sp_runtime::{
+	testing::{TestSignature, UintAuthorityId},
+	traits::ValidateUnsigned,
+	transaction_validity::{
+		InvalidTransaction, TransactionSource, TransactionValidity, UnknownTransaction,
+	},
+	RuntimeAppPublic,
+};
+
+/// The depot every test shipment is addressed to, see [`register_destination`].
+const DESTINATION: u64 = 0;
+
+/// The depot shipments created with [`create_returnable`] leave from.
+const ORIGIN: u64 = 1;
+
+/// Carriage fee every test shipment is created with.
+const FEE: u64 = 90;
+
+fn coords() -> Coords {
+	Coords::new(51_507_400, -127_800).expect("within range")
+}
+
+fn manifest() -> ManifestOf<Test> {
+	ManifestOf::<Test> {
+		items: vec![ManifestItem { sku_hash: [1; 32], lot_hash: None, quantity: 12 }]
+			.try_into()
+			.unwrap(),
+		gross_weight: 2_500,
+		dimensions: Dimensions { length: 400, width: 300, height: 200 },
+		declared_value: 15_000,
+		handling: HandlingFlags { fragile: true, ..Default::default() },
+	}
+}
+
+/// Storage deposit held for a shipment with [`manifest`].
+fn deposit() -> u64 {
+	LogisticsModule::deposit_for(&manifest())
+}
+
+fn shipment(shipment_id: u64) -> Shipment<Test> {
+	Shipments::<Test>::get(shipment_id).expect("shipment exists")
+}
+
+fn register_destination() {
+	if !Facilities::<Test>::contains_key(DESTINATION) {
+		assert_ok!(LogisticsModule::register_facility(
+			RuntimeOrigin::signed(OPERATOR),
+			[0; 32],
+			coords(),
+			FacilityKind::Depot
+		));
+	}
+}
+
+fn create(shipment_id: u64) {
+	register_destination();
+	assert_ok!(LogisticsModule::begin_transit(
+		RuntimeOrigin::signed(SHIPPER),
+		shipment_id,
+		coords(),
+		None,
+		DESTINATION,
+		CONSIGNEE,
+		manifest(),
+		FEE,
+		None
+	));
+}
+
+/// Create a shipment that left from `ORIGIN` and can be returned there.
+fn create_returnable(shipment_id: u64) {
+	register_destination();
+	if !Facilities::<Test>::contains_key(ORIGIN) {
+		assert_ok!(LogisticsModule::register_facility(
+			RuntimeOrigin::signed(OPERATOR),
+			[1; 32],
+			coords(),
+			FacilityKind::Depot
+		));
+	}
+	assert_ok!(LogisticsModule::begin_transit(
+		RuntimeOrigin::signed(SHIPPER),
+		shipment_id,
+		coords(),
+		Some(ORIGIN),
+		DESTINATION,
+		CONSIGNEE,
+		manifest(),
+		FEE,
+		None
+	));
+}
+
+/// Create a shipment promised to be delivered by `deadline`.
+fn create_due(shipment_id: u64, deadline: Deadline<u64>) {
+	register_destination();
+	assert_ok!(LogisticsModule::begin_transit(
+		RuntimeOrigin::signed(SHIPPER),
+		shipment_id,
+		coords(),
+		None,
+		DESTINATION,
+		CONSIGNEE,
+		manifest(),
+		FEE,
+		Some(deadline)
+	));
+}
+
+fn hand_off(shipment_id: u64, from: u64, to: u64) {
+	assert_ok!(LogisticsModule::offer_handoff(RuntimeOrigin::signed(from), shipment_id, to));
+	assert_ok!(LogisticsModule::shipment_received(
+		RuntimeOrigin::signed(to),
+		shipment_id,
+		coords()
+	));
+}
+
+/// Create a shipment and hand it to `CARRIER`, then to `COURIER`, who delivers it.
+fn deliver(shipment_id: u64) {
+	create(shipment_id);
+	hand_off(shipment_id, SHIPPER, CARRIER);
+	hand_off(shipment_id, CARRIER, COURIER);
+	assert_ok!(LogisticsModule::shipment_delivered(
+		RuntimeOrigin::signed(COURIER),
+		shipment_id,
+		coords(),
+		None,
+		Default::default()
+	));
+}
+
+/// Number of `ShipmentOverdue` events deposited so far.
+fn overdue_events() -> usize {
+	System::events()
+		.into_iter()
+		.filter(|record| {
+			matches!(record.event, RuntimeEvent::LogisticsModule(Event::ShipmentOverdue { .. }))
+		})
+		.count()
+}
+
+fn idle(n: u64, limit: Weight) -> Weight {
+	System::set_block_number(n);
+	LogisticsModule::on_idle(n, limit)
+}
+
+#[test]
+fn begin_transit_creates_shipment_held_by_shipper() {
+	new_test_ext().execute_with(|| {
+		create(0);
+
+		let package = shipment(0);
+		assert_eq!(package.shipped_by, SHIPPER);
+		assert_eq!(package.received_by, SHIPPER);
+		assert_eq!(package.destination, DESTINATION);
+		assert_eq!(package.received_on, 1);
+		assert_eq!(package.status, ShipmentStatus::Created);
+		assert_eq!(Shipments::<Test>::count(), 1);
+		assert_eq!(Manifests::<Test>::get(0), Some(manifest()));
+		assert_eq!(Escrows::<Test>::get(0), Some(FEE));
+		assert_eq!(Balances::reserved_balance(SHIPPER), FEE + deposit());
+
+		System::assert_has_event(
+			Event::ShipmentCreated {
+				shipment_id: 0,
+				shipped_by: SHIPPER,
+				destination: DESTINATION,
+			}
+			.into(),
+		);
+		System::assert_last_event(
+			Event::ShipmentReceived { shipment_id: 0, received_by: SHIPPER, received_at: coords() }
+				.into(),
+		);
+	});
+}
+
+#[test]
+fn begin_transit_rejects_duplicate_id() {
+	new_test_ext().execute_with(|| {
+		create(0);
+
+		assert_noop!(
+			LogisticsModule::begin_transit(
+				RuntimeOrigin::signed(SHIPPER),
+				0,
+				coords(),
+				None,
+				0,
+				CONSIGNEE,
+				manifest(),
+				FEE,
+				None
+			),
+			Error::<Test>::DuplicateShipment
+		);
+		assert_eq!(Shipments::<Test>::count(), 1);
+	});
+}
+
+#[test]
+fn begin_transit_requires_open_destination() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			LogisticsModule::begin_transit(
+				RuntimeOrigin::signed(SHIPPER),
+				0,
+				coords(),
+				None,
+				0,
+				CONSIGNEE,
+				manifest(),
+				FEE,
+				None
+			),
+			Error::<Test>::FacilityDoesNotExist
+		);
+
+		register_destination();
+		assert_ok!(LogisticsModule::set_facility_status(
+			RuntimeOrigin::signed(OPERATOR),
+			DESTINATION,
+			FacilityStatus::Suspended
+		));
+		create(0);
+
+		assert_ok!(LogisticsModule::set_facility_status(
+			RuntimeOrigin::signed(OPERATOR),
+			DESTINATION,
+			FacilityStatus::Closed
+		));
+		assert_noop!(
+			LogisticsModule::begin_transit(
+				RuntimeOrigin::signed(SHIPPER),
+				1,
+				coords(),
+				None,
+				DESTINATION,
+				CONSIGNEE,
+				manifest(),
+				FEE,
+				None
+			),
+			Error::<Test>::FacilityClosed
+		);
+	});
+}
+
+#[test]
+fn begin_transit_requires_open_origin() {
+	new_test_ext().execute_with(|| {
+		register_destination();
+		assert_noop!(
+			LogisticsModule::begin_transit(
+				RuntimeOrigin::signed(SHIPPER),
+				0,
+				coords(),
+				Some(ORIGIN),
+				DESTINATION,
+				CONSIGNEE,
+				manifest(),
+				FEE,
+				None
+			),
+			Error::<Test>::FacilityDoesNotExist
+		);
+
+		create_returnable(0);
+		assert_eq!(shipment(0).origin, Some(ORIGIN));
+
+		assert_ok!(LogisticsModule::set_facility_status(
+			RuntimeOrigin::signed(OPERATOR),
+			ORIGIN,
+			FacilityStatus::Closed
+		));
+		assert_noop!(
+			LogisticsModule::begin_transit(
+				RuntimeOrigin::signed(SHIPPER),
+				1,
+				coords(),
+				Some(ORIGIN),
+				DESTINATION,
+				CONSIGNEE,
+				manifest(),
+				FEE,
+				None
+			),
+			Error::<Test>::FacilityClosed
+		);
+	});
+}
+
+#[test]
+fn register_facility_assigns_sequential_ids() {
+	new_test_ext().execute_with(|| {
+		for (facility_id, kind) in
+			[FacilityKind::Warehouse, FacilityKind::Hub].into_iter().enumerate()
+		{
+			assert_ok!(LogisticsModule::register_facility(
+				RuntimeOrigin::signed(OPERATOR),
+				[facility_id as u8; 32],
+				coords(),
+				kind
+			));
+			System::assert_last_event(
+				Event::FacilityRegistered {
+					facility_id: facility_id as u64,
+					owner: OPERATOR,
+					kind,
+				}
+				.into(),
+			);
+		}
+
+		let hub = LogisticsModule::facility(1).expect("facility was registered");
+		assert_eq!(hub.owner, OPERATOR);
+		assert_eq!(hub.name_hash, [1; 32]);
+		assert_eq!(hub.coords, coords());
+		assert_eq!(hub.kind, FacilityKind::Hub);
+		assert_eq!(hub.status, FacilityStatus::Operational);
+		assert_eq!(LogisticsModule::facility(2), None);
+	});
+}
+
+#[test]
+fn facilities_can_only_be_changed_by_their_owner() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			LogisticsModule::update_facility(
+				RuntimeOrigin::signed(OPERATOR),
+				0,
+				[1; 32],
+				coords(),
+				FacilityKind::Hub
+			),
+			Error::<Test>::FacilityDoesNotExist
+		);
+
+		register_destination();
+		assert_noop!(
+			LogisticsModule::update_facility(
+				RuntimeOrigin::signed(CARRIER),
+				DESTINATION,
+				[1; 32],
+				coords(),
+				FacilityKind::Hub
+			),
+			Error::<Test>::NotFacilityOwner
+		);
+		assert_noop!(
+			LogisticsModule::set_facility_status(
+				RuntimeOrigin::signed(CARRIER),
+				DESTINATION,
+				FacilityStatus::Closed
+			),
+			Error::<Test>::NotFacilityOwner
+		);
+
+		let moved = Coords::new(51_470_000, -454_300).unwrap();
+		assert_ok!(LogisticsModule::update_facility(
+			RuntimeOrigin::signed(OPERATOR),
+			DESTINATION,
+			[1; 32],
+			moved.clone(),
+			FacilityKind::Hub
+		));
+		System::assert_last_event(Event::FacilityUpdated { facility_id: DESTINATION }.into());
+		assert_ok!(LogisticsModule::set_facility_status(
+			RuntimeOrigin::signed(OPERATOR),
+			DESTINATION,
+			FacilityStatus::Suspended
+		));
+		System::assert_last_event(
+			Event::FacilityStatusChanged {
+				facility_id: DESTINATION,
+				status: FacilityStatus::Suspended,
+			}
+			.into(),
+		);
+
+		let facility = LogisticsModule::facility(DESTINATION).unwrap();
+		assert_eq!((facility.coords, facility.kind), (moved, FacilityKind::Hub));
+		assert_eq!(facility.status, FacilityStatus::Suspended);
+	});
+}
+
+#[test]
+fn begin_transit_requires_consignee_role() {
+	new_test_ext().execute_with(|| {
+		register_destination();
+		// Neither a member of any organization nor of one holding the consignee role.
+		for consignee in [STRANGER, CARRIER] {
+			assert_noop!(
+				LogisticsModule::begin_transit(
+					RuntimeOrigin::signed(SHIPPER),
+					0,
+					coords(),
+					None,
+					DESTINATION,
+					consignee,
+					manifest(),
+					FEE,
+					None
+				),
+				Error::<Test>::InvalidConsignee
+			);
+		}
+	});
+}
+
+#[test]
+fn begin_transit_rejects_invalid_manifest() {
+	new_test_ext().execute_with(|| {
+		register_destination();
+		let mut empty = manifest();
+		empty.items = Default::default();
+		let mut weightless = manifest();
+		weightless.gross_weight = 0;
+		let mut zero_quantity = manifest();
+		zero_quantity.items[0].quantity = 0;
+
+		for invalid in [empty, weightless, zero_quantity] {
+			assert_noop!(
+				LogisticsModule::begin_transit(
+					RuntimeOrigin::signed(SHIPPER),
+					0,
+					coords(),
+					None,
+					DESTINATION,
+					CONSIGNEE,
+					invalid,
+					FEE,
+					None
+				),
+				Error::<Test>::InvalidManifest
+			);
+		}
+	});
+}
+
+#[test]
+fn shipper_amends_manifest_before_first_handoff() {
+	new_test_ext().execute_with(|| {
+		create(0);
+		let mut amended = manifest();
+		amended
+			.items
+			.try_push(ManifestItem { sku_hash: [2; 32], lot_hash: Some([3; 32]), quantity: 3 })
+			.unwrap();
+		amended.handling.hazmat = Some(HazmatClass::FlammableLiquids);
+
+		assert_ok!(LogisticsModule::amend_manifest(
+			RuntimeOrigin::signed(SHIPPER),
+			0,
+			amended.clone()
+		));
+
+		System::assert_last_event(Event::ManifestAmended { shipment_id: 0 }.into());
+		let stored = Manifests::<Test>::get(0).unwrap();
+		assert_eq!(stored, amended);
+		assert_eq!(stored.item_count(), 15);
+
+		// The longer manifest needs a larger deposit.
+		let required = LogisticsModule::deposit_for(&amended);
+		assert!(required > deposit());
+		assert_eq!(Deposits::<Test>::get(0), Some(required));
+		assert_eq!(Balances::reserved_balance(SHIPPER), FEE + required);
+	});
+}
+
+#[test]
+fn amend_manifest_fails_for_invalid_requests() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			LogisticsModule::amend_manifest(RuntimeOrigin::signed(SHIPPER), 0, manifest()),
+			Error::<Test>::ShipmentDoesNotExist
+		);
+
+		create(0);
+		let shippers = Members::<Test>::get(SHIPPER).unwrap();
+		assert_ok!(LogisticsModule::add_member(RuntimeOrigin::root(), shippers, STRANGER));
+		assert_noop!(
+			LogisticsModule::amend_manifest(RuntimeOrigin::signed(STRANGER), 0, manifest()),
+			Error::<Test>::NotShipper
+		);
+		assert_noop!(
+			LogisticsModule::amend_manifest(RuntimeOrigin::signed(CARRIER), 0, manifest()),
+			Error::<Test>::MissingRole
+		);
+		let mut invalid = manifest();
+		invalid.dimensions.height = 0;
+		assert_noop!(
+			LogisticsModule::amend_manifest(RuntimeOrigin::signed(SHIPPER), 0, invalid),
+			Error::<Test>::InvalidManifest
+		);
+	});
+}
+
+#[test]
+fn manifest_is_locked_once_offered() {
+	new_test_ext().execute_with(|| {
+		create(0);
+		assert_ok!(LogisticsModule::offer_handoff(RuntimeOrigin::signed(SHIPPER), 0, CARRIER));
+		assert_noop!(
+			LogisticsModule::amend_manifest(RuntimeOrigin::signed(SHIPPER), 0, manifest()),
+			Error::<Test>::ManifestLocked
+		);
+
+		assert_ok!(LogisticsModule::shipment_received(RuntimeOrigin::signed(CARRIER), 0, coords()));
+		assert_noop!(
+			LogisticsModule::amend_manifest(RuntimeOrigin::signed(SHIPPER), 0, manifest()),
+			Error::<Test>::ManifestLocked
+		);
+	});
+}
+
+#[test]
+fn offer_handoff_records_pending_offer() {
+	new_test_ext().execute_with(|| {
+		create(0);
+
+		assert_ok!(LogisticsModule::offer_handoff(RuntimeOrigin::signed(SHIPPER), 0, CARRIER));
+
+		let handoff = PendingHandoffs::<Test>::get(0).expect("offer is pending");
+		assert_eq!((handoff.from, handoff.to, handoff.expires_on), (SHIPPER, CARRIER, 11));
+		System::assert_last_event(
+			Event::HandoffOffered { shipment_id: 0, from: SHIPPER, to: CARRIER, expires_on: 11 }
+				.into(),
+		);
+		// Custody does not move until the offer is accepted.
+		assert_eq!(shipment(0).received_by, SHIPPER);
+	});
+}
+
+#[test]
+fn offer_handoff_replaces_previous_offer() {
+	new_test_ext().execute_with(|| {
+		create(0);
+
+		assert_ok!(LogisticsModule::offer_handoff(RuntimeOrigin::signed(SHIPPER), 0, STRANGER));
+		assert_ok!(LogisticsModule::offer_handoff(RuntimeOrigin::signed(SHIPPER), 0, CARRIER));
+
+		assert_eq!(PendingHandoffs::<Test>::get(0).map(|h| h.to), Some(CARRIER));
+	});
+}
+
+#[test]
+fn offer_handoff_fails_for_invalid_requests() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			LogisticsModule::offer_handoff(RuntimeOrigin::signed(SHIPPER), 0, CARRIER),
+			Error::<Test>::ShipmentDoesNotExist
+		);
+
+		create(0);
+
+		assert_noop!(
+			LogisticsModule::offer_handoff(RuntimeOrigin::signed(COURIER), 0, STRANGER),
+			Error::<Test>::NotCurrentHolder
+		);
+		assert_noop!(
+			LogisticsModule::offer_handoff(RuntimeOrigin::signed(SHIPPER), 0, SHIPPER),
+			Error::<Test>::CannotHandoffToSelf
+		);
+	});
+}
+
+#[test]
+fn shipment_received_moves_custody_to_offered_account() {
+	new_test_ext().execute_with(|| {
+		create(0);
+		assert_ok!(LogisticsModule::offer_handoff(RuntimeOrigin::signed(SHIPPER), 0, CARRIER));
+		System::set_block_number(3);
+
+		assert_ok!(LogisticsModule::shipment_received(RuntimeOrigin::signed(CARRIER), 0, coords()));
+
+		let package = shipment(0);
+		assert_eq!(package.received_by, CARRIER);
+		assert_eq!(package.received_on, 3);
+		assert_eq!(package.status, ShipmentStatus::InTransit);
+		assert!(!PendingHandoffs::<Test>::contains_key(0));
+		System::assert_has_event(
+			Event::ShipmentReceived { shipment_id: 0, received_by: CARRIER, received_at: coords() }
+				.into(),
+		);
+		System::assert_last_event(
+			Event::ShipmentStatusChanged {
+				shipment_id: 0,
+				from: ShipmentStatus::Created,
+				to: ShipmentStatus::InTransit,
+			}
+			.into(),
+		);
+	});
+}
+
+#[test]
+fn shipment_received_cannot_hijack_custody() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			LogisticsModule::shipment_received(RuntimeOrigin::signed(COURIER), 0, coords()),
+			Error::<Test>::ShipmentDoesNotExist
+		);
+
+		create(0);
+		assert_noop!(
+			LogisticsModule::shipment_received(RuntimeOrigin::signed(COURIER), 0, coords()),
+			Error::<Test>::NoPendingOffer
+		);
+
+		assert_ok!(LogisticsModule::offer_handoff(RuntimeOrigin::signed(SHIPPER), 0, CARRIER));
+		assert_noop!(
+			LogisticsModule::shipment_received(RuntimeOrigin::signed(COURIER), 0, coords()),
+			Error::<Test>::NotOfferedRecipient
+		);
+		assert_eq!(shipment(0).received_by, SHIPPER);
+	});
+}
+
+#[test]
+fn shipment_received_rejects_expired_offer() {
+	new_test_ext().execute_with(|| {
+		create(0);
+		assert_ok!(LogisticsModule::offer_handoff(RuntimeOrigin::signed(SHIPPER), 0, CARRIER));
+
+		// The offer is still open in the block it expires in.
+		System::set_block_number(11);
+		assert_ok!(LogisticsModule::shipment_received(RuntimeOrigin::signed(CARRIER), 0, coords()));
+
+		assert_ok!(LogisticsModule::offer_handoff(RuntimeOrigin::signed(CARRIER), 0, COURIER));
+		System::set_block_number(22);
+		assert_noop!(
+			LogisticsModule::shipment_received(RuntimeOrigin::signed(COURIER), 0, coords()),
+			Error::<Test>::HandoffExpired
+		);
+	});
+}
+
+#[test]
+fn cancel_handoff_withdraws_offer() {
+	new_test_ext().execute_with(|| {
+		create(0);
+		assert_noop!(
+			LogisticsModule::cancel_handoff(RuntimeOrigin::signed(SHIPPER), 0),
+			Error::<Test>::NoPendingOffer
+		);
+
+		assert_ok!(LogisticsModule::offer_handoff(RuntimeOrigin::signed(SHIPPER), 0, CARRIER));
+		assert_noop!(
+			LogisticsModule::cancel_handoff(RuntimeOrigin::signed(CARRIER), 0),
+			Error::<Test>::NotCurrentHolder
+		);
+
+		assert_ok!(LogisticsModule::cancel_handoff(RuntimeOrigin::signed(SHIPPER), 0));
+		System::assert_last_event(Event::HandoffCancelled { shipment_id: 0 }.into());
+		assert_noop!(
+			LogisticsModule::shipment_received(RuntimeOrigin::signed(CARRIER), 0, coords()),
+			Error::<Test>::NoPendingOffer
+		);
+	});
+}
+
+#[test]
+fn status_transitions_follow_lifecycle() {
+	use ShipmentStatus::*;
+
+	assert!(Created.can_transition_to(&InTransit));
+	assert!(Created.can_transition_to(&Cancelled));
+	assert!(!Created.can_transition_to(&Delivered));
+	assert!(InTransit.can_transition_to(&InTransit));
+	assert!(OutForDelivery.can_transition_to(&DeliveryFailed));
+	assert!(DeliveryFailed.can_transition_to(&OutForDelivery));
+	assert!(!DeliveryFailed.can_transition_to(&Delivered));
+	assert!(!AtFacility.can_transition_to(&DeliveryFailed));
+
+	for status in [Delivered, Returned, Lost, Cancelled, Rejected] {
+		assert!(status.is_final());
+		assert!(!status.can_transition_to(&InTransit));
+	}
+}
+
+#[test]
+fn update_status_moves_shipment_along() {
+	new_test_ext().execute_with(|| {
+		create(0);
+		hand_off(0, SHIPPER, CARRIER);
+
+		for (from, to) in [
+			(ShipmentStatus::InTransit, ShipmentStatus::AtFacility),
+			(ShipmentStatus::AtFacility, ShipmentStatus::OutForDelivery),
+			(ShipmentStatus::OutForDelivery, ShipmentStatus::DeliveryFailed),
+		] {
+			assert_ok!(LogisticsModule::update_status(RuntimeOrigin::signed(CARRIER), 0, to));
+			assert_eq!(shipment(0).status, to);
+			System::assert_last_event(
+				Event::ShipmentStatusChanged { shipment_id: 0, from, to }.into(),
+			);
+		}
+	});
+}
+
+#[test]
+fn update_status_rejects_illegal_transitions() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			LogisticsModule::update_status(RuntimeOrigin::signed(CARRIER), 0, ShipmentStatus::Lost),
+			Error::<Test>::ShipmentDoesNotExist
+		);
+
+		create(0);
+		hand_off(0, SHIPPER, CARRIER);
+		assert_noop!(
+			LogisticsModule::update_status(
+				RuntimeOrigin::signed(CARRIER),
+				0,
+				ShipmentStatus::DeliveryFailed
+			),
+			Error::<Test>::InvalidStatusTransition
+		);
+		// Statuses with a dedicated call cannot be set directly.
+		assert_noop!(
+			LogisticsModule::update_status(
+				RuntimeOrigin::signed(CARRIER),
+				0,
+				ShipmentStatus::Delivered
+			),
+			Error::<Test>::InvalidStatusTransition
+		);
+		assert_noop!(
+			LogisticsModule::update_status(
+				RuntimeOrigin::signed(COURIER),
+				0,
+				ShipmentStatus::AtFacility
+			),
+			Error::<Test>::NotCurrentHolder
+		);
+	});
+}
+
+#[test]
+fn lost_shipment_is_final() {
+	new_test_ext().execute_with(|| {
+		create(0);
+		hand_off(0, SHIPPER, CARRIER);
+		assert_ok!(LogisticsModule::offer_handoff(RuntimeOrigin::signed(CARRIER), 0, COURIER));
+
+		assert_ok!(LogisticsModule::update_status(
+			RuntimeOrigin::signed(CARRIER),
+			0,
+			ShipmentStatus::Lost
+		));
+
+		assert!(!PendingHandoffs::<Test>::contains_key(0));
+		assert_noop!(
+			LogisticsModule::offer_handoff(RuntimeOrigin::signed(CARRIER), 0, COURIER),
+			Error::<Test>::ShipmentNotInTransit
+		);
+		assert_noop!(
+			LogisticsModule::shipment_delivered(
+				RuntimeOrigin::signed(CARRIER),
+				0,
+				coords(),
+				None,
+				Default::default()
+			),
+			Error::<Test>::ShipmentNotInTransit
+		);
+	});
+}
+
+#[test]
+fn shipment_delivered_closes_shipment() {
+	new_test_ext().execute_with(|| {
+		deliver(0);
+
+		let package = shipment(0);
+		assert_eq!(package.status, ShipmentStatus::Delivered);
+		assert_eq!(package.received_by, COURIER);
+		assert_eq!(PruneCursor::<Test>::get().tail, 1);
+		System::assert_has_event(
+			Event::ShipmentDelivered { shipment_id: 0, class: DeliveryClass::CourierAsserted }
+				.into(),
+		);
+		assert_eq!(
+			Deliveries::<Test>::get(0),
+			Some(ProofOfDelivery {
+				class: DeliveryClass::CourierAsserted,
+				consignee: None,
+				evidence: Default::default(),
+			})
+		);
+		System::assert_last_event(
+			Event::ShipmentStatusChanged {
+				shipment_id: 0,
+				from: ShipmentStatus::InTransit,
+				to: ShipmentStatus::Delivered,
+			}
+			.into(),
+		);
+
+		assert_noop!(
+			LogisticsModule::shipment_delivered(
+				RuntimeOrigin::signed(COURIER),
+				0,
+				coords(),
+				None,
+				Default::default()
+			),
+			Error::<Test>::ShipmentNotInTransit
+		);
+	});
+}
+
+#[test]
+fn delivery_pays_each_leg_an_equal_share() {
+	new_test_ext().execute_with(|| {
+		deliver(0);
+
+		assert_eq!(Escrows::<Test>::get(0), None);
+		assert_eq!(Balances::reserved_balance(SHIPPER), deposit());
+		assert_eq!(Balances::free_balance(SHIPPER), ENDOWMENT - FEE - deposit());
+		assert_eq!(Balances::free_balance(CARRIER), ENDOWMENT + FEE / 2);
+		assert_eq!(Balances::free_balance(COURIER), ENDOWMENT + FEE / 2);
+		System::assert_has_event(
+			Event::FeePaid { shipment_id: 0, to: CARRIER, amount: FEE / 2 }.into(),
+		);
+		System::assert_has_event(
+			Event::FeePaid { shipment_id: 0, to: COURIER, amount: FEE / 2 }.into(),
+		);
+	});
+}
+
+#[test]
+fn delivering_carrier_gets_the_remainder() {
+	new_test_ext().execute_with(|| {
+		register_destination();
+		assert_ok!(LogisticsModule::begin_transit(
+			RuntimeOrigin::signed(SHIPPER),
+			0,
+			coords(),
+			None,
+			DESTINATION,
+			CONSIGNEE,
+			manifest(),
+			100,
+			None
+		));
+		hand_off(0, SHIPPER, OPERATOR);
+		hand_off(0, OPERATOR, CARRIER);
+		hand_off(0, CARRIER, COURIER);
+		assert_ok!(LogisticsModule::shipment_delivered(
+			RuntimeOrigin::signed(COURIER),
+			0,
+			coords(),
+			None,
+			Default::default()
+		));
+
+		// Three custodians: 33 each, plus the one left over for the courier delivering.
+		assert_eq!(Balances::free_balance(OPERATOR), ENDOWMENT + 33);
+		assert_eq!(Balances::free_balance(CARRIER), ENDOWMENT + 33);
+		assert_eq!(Balances::free_balance(COURIER), ENDOWMENT + 34);
+		assert_eq!(Balances::free_balance(SHIPPER), ENDOWMENT - 100 - deposit());
+	});
+}
+
+#[test]
+fn handing_back_and_forth_does_not_dilute_other_custodians() {
+	new_test_ext().execute_with(|| {
+		register_destination();
+		assert_ok!(LogisticsModule::begin_transit(
+			RuntimeOrigin::signed(SHIPPER),
+			0,
+			coords(),
+			None,
+			DESTINATION,
+			CONSIGNEE,
+			manifest(),
+			90,
+			None
+		));
+		hand_off(0, SHIPPER, OPERATOR);
+		hand_off(0, OPERATOR, COURIER);
+		hand_off(0, COURIER, OPERATOR);
+		hand_off(0, OPERATOR, COURIER);
+		hand_off(0, COURIER, CARRIER);
+		assert_ok!(LogisticsModule::shipment_delivered(
+			RuntimeOrigin::signed(CARRIER),
+			0,
+			coords(),
+			None,
+			Default::default()
+		));
+
+		// Five legs, but three custodians who each get a third.
+		assert_eq!(Balances::free_balance(OPERATOR), ENDOWMENT + 30);
+		assert_eq!(Balances::free_balance(COURIER), ENDOWMENT + 30);
+		assert_eq!(Balances::free_balance(CARRIER), ENDOWMENT + 30);
+	});
+}
+
+#[test]
+fn fee_can_go_to_delivering_carrier_only() {
+	new_test_ext().execute_with(|| {
+		Distribution::set(FeeDistribution::DeliveringCarrier);
+		deliver(0);
+
+		assert_eq!(Balances::free_balance(CARRIER), ENDOWMENT);
+		assert_eq!(Balances::free_balance(COURIER), ENDOWMENT + FEE);
+		System::assert_has_event(
+			Event::FeePaid { shipment_id: 0, to: COURIER, amount: FEE }.into(),
+		);
+	});
+}
+
+#[test]
+fn lost_shipment_refunds_fee() {
+	new_test_ext().execute_with(|| {
+		create(0);
+		hand_off(0, SHIPPER, CARRIER);
+		assert_ok!(LogisticsModule::update_status(
+			RuntimeOrigin::signed(CARRIER),
+			0,
+			ShipmentStatus::Lost
+		));
+
+		assert_eq!(Escrows::<Test>::get(0), None);
+		assert_eq!(Balances::reserved_balance(SHIPPER), deposit());
+		assert_eq!(Balances::free_balance(SHIPPER), ENDOWMENT - deposit());
+		assert_eq!(Balances::free_balance(CARRIER), ENDOWMENT);
+		System::assert_has_event(
+			Event::FeeRefunded { shipment_id: 0, to: SHIPPER, amount: FEE }.into(),
+		);
+		assert_eq!(PruneCursor::<Test>::get().tail, 1);
+
+		// The shipper is not punished for what the carrier lost: nobody can reap it, and the
+		// deposit is released when it is pruned.
+		System::set_block_number(100);
+		assert_noop!(
+			LogisticsModule::reap_abandoned(RuntimeOrigin::signed(STRANGER), 0),
+			Error::<Test>::NotAbandoned
+		);
+		idle(100, Weight::MAX);
+		assert!(!Shipments::<Test>::contains_key(0));
+		assert_eq!(Balances::free_balance(SHIPPER), ENDOWMENT);
+	});
+}
+
+#[test]
+fn begin_transit_requires_fee_to_be_covered() {
+	new_test_ext().execute_with(|| {
+		register_destination();
+		assert_noop!(
+			LogisticsModule::begin_transit(
+				RuntimeOrigin::signed(SHIPPER),
+				0,
+				coords(),
+				None,
+				DESTINATION,
+				CONSIGNEE,
+				manifest(),
+				ENDOWMENT + 1,
+				None
+			),
+			pallet_balances::Error::<Test>::InsufficientBalance
+		);
+	});
+}
+
+#[test]
+fn shipment_without_fee_holds_no_escrow() {
+	new_test_ext().execute_with(|| {
+		register_destination();
+		assert_ok!(LogisticsModule::begin_transit(
+			RuntimeOrigin::signed(SHIPPER),
+			0,
+			coords(),
+			None,
+			DESTINATION,
+			CONSIGNEE,
+			manifest(),
+			0,
+			None
+		));
+		assert!(!Escrows::<Test>::contains_key(0));
+
+		hand_off(0, SHIPPER, CARRIER);
+		assert_ok!(LogisticsModule::shipment_delivered(
+			RuntimeOrigin::signed(CARRIER),
+			0,
+			coords(),
+			None,
+			Default::default()
+		));
+		assert_eq!(Balances::free_balance(SHIPPER), ENDOWMENT - deposit());
+		assert_eq!(Balances::free_balance(CARRIER), ENDOWMENT);
+	});
+}
+
+#[test]
+fn shipment_delivered_fails_for_invalid_requests() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			LogisticsModule::shipment_delivered(
+				RuntimeOrigin::signed(COURIER),
+				0,
+				coords(),
+				None,
+				Default::default()
+			),
+			Error::<Test>::ShipmentDoesNotExist
+		);
+
+		create(0);
+		// The shipper has to hand the shipment to a carrier first.
+		assert_noop!(
+			LogisticsModule::shipment_delivered(
+				RuntimeOrigin::signed(COURIER),
+				0,
+				coords(),
+				None,
+				Default::default()
+			),
+			Error::<Test>::InvalidStatusTransition
+		);
+
+		hand_off(0, SHIPPER, CARRIER);
+		assert_noop!(
+			LogisticsModule::shipment_delivered(
+				RuntimeOrigin::signed(COURIER),
+				0,
+				coords(),
+				None,
+				Default::default()
+			),
+			Error::<Test>::NotCurrentHolder
+		);
+	});
+}
+
+fn consignee() -> sr25519::Pair {
+	sr25519::Pair::from_seed(&[9; 32])
+}
+
+/// Have `CONSIGNEE` sign for deliveries with the [`consignee`] key.
+fn set_consignee_key() {
+	assert_ok!(LogisticsModule::set_consignee_key(
+		RuntimeOrigin::signed(CONSIGNEE),
+		Some(consignee().public())
+	));
+}
+
+/// Signature of [`consignee`] acknowledging they received `shipment_id` at `block`.
+fn signed_for(shipment_id: u64, block: u64) -> ConsigneeSignature<u64> {
+	let message = ConsigneeSignature::message(shipment_id, &block);
+	ConsigneeSignature {
+		signer: consignee().public(),
+		block,
+		signature: consignee().sign(&message),
+	}
+}
+
+#[test]
+fn delivery_signed_by_consignee_is_confirmed() {
+	new_test_ext().execute_with(|| {
+		set_consignee_key();
+		System::assert_last_event(
+			Event::ConsigneeKeySet { who: CONSIGNEE, key: Some(consignee().public()) }.into(),
+		);
+		create(0);
+		hand_off(0, SHIPPER, CARRIER);
+		System::set_block_number(3);
+
+		let evidence = vec![[7; 32], [8; 32]].try_into().unwrap();
+		assert_ok!(LogisticsModule::shipment_delivered(
+			RuntimeOrigin::signed(CARRIER),
+			0,
+			coords(),
+			Some(signed_for(0, 2)),
+			evidence
+		));
+		System::assert_has_event(
+			Event::ShipmentDelivered { shipment_id: 0, class: DeliveryClass::ConfirmedByConsignee }
+				.into(),
+		);
+		assert_eq!(
+			Deliveries::<Test>::get(0),
+			Some(ProofOfDelivery {
+				class: DeliveryClass::ConfirmedByConsignee,
+				consignee: Some(consignee().public()),
+				evidence: vec![[7; 32], [8; 32]].try_into().unwrap(),
+			})
+		);
+
+		idle(8, Weight::MAX);
+		assert!(!Deliveries::<Test>::contains_key(0));
+	});
+}
+
+#[test]
+fn consignee_signature_has_to_cover_the_delivery() {
+	new_test_ext().execute_with(|| {
+		let deliver_signed = |signature| {
+			LogisticsModule::shipment_delivered(
+				RuntimeOrigin::signed(CARRIER),
+				0,
+				coords(),
+				Some(signature),
+				Default::default(),
+			)
+		};
+		set_consignee_key();
+		create(0);
+		System::set_block_number(3);
+		hand_off(0, SHIPPER, CARRIER);
+		System::set_block_number(5);
+
+		// Another shipment, before the carrier took custody, and not yet signed.
+		for signature in [signed_for(1, 4), signed_for(0, 2), signed_for(0, 6)] {
+			assert_noop!(deliver_signed(signature), Error::<Test>::InvalidConsigneeSignature);
+		}
+		let forged = ConsigneeSignature {
+			signature: sr25519::Pair::from_seed(&[10; 32])
+				.sign(&ConsigneeSignature::message(0, &4)),
+			..signed_for(0, 4)
+		};
+		assert_noop!(deliver_signed(forged), Error::<Test>::InvalidConsigneeSignature);
+
+		assert_ok!(deliver_signed(signed_for(0, 3)));
+	});
+}
+
+#[test]
+fn courier_cannot_sign_for_consignee() {
+	new_test_ext().execute_with(|| {
+		create(0);
+		hand_off(0, SHIPPER, CARRIER);
+
+		// The courier signs with a key of their own, valid as a signature but not the
+		// consignee's.
+		let courier = sr25519::Pair::from_seed(&[10; 32]);
+		let message = ConsigneeSignature::message(0, &1);
+		let self_signed = ConsigneeSignature {
+			signer: courier.public(),
+			block: 1,
+			signature: courier.sign(&message),
+		};
+		let deliver_signed = |signature| {
+			LogisticsModule::shipment_delivered(
+				RuntimeOrigin::signed(CARRIER),
+				0,
+				coords(),
+				Some(signature),
+				Default::default(),
+			)
+		};
+		assert_noop!(deliver_signed(self_signed.clone()), Error::<Test>::NotConsigneeKey);
+		// Couriers cannot register a consignee key, and one stored for them would not count.
+		assert_noop!(
+			LogisticsModule::set_consignee_key(
+				RuntimeOrigin::signed(CARRIER),
+				Some(courier.public())
+			),
+			Error::<Test>::MissingRole
+		);
+		ConsigneeKeys::<Test>::insert(CARRIER, courier.public());
+		assert_noop!(deliver_signed(self_signed), Error::<Test>::NotConsigneeKey);
+		// No signature counts while the consignee has no key set.
+		assert_noop!(deliver_signed(signed_for(0, 1)), Error::<Test>::NotConsigneeKey);
+
+		set_consignee_key();
+		assert_ok!(deliver_signed(signed_for(0, 1)));
+	});
+}
+
+#[test]
+fn delivery_without_recorded_consignee_stays_unconfirmed() {
+	new_test_ext().execute_with(|| {
+		set_consignee_key();
+		create(0);
+		// As migrated from before consignees were recorded.
+		Shipments::<Test>::mutate(0, |shipment| shipment.as_mut().unwrap().consignee = None);
+		hand_off(0, SHIPPER, CARRIER);
+
+		assert_ok!(LogisticsModule::shipment_delivered(
+			RuntimeOrigin::signed(CARRIER),
+			0,
+			coords(),
+			Some(signed_for(0, 1)),
+			Default::default()
+		));
+
+		assert_eq!(
+			Deliveries::<Test>::get(0),
+			Some(ProofOfDelivery {
+				class: DeliveryClass::CourierAsserted,
+				consignee: None,
+				evidence: Default::default(),
+			})
+		);
+		System::assert_has_event(
+			Event::ShipmentDelivered { shipment_id: 0, class: DeliveryClass::CourierAsserted }
+				.into(),
+		);
+	});
+}
+
+#[test]
+fn consignee_confirms_receipt_of_delivery() {
+	new_test_ext().execute_with(|| {
+		deliver(0);
+		assert_eq!(shipment(0).consignee, Some(CONSIGNEE));
+
+		assert_noop!(
+			LogisticsModule::confirm_receipt(RuntimeOrigin::signed(SHIPPER), 0),
+			Error::<Test>::MissingRole
+		);
+		assert_ok!(LogisticsModule::add_member(RuntimeOrigin::root(), 3, STRANGER));
+		assert_noop!(
+			LogisticsModule::confirm_receipt(RuntimeOrigin::signed(STRANGER), 0),
+			Error::<Test>::NotConsignee
+		);
+		assert_ok!(LogisticsModule::confirm_receipt(RuntimeOrigin::signed(CONSIGNEE), 0));
+
+		assert_eq!(
+			Deliveries::<Test>::get(0).map(|d| d.class),
+			Some(DeliveryClass::ConfirmedByConsignee)
+		);
+		System::assert_last_event(
+			Event::ReceiptConfirmed { shipment_id: 0, consignee: CONSIGNEE }.into(),
+		);
+		assert_noop!(
+			LogisticsModule::confirm_receipt(RuntimeOrigin::signed(CONSIGNEE), 0),
+			Error::<Test>::AlreadyConfirmed
+		);
+	});
+}
+
+#[test]
+fn confirm_receipt_fails_for_undelivered_shipments() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			LogisticsModule::confirm_receipt(RuntimeOrigin::signed(CONSIGNEE), 0),
+			Error::<Test>::ShipmentDoesNotExist
+		);
+
+		create(0);
+		hand_off(0, SHIPPER, CARRIER);
+		assert_noop!(
+			LogisticsModule::confirm_receipt(RuntimeOrigin::signed(CONSIGNEE), 0),
+			Error::<Test>::NotDelivered
+		);
+
+		assert_ok!(LogisticsModule::update_status(
+			RuntimeOrigin::signed(CARRIER),
+			0,
+			ShipmentStatus::Lost
+		));
+		assert_noop!(
+			LogisticsModule::confirm_receipt(RuntimeOrigin::signed(CONSIGNEE), 0),
+			Error::<Test>::NotDelivered
+		);
+	});
+}
+
+#[test]
+fn delivery_must_be_within_default_geofence() {
+	new_test_ext().execute_with(|| {
+		create(0);
+		hand_off(0, SHIPPER, CARRIER);
+		// About 700 m from the destination.
+		let nearby = Coords::new(51_513_600, -127_800).unwrap();
+		let far = Coords::new(51_530_000, -127_800).unwrap();
+
+		assert_noop!(
+			LogisticsModule::set_default_geofence(RuntimeOrigin::signed(OPERATOR), Some(1_000)),
+			DispatchError::BadOrigin
+		);
+		assert_ok!(LogisticsModule::set_default_geofence(RuntimeOrigin::root(), Some(1_000)));
+		System::assert_last_event(Event::DefaultGeofenceSet { radius: Some(1_000) }.into());
+
+		assert_noop!(
+			LogisticsModule::shipment_delivered(
+				RuntimeOrigin::signed(CARRIER),
+				0,
+				far,
+				None,
+				Default::default()
+			),
+			Error::<Test>::OutsideGeofence
+		);
+		assert_ok!(LogisticsModule::shipment_delivered(
+			RuntimeOrigin::signed(CARRIER),
+			0,
+			nearby,
+			None,
+			Default::default()
+		));
+	});
+}
+
+#[test]
+fn facility_geofence_overrides_default() {
+	new_test_ext().execute_with(|| {
+		create(0);
+		hand_off(0, SHIPPER, CARRIER);
+		let nearby = Coords::new(51_513_600, -127_800).unwrap();
+		assert_ok!(LogisticsModule::set_default_geofence(RuntimeOrigin::root(), Some(1_000)));
+
+		assert_noop!(
+			LogisticsModule::set_facility_geofence(
+				RuntimeOrigin::signed(CARRIER),
+				DESTINATION,
+				Some(100)
+			),
+			Error::<Test>::NotFacilityOwner
+		);
+		assert_ok!(LogisticsModule::set_facility_geofence(
+			RuntimeOrigin::signed(OPERATOR),
+			DESTINATION,
+			Some(100)
+		));
+		System::assert_last_event(
+			Event::FacilityGeofenceSet { facility_id: DESTINATION, radius: Some(100) }.into(),
+		);
+		assert_eq!(LogisticsModule::geofence_radius(DESTINATION), Some(100));
+		assert_noop!(
+			LogisticsModule::shipment_delivered(
+				RuntimeOrigin::signed(CARRIER),
+				0,
+				nearby.clone(),
+				None,
+				Default::default()
+			),
+			Error::<Test>::OutsideGeofence
+		);
+
+		// Clearing the override falls back to the default.
+		assert_ok!(LogisticsModule::set_facility_geofence(
+			RuntimeOrigin::signed(OPERATOR),
+			DESTINATION,
+			None
+		));
+		assert_eq!(LogisticsModule::geofence_radius(DESTINATION), Some(1_000));
+		assert_ok!(LogisticsModule::shipment_delivered(
+			RuntimeOrigin::signed(CARRIER),
+			0,
+			nearby,
+			None,
+			Default::default()
+		));
+	});
+}
+
+#[test]
+fn deliveries_are_not_geofenced_without_radius() {
+	new_test_ext().execute_with(|| {
+		create(0);
+		hand_off(0, SHIPPER, CARRIER);
+
+		assert_eq!(LogisticsModule::geofence_radius(DESTINATION), None);
+		assert_ok!(LogisticsModule::shipment_delivered(
+			RuntimeOrigin::signed(CARRIER),
+			0,
+			Coords::new(48_856_600, 2_352_200).unwrap(),
+			None,
+			Default::default()
+		));
+	});
+}
+
+#[test]
+fn custody_history_records_every_hop() {
+	new_test_ext().execute_with(|| {
+		Timestamp::set_timestamp(1_000);
+		create(0);
+		System::set_block_number(2);
+		Timestamp::set_timestamp(7_000);
+		hand_off(0, SHIPPER, CARRIER);
+
+		let history = LogisticsModule::custody_history(0);
+		assert_eq!(
+			history
+				.iter()
+				.map(|hop| (hop.holder, hop.block, hop.timestamp))
+				.collect::<Vec<_>>(),
+			vec![(SHIPPER, 1, 1_000), (CARRIER, 2, 7_000)]
+		);
+		assert_eq!(history[1].coords, coords());
+	});
+}
+
+#[test]
+fn custody_history_keeps_latest_hops() {
+	new_test_ext().execute_with(|| {
+		create(0);
+		hand_off(0, SHIPPER, CARRIER);
+		// The mock allows eight hops, two of which are taken by now.
+		for hop in 0..6 {
+			let (from, to) = if hop % 2 == 0 { (CARRIER, COURIER) } else { (COURIER, CARRIER) };
+			System::set_block_number(hop + 2);
+			hand_off(0, from, to);
+		}
+		assert_eq!(CustodyHistory::<Test>::get(0).len(), 8);
+
+		System::set_block_number(10);
+		hand_off(0, CARRIER, COURIER);
+		assert_ok!(LogisticsModule::shipment_delivered(
+			RuntimeOrigin::signed(COURIER),
+			0,
+			coords(),
+			None,
+			Default::default()
+		));
+
+		// The shipper's hop is kept, the two oldest after it make way.
+		let history = LogisticsModule::custody_history(0);
+		assert_eq!(
+			history.iter().map(|hop| (hop.holder, hop.block)).collect::<Vec<_>>(),
+			vec![
+				(SHIPPER, 1),
+				(CARRIER, 3),
+				(COURIER, 4),
+				(CARRIER, 5),
+				(COURIER, 6),
+				(CARRIER, 7),
+				(COURIER, 10),
+				(COURIER, 10)
+			]
+		);
+		assert_eq!(shipment(0).status, ShipmentStatus::Delivered);
+		assert!(FormerCustodians::<Test>::get(0).is_empty());
+	});
+}
+
+#[test]
+fn custodians_pushed_out_of_history_can_still_be_claimed_against() {
+	new_test_ext().execute_with(|| {
+		create(0);
+		hand_off(0, SHIPPER, OPERATOR);
+		hand_off(0, OPERATOR, CARRIER);
+		// Eight hops fit, so the sixth handoff back and forth pushes the operator's hop out.
+		for hop in 0..6 {
+			let (from, to) = if hop % 2 == 0 { (CARRIER, COURIER) } else { (COURIER, CARRIER) };
+			hand_off(0, from, to);
+		}
+
+		let history = LogisticsModule::custody_history(0);
+		assert_eq!(history.len(), 8);
+		assert_eq!(history[0].holder, SHIPPER);
+		assert!(history.iter().all(|hop| hop.holder != OPERATOR));
+		assert_eq!(FormerCustodians::<Test>::get(0).into_inner(), vec![OPERATOR]);
+
+		assert_ok!(LogisticsModule::file_claim(
+			RuntimeOrigin::signed(SHIPPER),
+			0,
+			OPERATOR,
+			ClaimKind::Damaged,
+			[7; 32]
+		));
+	});
+}
+
+#[test]
+fn delivered_shipments_are_pruned_after_retention() {
+	new_test_ext().execute_with(|| {
+		deliver(0);
+		create(1);
+		assert_eq!(Shipments::<Test>::count(), 2);
+
+		// Delivered in block 1 with a retention of five blocks.
+		idle(5, Weight::MAX);
+		assert!(Shipments::<Test>::contains_key(0));
+
+		idle(6, Weight::MAX);
+		assert!(!Shipments::<Test>::contains_key(0));
+		assert!(CustodyHistory::<Test>::get(0).is_empty());
+		assert!(!Manifests::<Test>::contains_key(0));
+		assert_eq!(Shipments::<Test>::count(), 1);
+		assert_eq!(PruneCursor::<Test>::get().head, 1);
+	});
+}
+
+#[test]
+fn pruning_stays_within_weight_limit() {
+	new_test_ext().execute_with(|| {
+		for shipment_id in 0..3 {
+			deliver(shipment_id);
+		}
+
+		assert_eq!(idle(6, Weight::zero()), Weight::zero());
+		let limit = <() as WeightInfo>::prune_delivered(0);
+		assert_eq!(idle(6, limit), limit);
+		assert_eq!(Shipments::<Test>::count(), 3);
+
+		let limit = <() as WeightInfo>::prune_delivered(2);
+		assert_eq!(idle(7, limit), limit);
+		assert_eq!(Shipments::<Test>::count(), 1);
+
+		idle(8, Weight::MAX);
+		assert_eq!(Shipments::<Test>::count(), 0);
+	});
+}
+
+#[test]
+fn deliveries_per_block_are_not_capped() {
+	new_test_ext().execute_with(|| {
+		for shipment_id in 0..150 {
+			deliver(shipment_id);
+		}
+
+		assert_eq!(PruneCursor::<Test>::get().tail, 150);
+		idle(6, Weight::MAX);
+		assert_eq!(Shipments::<Test>::count(), 0);
+	});
+}
+
+#[test]
+fn pruning_releases_deposit() {
+	new_test_ext().execute_with(|| {
+		deliver(0);
+		assert_eq!(Balances::reserved_balance(SHIPPER), deposit());
+
+		idle(6, Weight::MAX);
+		assert!(!Deposits::<Test>::contains_key(0));
+		assert_eq!(Balances::reserved_balance(SHIPPER), 0);
+		assert_eq!(Balances::free_balance(SHIPPER), ENDOWMENT - FEE);
+		System::assert_has_event(
+			Event::DepositReleased { shipment_id: 0, to: SHIPPER, amount: deposit() }.into(),
+		);
+	});
+}
+
+#[test]
+fn abandoned_shipment_is_reaped() {
+	new_test_ext().execute_with(|| {
+		create(0);
+		hand_off(0, SHIPPER, CARRIER);
+		assert_ok!(LogisticsModule::offer_handoff(RuntimeOrigin::signed(CARRIER), 0, COURIER));
+
+		// Last changed hands in block 1, with a timeout of twenty blocks.
+		System::set_block_number(20);
+		assert_noop!(
+			LogisticsModule::reap_abandoned(RuntimeOrigin::signed(STRANGER), 0),
+			Error::<Test>::NotAbandoned
+		);
+
+		System::set_block_number(21);
+		assert_ok!(LogisticsModule::reap_abandoned(RuntimeOrigin::signed(STRANGER), 0));
+
+		assert!(!Shipments::<Test>::contains_key(0));
+		assert!(CustodyHistory::<Test>::get(0).is_empty());
+		assert!(!Manifests::<Test>::contains_key(0));
+		assert!(!PendingHandoffs::<Test>::contains_key(0));
+		assert!(!Deposits::<Test>::contains_key(0));
+		assert!(!Escrows::<Test>::contains_key(0));
+		// The fee is refunded, the deposit is not.
+		assert_eq!(Balances::reserved_balance(SHIPPER), 0);
+		assert_eq!(Balances::free_balance(SHIPPER), ENDOWMENT - deposit());
+		System::assert_last_event(
+			Event::ShipmentAbandoned { shipment_id: 0, slashed: deposit() }.into(),
+		);
+	});
+}
+
+#[test]
+fn reap_abandoned_fails_for_active_shipments() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			LogisticsModule::reap_abandoned(RuntimeOrigin::signed(STRANGER), 0),
+			Error::<Test>::ShipmentDoesNotExist
+		);
+
+		create(0);
+		System::set_block_number(15);
+		hand_off(0, SHIPPER, CARRIER);
+		// Changing hands restarts the timeout.
+		System::set_block_number(21);
+		assert_noop!(
+			LogisticsModule::reap_abandoned(RuntimeOrigin::signed(STRANGER), 0),
+			Error::<Test>::NotAbandoned
+		);
+		// So does scanning it, without it changing hands.
+		System::set_block_number(30);
+		assert_ok!(LogisticsModule::update_status(
+			RuntimeOrigin::signed(CARRIER),
+			0,
+			ShipmentStatus::AtFacility
+		));
+		System::set_block_number(49);
+		assert_noop!(
+			LogisticsModule::reap_abandoned(RuntimeOrigin::signed(STRANGER), 0),
+			Error::<Test>::NotAbandoned
+		);
+		System::set_block_number(50);
+		assert_ok!(LogisticsModule::reap_abandoned(RuntimeOrigin::signed(STRANGER), 0));
+		assert!(!LastScanned::<Test>::contains_key(0));
+
+		// Delivered shipments wait for pruning.
+		deliver(1);
+		System::set_block_number(100);
+		assert_noop!(
+			LogisticsModule::reap_abandoned(RuntimeOrigin::signed(STRANGER), 1),
+			Error::<Test>::NotAbandoned
+		);
+
+		create(2);
+		hand_off(2, SHIPPER, CARRIER);
+		assert_ok!(LogisticsModule::file_claim(
+			RuntimeOrigin::signed(SHIPPER),
+			2,
+			CARRIER,
+			ClaimKind::Lost,
+			[7; 32]
+		));
+		System::set_block_number(200);
+		assert_noop!(
+			LogisticsModule::reap_abandoned(RuntimeOrigin::signed(STRANGER), 2),
+			Error::<Test>::ClaimExists
+		);
+	});
+}
+
+#[test]
+fn shipper_cancels_shipment_before_handoff() {
+	new_test_ext().execute_with(|| {
+		create(0);
+		assert_ok!(LogisticsModule::offer_handoff(RuntimeOrigin::signed(SHIPPER), 0, CARRIER));
+
+		assert_ok!(LogisticsModule::cancel_shipment(RuntimeOrigin::signed(SHIPPER), 0));
+
+		assert_eq!(shipment(0).status, ShipmentStatus::Cancelled);
+		assert!(!PendingHandoffs::<Test>::contains_key(0));
+		assert!(!Escrows::<Test>::contains_key(0));
+		// The fee is refunded straight away, the deposit once the shipment is pruned.
+		assert_eq!(Balances::reserved_balance(SHIPPER), deposit());
+		System::assert_has_event(Event::ShipmentCancelled { shipment_id: 0 }.into());
+		System::assert_has_event(
+			Event::FeeRefunded { shipment_id: 0, to: SHIPPER, amount: FEE }.into(),
+		);
+		System::assert_last_event(
+			Event::ShipmentStatusChanged {
+				shipment_id: 0,
+				from: ShipmentStatus::Created,
+				to: ShipmentStatus::Cancelled,
+			}
+			.into(),
+		);
+		assert_noop!(
+			LogisticsModule::reap_abandoned(RuntimeOrigin::signed(STRANGER), 0),
+			Error::<Test>::NotAbandoned
+		);
+
+		idle(6, Weight::MAX);
+		assert!(!Shipments::<Test>::contains_key(0));
+		assert_eq!(Balances::free_balance(SHIPPER), ENDOWMENT);
+	});
+}
+
+#[test]
+fn cancel_shipment_fails_for_invalid_requests() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			LogisticsModule::cancel_shipment(RuntimeOrigin::signed(SHIPPER), 0),
+			Error::<Test>::ShipmentDoesNotExist
+		);
+
+		create(0);
+		assert_noop!(
+			LogisticsModule::cancel_shipment(RuntimeOrigin::signed(CARRIER), 0),
+			Error::<Test>::MissingRole
+		);
+
+		hand_off(0, SHIPPER, CARRIER);
+		assert_noop!(
+			LogisticsModule::cancel_shipment(RuntimeOrigin::signed(SHIPPER), 0),
+			Error::<Test>::InvalidStatusTransition
+		);
+	});
+}
+
+#[test]
+fn returned_shipment_goes_back_to_origin() {
+	new_test_ext().execute_with(|| {
+		create_returnable(0);
+		hand_off(0, SHIPPER, CARRIER);
+
+		assert_ok!(LogisticsModule::return_to_sender(RuntimeOrigin::signed(SHIPPER), 0));
+
+		let package = shipment(0);
+		assert_eq!(package.destination, ORIGIN);
+		assert_eq!(package.origin, Some(DESTINATION));
+		assert!(package.returning);
+		assert_eq!(package.status, ShipmentStatus::InTransit);
+		System::assert_last_event(
+			Event::ShipmentReturning { shipment_id: 0, destination: ORIGIN }.into(),
+		);
+
+		hand_off(0, CARRIER, COURIER);
+		assert_ok!(LogisticsModule::shipment_delivered(
+			RuntimeOrigin::signed(COURIER),
+			0,
+			coords(),
+			None,
+			Default::default()
+		));
+
+		assert_eq!(shipment(0).status, ShipmentStatus::Returned);
+		assert_eq!(LogisticsModule::custody_history(0).len(), 4);
+		// Carriers are paid for bringing it back as for delivering it.
+		assert_eq!(Balances::free_balance(CARRIER), ENDOWMENT + FEE / 2);
+		assert_eq!(Balances::free_balance(COURIER), ENDOWMENT + FEE / 2);
+		System::assert_has_event(Event::ShipmentReturned { shipment_id: 0 }.into());
+		System::assert_last_event(
+			Event::ShipmentStatusChanged {
+				shipment_id: 0,
+				from: ShipmentStatus::InTransit,
+				to: ShipmentStatus::Returned,
+			}
+			.into(),
+		);
+		assert!(!System::events().into_iter().any(|record| matches!(
+			record.event,
+			RuntimeEvent::LogisticsModule(Event::ShipmentDelivered { .. })
+		)));
+	});
+}
+
+#[test]
+fn return_to_sender_fails_for_invalid_requests() {
+	new_test_ext().execute_with(|| {
+		create(0);
+		// A shipment that has not left can be cancelled instead.
+		assert_noop!(
+			LogisticsModule::return_to_sender(RuntimeOrigin::signed(SHIPPER), 0),
+			Error::<Test>::InvalidStatusTransition
+		);
+		hand_off(0, SHIPPER, CARRIER);
+		assert_noop!(
+			LogisticsModule::return_to_sender(RuntimeOrigin::signed(SHIPPER), 0),
+			Error::<Test>::NoOriginFacility
+		);
+
+		create_returnable(1);
+		hand_off(1, SHIPPER, CARRIER);
+		assert_noop!(
+			LogisticsModule::return_to_sender(RuntimeOrigin::signed(CARRIER), 1),
+			Error::<Test>::MissingRole
+		);
+		assert_ok!(LogisticsModule::return_to_sender(RuntimeOrigin::signed(SHIPPER), 1));
+		assert_noop!(
+			LogisticsModule::return_to_sender(RuntimeOrigin::signed(SHIPPER), 1),
+			Error::<Test>::AlreadyReturning
+		);
+
+		deliver(2);
+		assert_noop!(
+			LogisticsModule::return_to_sender(RuntimeOrigin::signed(SHIPPER), 2),
+			Error::<Test>::ShipmentNotInTransit
+		);
+	});
+}
+
+#[test]
+fn consignee_can_reject_shipment() {
+	new_test_ext().execute_with(|| {
+		create_returnable(1);
+		assert_noop!(
+			LogisticsModule::reject_shipment(RuntimeOrigin::signed(CONSIGNEE), 1),
+			Error::<Test>::InvalidStatusTransition
+		);
+		hand_off(1, SHIPPER, CARRIER);
+		for who in [SHIPPER, CARRIER, STRANGER] {
+			assert_noop!(
+				LogisticsModule::reject_shipment(RuntimeOrigin::signed(who), 1),
+				Error::<Test>::MissingRole
+			);
+		}
+		assert_ok!(LogisticsModule::add_member(RuntimeOrigin::root(), 3, STRANGER));
+		assert_noop!(
+			LogisticsModule::reject_shipment(RuntimeOrigin::signed(STRANGER), 1),
+			Error::<Test>::NotConsignee
+		);
+
+		Deadlines::<Test>::insert(1, Deadline::Block(10));
+		assert_ok!(LogisticsModule::reject_shipment(RuntimeOrigin::signed(CONSIGNEE), 1));
+
+		let package = shipment(1);
+		assert_eq!(package.destination, ORIGIN);
+		assert!(package.returning);
+		// The shipper's promise was to the consignee.
+		assert!(!Deadlines::<Test>::contains_key(1));
+		System::assert_last_event(
+			Event::ShipmentRejected { shipment_id: 1, destination: Some(ORIGIN) }.into(),
+		);
+		assert_noop!(
+			LogisticsModule::reject_shipment(RuntimeOrigin::signed(CONSIGNEE), 1),
+			Error::<Test>::AlreadyReturning
+		);
+	});
+}
+
+#[test]
+fn rejected_shipment_without_origin_is_closed() {
+	new_test_ext().execute_with(|| {
+		create(0);
+		hand_off(0, SHIPPER, CARRIER);
+		Deadlines::<Test>::insert(0, Deadline::Block(10));
+
+		assert_ok!(LogisticsModule::reject_shipment(RuntimeOrigin::signed(CONSIGNEE), 0));
+
+		assert_eq!(shipment(0).status, ShipmentStatus::Rejected);
+		assert!(!Deadlines::<Test>::contains_key(0));
+		assert!(!Escrows::<Test>::contains_key(0));
+		assert_eq!(Balances::free_balance(SHIPPER), ENDOWMENT - deposit());
+		assert_eq!(Balances::free_balance(CARRIER), ENDOWMENT);
+		System::assert_has_event(
+			Event::ShipmentStatusChanged {
+				shipment_id: 0,
+				from: ShipmentStatus::InTransit,
+				to: ShipmentStatus::Rejected,
+			}
+			.into(),
+		);
+		System::assert_last_event(
+			Event::ShipmentRejected { shipment_id: 0, destination: None }.into(),
+		);
+		assert_noop!(
+			LogisticsModule::reject_shipment(RuntimeOrigin::signed(CONSIGNEE), 0),
+			Error::<Test>::ShipmentNotInTransit
+		);
+
+		// Closed shipments are pruned as delivered ones are.
+		idle(6, Weight::MAX);
+		assert!(!Shipments::<Test>::contains_key(0));
+	});
+}
+
+#[test]
+fn courier_asserted_delivery_can_be_rejected_until_pruned() {
+	new_test_ext().execute_with(|| {
+		deliver(0);
+		deliver(1);
+		assert_ok!(LogisticsModule::confirm_receipt(RuntimeOrigin::signed(CONSIGNEE), 1));
+		assert_noop!(
+			LogisticsModule::reject_shipment(RuntimeOrigin::signed(CONSIGNEE), 1),
+			Error::<Test>::AlreadyConfirmed
+		);
+
+		assert_ok!(LogisticsModule::reject_shipment(RuntimeOrigin::signed(CONSIGNEE), 0));
+
+		assert_eq!(shipment(0).status, ShipmentStatus::Rejected);
+		// The fee was paid out on delivery, which only a claim can undo.
+		assert_eq!(Balances::free_balance(SHIPPER), ENDOWMENT - 2 * FEE - 2 * deposit());
+		System::assert_has_event(
+			Event::ShipmentStatusChanged {
+				shipment_id: 0,
+				from: ShipmentStatus::Delivered,
+				to: ShipmentStatus::Rejected,
+			}
+			.into(),
+		);
+		System::assert_last_event(
+			Event::ShipmentRejected { shipment_id: 0, destination: None }.into(),
+		);
+		assert_noop!(
+			LogisticsModule::reject_shipment(RuntimeOrigin::signed(CONSIGNEE), 0),
+			Error::<Test>::ShipmentNotInTransit
+		);
+
+		// Pruned when its delivery would have been.
+		idle(6, Weight::MAX);
+		assert!(!Shipments::<Test>::contains_key(0));
+		assert_noop!(
+			LogisticsModule::reject_shipment(RuntimeOrigin::signed(CONSIGNEE), 0),
+			Error::<Test>::ShipmentDoesNotExist
+		);
+	});
+}
+
+fn part(shipment_id: u64, destination: u64) -> SplitPart<Test> {
+	SplitPart { shipment_id, destination, manifest: manifest() }
+}
+
+#[test]
+fn split_shipment_carries_on_as_its_parts() {
+	new_test_ext().execute_with(|| {
+		create_returnable(0);
+		hand_off(0, SHIPPER, CARRIER);
+		let history = LogisticsModule::custody_history(0);
+
+		let parts =
+			vec![part(10, DESTINATION), part(11, ORIGIN), part(12, DESTINATION), part(13, ORIGIN)];
+		assert_ok!(LogisticsModule::split_shipment(
+			RuntimeOrigin::signed(CARRIER),
+			0,
+			parts.try_into().unwrap()
+		));
+
+		assert_eq!(shipment(0).status, ShipmentStatus::Split);
+		for (shipment_id, destination) in
+			[(10, DESTINATION), (11, ORIGIN), (12, DESTINATION), (13, ORIGIN)]
+		{
+			let package = shipment(shipment_id);
+			assert_eq!(package.shipped_by, SHIPPER);
+			assert_eq!(package.received_by, CARRIER);
+			assert_eq!(package.destination, destination);
+			assert_eq!(package.origin, Some(ORIGIN));
+			assert_eq!(package.status, ShipmentStatus::InTransit);
+			assert_eq!(LogisticsModule::custody_history(shipment_id), history);
+			assert_eq!(SplitFrom::<Test>::get(shipment_id), Some(0));
+			assert_eq!(Manifests::<Test>::get(shipment_id), Some(manifest()));
+		}
+		// The fee is shared out between the parts, the last one getting the remainder.
+		assert_eq!(Escrows::<Test>::get(0), None);
+		assert_eq!(Escrows::<Test>::get(10), Some(22));
+		assert_eq!(Escrows::<Test>::get(13), Some(24));
+		// As is the deposit, so splitting reserves nothing more.
+		assert_eq!(Deposits::<Test>::get(0), None);
+		assert_eq!(Deposits::<Test>::get(10), Some(deposit() / 4));
+		assert_eq!(Deposits::<Test>::get(13), Some(deposit() - 3 * (deposit() / 4)));
+		assert_eq!(Balances::reserved_balance(SHIPPER), FEE + deposit());
+		System::assert_has_event(
+			Event::ShipmentSplit {
+				shipment_id: 0,
+				children: vec![10, 11, 12, 13].try_into().unwrap(),
+			}
+			.into(),
+		);
+		System::assert_last_event(
+			Event::ShipmentStatusChanged {
+				shipment_id: 0,
+				from: ShipmentStatus::InTransit,
+				to: ShipmentStatus::Split,
+			}
+			.into(),
+		);
+
+		assert_ok!(LogisticsModule::shipment_delivered(
+			RuntimeOrigin::signed(CARRIER),
+			10,
+			coords(),
+			None,
+			Default::default()
+		));
+		assert_eq!(Balances::free_balance(CARRIER), ENDOWMENT + 22);
+
+		// The split shipment is pruned like a delivered one.
+		idle(6, Weight::MAX);
+		assert!(!Shipments::<Test>::contains_key(0));
+		assert!(!Shipments::<Test>::contains_key(10));
+		assert!(!SplitFrom::<Test>::contains_key(10));
+		assert_eq!(Balances::reserved_balance(SHIPPER), FEE - 22 + deposit() - deposit() / 4);
+	});
+}
+
+#[test]
+fn parts_of_overdue_shipment_are_overdue() {
+	new_test_ext().execute_with(|| {
+		create_due(0, Deadline::Block(3));
+		hand_off(0, SHIPPER, CARRIER);
+		idle(4, Weight::MAX);
+		assert_eq!(overdue_events(), 1);
+
+		System::set_block_number(10);
+		let parts = vec![part(10, DESTINATION), part(11, DESTINATION)];
+		assert_ok!(LogisticsModule::split_shipment(
+			RuntimeOrigin::signed(CARRIER),
+			0,
+			parts.try_into().unwrap()
+		));
+
+		// Not on the agenda of block 4, which was already checked.
+		assert_eq!(DeadlineAgenda::<Test>::get(11).into_inner(), vec![10, 11]);
+		idle(11, Weight::MAX);
+		assert_eq!(overdue_events(), 3);
+		System::assert_has_event(
+			Event::ShipmentOverdue { shipment_id: 11, deadline: Deadline::Block(3) }.into(),
+		);
+		assert_eq!(DeadlineAgenda::<Test>::iter().count(), 0);
+	});
+}
+
+#[test]
+fn split_shipment_fails_for_invalid_requests() {
+	new_test_ext().execute_with(|| {
+		create(0);
+		let split = |who, parts: Vec<SplitPart<Test>>| {
+			LogisticsModule::split_shipment(
+				RuntimeOrigin::signed(who),
+				0,
+				parts.try_into().unwrap(),
+			)
+		};
+		let parts = vec![part(1, DESTINATION), part(2, DESTINATION)];
+
+		assert_noop!(split(SHIPPER, parts.clone()), Error::<Test>::MissingRole);
+		assert_noop!(split(CARRIER, parts.clone()), Error::<Test>::NotCurrentHolder);
+
+		hand_off(0, SHIPPER, CARRIER);
+		assert_noop!(split(CARRIER, vec![part(1, DESTINATION)]), Error::<Test>::InvalidSplit);
+		assert_noop!(
+			split(CARRIER, vec![part(1, DESTINATION), part(0, DESTINATION)]),
+			Error::<Test>::DuplicateShipment
+		);
+		assert_noop!(
+			split(CARRIER, vec![part(1, DESTINATION), part(1, DESTINATION)]),
+			Error::<Test>::DuplicateShipment
+		);
+		assert_noop!(
+			split(CARRIER, vec![part(1, DESTINATION), part(2, 9)]),
+			Error::<Test>::FacilityDoesNotExist
+		);
+		let mut empty = part(2, DESTINATION);
+		empty.manifest.items = Default::default();
+		assert_noop!(
+			split(CARRIER, vec![part(1, DESTINATION), empty]),
+			Error::<Test>::InvalidManifest
+		);
+
+		assert_ok!(LogisticsModule::offer_handoff(RuntimeOrigin::signed(CARRIER), 0, COURIER));
+		assert_noop!(split(CARRIER, parts), Error::<Test>::HandoffPending);
+	});
+}
+
+/// Keep between 2 and 8 °C, no humidity range.
+fn cold_chain() -> ConditionRanges {
+	ConditionRanges { temperature: Some(Range { min: 200, max: 800 }), ..Default::default() }
+}
+
+#[test]
+fn readings_outside_acceptable_ranges_are_breaches() {
+	new_test_ext().execute_with(|| {
+		create(0);
+		assert_ok!(LogisticsModule::set_condition_ranges(
+			RuntimeOrigin::signed(SHIPPER),
+			0,
+			cold_chain()
+		));
+		System::assert_last_event(
+			Event::ConditionRangesSet { shipment_id: 0, ranges: cold_chain() }.into(),
+		);
+		hand_off(0, SHIPPER, CARRIER);
+
+		let reading = Reading { temperature: Some(500), humidity: Some(450), shock: None };
+		assert_ok!(LogisticsModule::record_reading(
+			RuntimeOrigin::signed(CARRIER),
+			0,
+			reading.clone()
+		));
+		System::assert_last_event(Event::ReadingRecorded { shipment_id: 0, reading }.into());
+
+		Timestamp::set_timestamp(60_000);
+		let reading = Reading { temperature: Some(900), humidity: Some(700), shock: Some(150) };
+		assert_ok!(LogisticsModule::record_reading(RuntimeOrigin::signed(CARRIER), 0, reading));
+		// Humidity has no range, so only the temperature is a breach.
+		System::assert_last_event(
+			Event::ConditionBreached {
+				shipment_id: 0,
+				condition: Condition::Temperature,
+				value: 900,
+			}
+			.into(),
+		);
+
+		let telemetry = Telemetry::<Test>::get(0);
+		assert_eq!(telemetry.readings, 2);
+		assert_eq!(telemetry.breaches, 1);
+		assert_eq!(telemetry.last_reported_at, 60_000);
+		assert_eq!((telemetry.temperature().min, telemetry.temperature().max), (500, 900));
+		assert_eq!(telemetry.temperature().mean(), Some(700));
+		assert_eq!(telemetry.humidity().mean(), Some(575));
+		assert_eq!(telemetry.shock().count, 1);
+
+		assert_ok!(LogisticsModule::shipment_delivered(
+			RuntimeOrigin::signed(CARRIER),
+			0,
+			coords(),
+			None,
+			Default::default()
+		));
+		idle(6, Weight::MAX);
+		assert!(!Telemetry::<Test>::contains_key(0));
+		assert!(!AcceptableRanges::<Test>::contains_key(0));
+	});
+}
+
+#[test]
+fn early_excursions_age_out_of_telemetry() {
+	new_test_ext().execute_with(|| {
+		create(0);
+		hand_off(0, SHIPPER, CARRIER);
+
+		let read = |temperature| {
+			assert_ok!(LogisticsModule::record_reading(
+				RuntimeOrigin::signed(CARRIER),
+				0,
+				Reading { temperature: Some(temperature), humidity: None, shock: None }
+			));
+		};
+
+		// Windows hold two readings in the mock.
+		read(2_000);
+		read(500);
+		read(600);
+		read(700);
+		let telemetry = Telemetry::<Test>::get(0);
+		assert_eq!((telemetry.temperature().min, telemetry.temperature().max), (500, 2_000));
+
+		read(800);
+		let telemetry = Telemetry::<Test>::get(0);
+		assert_eq!((telemetry.temperature().min, telemetry.temperature().max), (600, 800));
+		assert_eq!(telemetry.temperature().mean(), Some(700));
+		assert_eq!(telemetry.readings, 5);
+	});
+}
+
+#[test]
+fn telemetry_fails_for_invalid_requests() {
+	new_test_ext().execute_with(|| {
+		let reading = Reading { temperature: Some(500), ..Default::default() };
+		assert_noop!(
+			LogisticsModule::set_condition_ranges(RuntimeOrigin::signed(SHIPPER), 0, cold_chain()),
+			Error::<Test>::ShipmentDoesNotExist
+		);
+
+		create(0);
+		assert_noop!(
+			LogisticsModule::set_condition_ranges(RuntimeOrigin::signed(CARRIER), 0, cold_chain()),
+			Error::<Test>::MissingRole
+		);
+		let empty =
+			ConditionRanges { shock: Some(Range { min: 10, max: 5 }), ..Default::default() };
+		assert_noop!(
+			LogisticsModule::set_condition_ranges(RuntimeOrigin::signed(SHIPPER), 0, empty),
+			Error::<Test>::InvalidRanges
+		);
+
+		hand_off(0, SHIPPER, CARRIER);
+		assert_noop!(
+			LogisticsModule::record_reading(RuntimeOrigin::signed(SHIPPER), 0, reading.clone()),
+			Error::<Test>::NotCurrentHolder
+		);
+		assert_noop!(
+			LogisticsModule::record_reading(RuntimeOrigin::signed(CARRIER), 0, Reading::default()),
+			Error::<Test>::InvalidReading
+		);
+		let soaked = Reading { humidity: Some(1_001), ..Default::default() };
+		assert_noop!(
+			LogisticsModule::record_reading(RuntimeOrigin::signed(CARRIER), 0, soaked),
+			Error::<Test>::InvalidReading
+		);
+
+		assert_ok!(LogisticsModule::shipment_delivered(
+			RuntimeOrigin::signed(CARRIER),
+			0,
+			coords(),
+			None,
+			Default::default()
+		));
+		assert_noop!(
+			LogisticsModule::record_reading(RuntimeOrigin::signed(CARRIER), 0, reading),
+			Error::<Test>::ShipmentNotInTransit
+		);
+	});
+}
+
+#[test]
+fn overdue_shipment_is_reported() {
+	new_test_ext().execute_with(|| {
+		create_due(0, Deadline::Block(3));
+		hand_off(0, SHIPPER, CARRIER);
+		assert_eq!(DeadlineAgenda::<Test>::get(4).into_inner(), vec![0]);
+
+		idle(3, Weight::MAX);
+		assert_eq!(overdue_events(), 0);
+
+		idle(4, Weight::MAX);
+		System::assert_has_event(
+			Event::ShipmentOverdue { shipment_id: 0, deadline: Deadline::Block(3) }.into(),
+		);
+		assert!(DeadlineAgenda::<Test>::get(4).is_empty());
+
+		// Each deadline is only reported once.
+		idle(5, Weight::MAX);
+		assert_eq!(overdue_events(), 1);
+	});
+}
+
+#[test]
+fn shipment_delivered_in_time_is_not_overdue() {
+	new_test_ext().execute_with(|| {
+		create_due(0, Deadline::Block(3));
+		hand_off(0, SHIPPER, CARRIER);
+		System::set_block_number(3);
+		assert_ok!(LogisticsModule::shipment_delivered(
+			RuntimeOrigin::signed(CARRIER),
+			0,
+			coords(),
+			None,
+			Default::default()
+		));
+
+		idle(4, Weight::MAX);
+		assert_eq!(overdue_events(), 0);
+		assert!(!SlaBreaches::<Test>::contains_key(0));
+	});
+}
+
+#[test]
+fn late_delivery_is_recorded_as_sla_breach() {
+	new_test_ext().execute_with(|| {
+		create_due(0, Deadline::Block(3));
+		hand_off(0, SHIPPER, CARRIER);
+		System::set_block_number(5);
+		Timestamp::set_timestamp(30_000);
+		assert_ok!(LogisticsModule::shipment_delivered(
+			RuntimeOrigin::signed(CARRIER),
+			0,
+			coords(),
+			None,
+			Default::default()
+		));
+
+		assert_eq!(
+			SlaBreaches::<Test>::get(0),
+			Some(SlaBreach { deadline: Deadline::Block(3), delivered_on: 5, delivered_at: 30_000 })
+		);
+		System::assert_has_event(
+			Event::SlaBreached { shipment_id: 0, deadline: Deadline::Block(3) }.into(),
+		);
+
+		// Breaches are kept as long as the shipment.
+		idle(11, Weight::MAX);
+		assert!(!SlaBreaches::<Test>::contains_key(0));
+	});
+}
+
+#[test]
+fn timestamp_deadlines_wait_for_the_clock() {
+	new_test_ext().execute_with(|| {
+		Timestamp::set_timestamp(1_000);
+		// Two blocks' worth of time ahead, so checked from block 4.
+		let deadline = Deadline::Timestamp(13_000);
+		create_due(0, deadline);
+		assert_eq!(DeadlineAgenda::<Test>::get(4).into_inner(), vec![0]);
+
+		// Blocks came faster than expected.
+		Timestamp::set_timestamp(13_000);
+		idle(4, Weight::MAX);
+		assert_eq!(overdue_events(), 0);
+		assert_eq!(DeadlineAgenda::<Test>::get(5).into_inner(), vec![0]);
+
+		Timestamp::set_timestamp(13_001);
+		idle(5, Weight::MAX);
+		System::assert_has_event(Event::ShipmentOverdue { shipment_id: 0, deadline }.into());
+	});
+}
+
+#[test]
+fn deadlines_move_on_when_agenda_is_full() {
+	new_test_ext().execute_with(|| {
+		for shipment_id in 0..3 {
+			create_due(shipment_id, Deadline::Block(3));
+		}
+
+		assert_eq!(DeadlineAgenda::<Test>::get(4).into_inner(), vec![0, 1]);
+		assert_eq!(DeadlineAgenda::<Test>::get(5).into_inner(), vec![2]);
+
+		// Blocks missed by `on_idle` are caught up on.
+		idle(9, Weight::MAX);
+		assert_eq!(overdue_events(), 3);
+	});
+}
+
+#[test]
+fn begin_transit_rejects_passed_deadline() {
+	new_test_ext().execute_with(|| {
+		register_destination();
+		Timestamp::set_timestamp(1_000);
+		for deadline in [Deadline::Block(0), Deadline::Timestamp(999)] {
+			assert_noop!(
+				LogisticsModule::begin_transit(
+					RuntimeOrigin::signed(SHIPPER),
+					0,
+					coords(),
+					None,
+					DESTINATION,
+					CONSIGNEE,
+					manifest(),
+					FEE,
+					Some(deadline)
+				),
+				Error::<Test>::DeadlinePassed
+			);
+		}
+	});
+}
+
+#[test]
+fn shipper_files_claim_against_custodian() {
+	new_test_ext().execute_with(|| {
+		create(0);
+		hand_off(0, SHIPPER, CARRIER);
+
+		assert_ok!(LogisticsModule::file_claim(
+			RuntimeOrigin::signed(SHIPPER),
+			0,
+			CARRIER,
+			ClaimKind::Lost,
+			[7; 32]
+		));
+
+		assert_eq!(
+			Claims::<Test>::get(0),
+			Some(Claim {
+				claimant: SHIPPER,
+				accused: CARRIER,
+				kind: ClaimKind::Lost,
+				evidence: [7; 32],
+				response: None,
+				bond: 50,
+				filed_on: 1,
+			})
+		);
+		assert_eq!(Balances::reserved_balance(SHIPPER), FEE + deposit() + 50);
+		System::assert_last_event(
+			Event::ClaimFiled {
+				shipment_id: 0,
+				claimant: SHIPPER,
+				accused: CARRIER,
+				kind: ClaimKind::Lost,
+			}
+			.into(),
+		);
+
+		assert_ok!(LogisticsModule::respond_to_claim(RuntimeOrigin::signed(CARRIER), 0, [8; 32]));
+		assert_eq!(Claims::<Test>::get(0).unwrap().response, Some([8; 32]));
+		assert_eq!(Balances::reserved_balance(CARRIER), 50);
+		System::assert_last_event(Event::ClaimAnswered { shipment_id: 0 }.into());
+	});
+}
+
+#[test]
+fn consignee_files_claim_on_delivered_shipment() {
+	new_test_ext().execute_with(|| {
+		deliver(0);
+
+		assert_ok!(LogisticsModule::file_claim(
+			RuntimeOrigin::signed(CONSIGNEE),
+			0,
+			COURIER,
+			ClaimKind::Damaged,
+			[7; 32]
+		));
+
+		assert_eq!(Claims::<Test>::get(0).map(|claim| claim.claimant), Some(CONSIGNEE));
+		assert_eq!(Balances::reserved_balance(CONSIGNEE), 50);
+	});
+}
+
+#[test]
+fn file_claim_fails_for_invalid_requests() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			LogisticsModule::file_claim(
+				RuntimeOrigin::signed(SHIPPER),
+				0,
+				CARRIER,
+				ClaimKind::Lost,
+				[7; 32]
+			),
+			Error::<Test>::ShipmentDoesNotExist
+		);
+
+		create(0);
+		hand_off(0, SHIPPER, CARRIER);
+		// Nor can the owner of the destination facility.
+		for who in [STRANGER, OPERATOR] {
+			assert_noop!(
+				LogisticsModule::file_claim(
+					RuntimeOrigin::signed(who),
+					0,
+					CARRIER,
+					ClaimKind::Lost,
+					[7; 32]
+				),
+				Error::<Test>::MissingRole
+			);
+		}
+		// Nor the consignee of some other shipment.
+		assert_ok!(LogisticsModule::add_member(RuntimeOrigin::root(), 3, STRANGER));
+		assert_noop!(
+			LogisticsModule::file_claim(
+				RuntimeOrigin::signed(STRANGER),
+				0,
+				CARRIER,
+				ClaimKind::Lost,
+				[7; 32]
+			),
+			Error::<Test>::NotClaimant
+		);
+		for accused in [STRANGER, SHIPPER] {
+			assert_noop!(
+				LogisticsModule::file_claim(
+					RuntimeOrigin::signed(SHIPPER),
+					0,
+					accused,
+					ClaimKind::Lost,
+					[7; 32]
+				),
+				Error::<Test>::NotCustodian
+			);
+		}
+
+		// The consignee can claim as well, but only once at a time.
+		assert_ok!(LogisticsModule::file_claim(
+			RuntimeOrigin::signed(CONSIGNEE),
+			0,
+			CARRIER,
+			ClaimKind::Damaged,
+			[7; 32]
+		));
+		assert_noop!(
+			LogisticsModule::file_claim(
+				RuntimeOrigin::signed(SHIPPER),
+				0,
+				CARRIER,
+				ClaimKind::Lost,
+				[7; 32]
+			),
+			Error::<Test>::ClaimExists
+		);
+
+		// Once a claim was upheld, there is nothing left to claim.
+		assert_ok!(LogisticsModule::resolve_claim(RuntimeOrigin::root(), 0, true));
+		assert_eq!(UpheldClaims::<Test>::get(0), Some(ClaimKind::Damaged));
+		assert_noop!(
+			LogisticsModule::file_claim(
+				RuntimeOrigin::signed(SHIPPER),
+				0,
+				CARRIER,
+				ClaimKind::Lost,
+				[7; 32]
+			),
+			Error::<Test>::NothingToClaim
+		);
+	});
+}
+
+#[test]
+fn claim_can_be_filed_against_holder_who_scanned_shipment_lost() {
+	new_test_ext().execute_with(|| {
+		create(0);
+		hand_off(0, SHIPPER, CARRIER);
+		assert_ok!(LogisticsModule::update_status(
+			RuntimeOrigin::signed(CARRIER),
+			0,
+			ShipmentStatus::Lost
+		));
+
+		assert_ok!(LogisticsModule::file_claim(
+			RuntimeOrigin::signed(SHIPPER),
+			0,
+			CARRIER,
+			ClaimKind::Lost,
+			[7; 32]
+		));
+		assert_ok!(LogisticsModule::respond_to_claim(RuntimeOrigin::signed(CARRIER), 0, [8; 32]));
+		assert_ok!(LogisticsModule::resolve_claim(RuntimeOrigin::root(), 0, true));
+
+		assert_eq!(shipment(0).status, ShipmentStatus::Lost);
+		assert!(!Deposits::<Test>::contains_key(0));
+		assert_noop!(
+			LogisticsModule::file_claim(
+				RuntimeOrigin::signed(CONSIGNEE),
+				0,
+				CARRIER,
+				ClaimKind::Lost,
+				[7; 32]
+			),
+			Error::<Test>::NothingToClaim
+		);
+		// It was queued for pruning when it was scanned lost.
+		assert_eq!(PruneCursor::<Test>::get().tail, 1);
+		assert_eq!(Balances::free_balance(SHIPPER), ENDOWMENT + 50);
+		assert_eq!(Balances::free_balance(CARRIER), ENDOWMENT - 50);
+
+		idle(6, Weight::MAX);
+		assert!(!Shipments::<Test>::contains_key(0));
+		assert!(!UpheldClaims::<Test>::contains_key(0));
+	});
+}
+
+#[test]
+fn respond_to_claim_fails_for_invalid_requests() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			LogisticsModule::respond_to_claim(RuntimeOrigin::signed(CARRIER), 0, [8; 32]),
+			Error::<Test>::NoClaim
+		);
+
+		deliver(0);
+		assert_ok!(LogisticsModule::file_claim(
+			RuntimeOrigin::signed(SHIPPER),
+			0,
+			COURIER,
+			ClaimKind::Damaged,
+			[7; 32]
+		));
+		assert_noop!(
+			LogisticsModule::respond_to_claim(RuntimeOrigin::signed(CARRIER), 0, [8; 32]),
+			Error::<Test>::NotAccused
+		);
+		// The accused answers only while their organization still handles shipments.
+		assert_ok!(LogisticsModule::set_organization_roles(
+			RuntimeOrigin::root(),
+			1,
+			Roles::CONSIGNEE
+		));
+		assert_noop!(
+			LogisticsModule::respond_to_claim(RuntimeOrigin::signed(COURIER), 0, [8; 32]),
+			Error::<Test>::MissingRole
+		);
+		assert_ok!(LogisticsModule::set_organization_roles(
+			RuntimeOrigin::root(),
+			1,
+			Roles::CARRIER | Roles::WAREHOUSE_OPERATOR
+		));
+
+		assert_ok!(LogisticsModule::respond_to_claim(RuntimeOrigin::signed(COURIER), 0, [8; 32]));
+		assert_noop!(
+			LogisticsModule::respond_to_claim(RuntimeOrigin::signed(COURIER), 0, [9; 32]),
+			Error::<Test>::AlreadyAnswered
+		);
+	});
+}
+
+#[test]
+fn upheld_claim_marks_shipment_lost_and_refunds_shipper() {
+	new_test_ext().execute_with(|| {
+		create(0);
+		hand_off(0, SHIPPER, CARRIER);
+		assert_ok!(LogisticsModule::offer_handoff(RuntimeOrigin::signed(CARRIER), 0, COURIER));
+		assert_ok!(LogisticsModule::file_claim(
+			RuntimeOrigin::signed(SHIPPER),
+			0,
+			CARRIER,
+			ClaimKind::Lost,
+			[7; 32]
+		));
+		assert_ok!(LogisticsModule::respond_to_claim(RuntimeOrigin::signed(CARRIER), 0, [8; 32]));
+
+		assert_noop!(
+			LogisticsModule::resolve_claim(RuntimeOrigin::signed(SHIPPER), 0, true),
+			DispatchError::BadOrigin
+		);
+		assert_ok!(LogisticsModule::resolve_claim(RuntimeOrigin::root(), 0, true));
+
+		assert!(!Claims::<Test>::contains_key(0));
+		assert_eq!(shipment(0).status, ShipmentStatus::Lost);
+		assert!(!PendingHandoffs::<Test>::contains_key(0));
+		assert!(!Escrows::<Test>::contains_key(0));
+		assert!(!Deposits::<Test>::contains_key(0));
+		assert_eq!(PruneCursor::<Test>::get().tail, 1);
+		// Fee and deposit come back, along with the carrier's bond.
+		assert_eq!(Balances::reserved_balance(SHIPPER), 0);
+		assert_eq!(Balances::free_balance(SHIPPER), ENDOWMENT + 50);
+		assert_eq!(Balances::reserved_balance(CARRIER), 0);
+		assert_eq!(Balances::free_balance(CARRIER), ENDOWMENT - 50);
+		System::assert_has_event(
+			Event::ShipmentStatusChanged {
+				shipment_id: 0,
+				from: ShipmentStatus::InTransit,
+				to: ShipmentStatus::Lost,
+			}
+			.into(),
+		);
+		System::assert_last_event(Event::ClaimResolved { shipment_id: 0, upheld: true }.into());
+
+		assert_noop!(
+			LogisticsModule::resolve_claim(RuntimeOrigin::root(), 0, true),
+			Error::<Test>::NoClaim
+		);
+	});
+}
+
+#[test]
+fn upheld_claim_marks_delivered_shipment_damaged() {
+	new_test_ext().execute_with(|| {
+		deliver(0);
+		assert_ok!(LogisticsModule::file_claim(
+			RuntimeOrigin::signed(CONSIGNEE),
+			0,
+			COURIER,
+			ClaimKind::Damaged,
+			[7; 32]
+		));
+
+		assert_ok!(LogisticsModule::resolve_claim(RuntimeOrigin::root(), 0, true));
+
+		assert_eq!(shipment(0).status, ShipmentStatus::Damaged);
+		// Already queued when it was delivered.
+		assert_eq!(PruneCursor::<Test>::get().tail, 1);
+		// An unanswered claim costs the accused nothing but the fee already paid out stays paid.
+		assert_eq!(Balances::free_balance(CONSIGNEE), ENDOWMENT);
+		assert_eq!(Balances::free_balance(COURIER), ENDOWMENT + FEE / 2);
+		assert_eq!(Balances::free_balance(SHIPPER), ENDOWMENT - FEE);
+	});
+}
+
+#[test]
+fn rejected_claim_compensates_accused() {
+	new_test_ext().execute_with(|| {
+		deliver(0);
+		assert_ok!(LogisticsModule::file_claim(
+			RuntimeOrigin::signed(SHIPPER),
+			0,
+			COURIER,
+			ClaimKind::Damaged,
+			[7; 32]
+		));
+		assert_ok!(LogisticsModule::respond_to_claim(RuntimeOrigin::signed(COURIER), 0, [8; 32]));
+
+		assert_ok!(LogisticsModule::resolve_claim(RuntimeOrigin::root(), 0, false));
+
+		assert_eq!(shipment(0).status, ShipmentStatus::Delivered);
+		assert_eq!(Balances::reserved_balance(COURIER), 0);
+		assert_eq!(Balances::free_balance(COURIER), ENDOWMENT + FEE / 2 + 50);
+		assert_eq!(Balances::reserved_balance(SHIPPER), deposit());
+		assert_eq!(Balances::free_balance(SHIPPER), ENDOWMENT - FEE - deposit() - 50);
+		System::assert_last_event(Event::ClaimResolved { shipment_id: 0, upheld: false }.into());
+	});
+}
+
+/// Create shipments `1` and `2` and load them into shipment `0`.
+fn load_container() {
+	for shipment_id in 0..3 {
+		create(shipment_id);
+	}
+	assert_ok!(LogisticsModule::load_shipments(
+		RuntimeOrigin::signed(SHIPPER),
+		0,
+		vec![1, 2].try_into().unwrap()
+	));
+}
+
+#[test]
+fn shipments_move_with_their_container() {
+	new_test_ext().execute_with(|| {
+		load_container();
+		assert_eq!(Contents::<Test>::get(0).into_inner(), vec![1, 2]);
+		assert_eq!(ContainedIn::<Test>::get(1), Some(0));
+		System::assert_last_event(Event::ShipmentLoaded { shipment_id: 2, container_id: 0 }.into());
+
+		hand_off(0, SHIPPER, CARRIER);
+		for shipment_id in [1, 2] {
+			let package = shipment(shipment_id);
+			assert_eq!(package.received_by, CARRIER);
+			assert_eq!(package.status, ShipmentStatus::InTransit);
+			assert_eq!(LogisticsModule::custody_history(shipment_id).len(), 2);
+		}
+		System::assert_has_event(
+			Event::ShipmentReceived { shipment_id: 2, received_by: CARRIER, received_at: coords() }
+				.into(),
+		);
+
+		assert_ok!(LogisticsModule::update_status(
+			RuntimeOrigin::signed(CARRIER),
+			0,
+			ShipmentStatus::AtFacility
+		));
+		assert_eq!(shipment(1).status, ShipmentStatus::AtFacility);
+		assert_eq!(shipment(2).status, ShipmentStatus::AtFacility);
+
+		assert_ok!(LogisticsModule::unload_shipments(
+			RuntimeOrigin::signed(CARRIER),
+			0,
+			vec![1].try_into().unwrap()
+		));
+		assert_eq!(ContainedIn::<Test>::get(1), None);
+		assert_eq!(Contents::<Test>::get(0).into_inner(), vec![2]);
+		System::assert_last_event(
+			Event::ShipmentUnloaded { shipment_id: 1, container_id: 0 }.into(),
+		);
+		// Unloaded shipments move on their own again.
+		assert_ok!(LogisticsModule::offer_handoff(RuntimeOrigin::signed(CARRIER), 1, COURIER));
+
+		assert_ok!(LogisticsModule::unload_shipments(
+			RuntimeOrigin::signed(CARRIER),
+			0,
+			vec![2].try_into().unwrap()
+		));
+		assert!(!Contents::<Test>::contains_key(0));
+		assert_ok!(LogisticsModule::shipment_delivered(
+			RuntimeOrigin::signed(CARRIER),
+			0,
+			coords(),
+			None,
+			Default::default()
+		));
+	});
+}
+
+#[test]
+fn lost_container_is_lost_with_its_contents() {
+	new_test_ext().execute_with(|| {
+		load_container();
+		hand_off(0, SHIPPER, CARRIER);
+
+		assert_ok!(LogisticsModule::update_status(
+			RuntimeOrigin::signed(CARRIER),
+			0,
+			ShipmentStatus::Lost
+		));
+
+		assert!(!Contents::<Test>::contains_key(0));
+		for shipment_id in [1, 2] {
+			assert_eq!(shipment(shipment_id).status, ShipmentStatus::Lost);
+			assert_eq!(ContainedIn::<Test>::get(shipment_id), None);
+			assert!(!Escrows::<Test>::contains_key(shipment_id));
+		}
+		assert_eq!(Balances::reserved_balance(SHIPPER), 3 * deposit());
+	});
+}
+
+#[test]
+fn loaded_shipments_cannot_move_on_their_own() {
+	new_test_ext().execute_with(|| {
+		load_container();
+
+		assert_noop!(
+			LogisticsModule::offer_handoff(RuntimeOrigin::signed(SHIPPER), 1, CARRIER),
+			Error::<Test>::ShipmentInContainer
+		);
+		assert_noop!(
+			LogisticsModule::cancel_shipment(RuntimeOrigin::signed(SHIPPER), 1),
+			Error::<Test>::ShipmentInContainer
+		);
+		assert_noop!(
+			LogisticsModule::cancel_shipment(RuntimeOrigin::signed(SHIPPER), 0),
+			Error::<Test>::ContainerNotEmpty
+		);
+		assert_noop!(
+			LogisticsModule::file_claim(
+				RuntimeOrigin::signed(SHIPPER),
+				0,
+				CARRIER,
+				ClaimKind::Lost,
+				[1; 32]
+			),
+			Error::<Test>::ContainerNotEmpty
+		);
+
+		hand_off(0, SHIPPER, CARRIER);
+		assert_noop!(
+			LogisticsModule::update_status(
+				RuntimeOrigin::signed(CARRIER),
+				1,
+				ShipmentStatus::AtFacility
+			),
+			Error::<Test>::ShipmentInContainer
+		);
+		assert_noop!(
+			LogisticsModule::shipment_delivered(
+				RuntimeOrigin::signed(CARRIER),
+				1,
+				coords(),
+				None,
+				Default::default()
+			),
+			Error::<Test>::ShipmentInContainer
+		);
+		assert_noop!(
+			LogisticsModule::shipment_delivered(
+				RuntimeOrigin::signed(CARRIER),
+				0,
+				coords(),
+				None,
+				Default::default()
+			),
+			Error::<Test>::ContainerNotEmpty
+		);
+	});
+}
+
+#[test]
+fn load_shipments_fails_for_invalid_requests() {
+	new_test_ext().execute_with(|| {
+		load_container();
+		for shipment_id in 3..6 {
+			create(shipment_id);
+		}
+		let load = |who, container_id, shipment_ids: Vec<u64>| {
+			LogisticsModule::load_shipments(
+				RuntimeOrigin::signed(who),
+				container_id,
+				shipment_ids.try_into().unwrap(),
+			)
+		};
+
+		assert_noop!(load(SHIPPER, 6, vec![3]), Error::<Test>::ShipmentDoesNotExist);
+		assert_noop!(load(CARRIER, 3, vec![4]), Error::<Test>::NotCurrentHolder);
+		assert_noop!(load(SHIPPER, 3, vec![3]), Error::<Test>::NestedContainer);
+		assert_noop!(load(SHIPPER, 3, vec![0]), Error::<Test>::NestedContainer);
+		assert_noop!(load(SHIPPER, 1, vec![3]), Error::<Test>::NestedContainer);
+		assert_noop!(load(SHIPPER, 3, vec![1]), Error::<Test>::ShipmentInContainer);
+		assert_noop!(load(SHIPPER, 0, vec![3, 4]), Error::<Test>::ContainerFull);
+
+		assert_ok!(LogisticsModule::offer_handoff(RuntimeOrigin::signed(SHIPPER), 4, CARRIER));
+		assert_noop!(load(SHIPPER, 3, vec![5, 4]), Error::<Test>::HandoffPending);
+
+		assert_noop!(
+			LogisticsModule::unload_shipments(
+				RuntimeOrigin::signed(SHIPPER),
+				0,
+				vec![3].try_into().unwrap()
+			),
+			Error::<Test>::NotInContainer
+		);
+		assert_noop!(
+			LogisticsModule::unload_shipments(
+				RuntimeOrigin::signed(CARRIER),
+				0,
+				vec![1].try_into().unwrap()
+			),
+			Error::<Test>::NotCurrentHolder
+		);
+	});
+}
+
+#[test]
+fn genesis_registers_organizations() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(LogisticsModule::roles_of(&SHIPPER), Roles::SHIPPER);
+		assert_eq!(LogisticsModule::roles_of(&COURIER), Roles::CARRIER | Roles::WAREHOUSE_OPERATOR);
+		assert_eq!(LogisticsModule::roles_of(&STRANGER), Roles::empty());
+		assert_eq!(Members::<Test>::get(CARRIER), Members::<Test>::get(COURIER));
+	});
+}
+
+#[test]
+fn each_transition_needs_an_allowed_role() {
+	new_test_ext().execute_with(|| {
+		register_destination();
+		for who in [STRANGER, CARRIER] {
+			assert_noop!(
+				LogisticsModule::begin_transit(
+					RuntimeOrigin::signed(who),
+					0,
+					coords(),
+					None,
+					DESTINATION,
+					CONSIGNEE,
+					manifest(),
+					FEE,
+					None
+				),
+				Error::<Test>::MissingRole
+			);
+		}
+		assert_noop!(
+			LogisticsModule::register_facility(
+				RuntimeOrigin::signed(SHIPPER),
+				[0; 32],
+				coords(),
+				FacilityKind::Depot
+			),
+			Error::<Test>::MissingRole
+		);
+
+		create(0);
+		assert_ok!(LogisticsModule::offer_handoff(RuntimeOrigin::signed(SHIPPER), 0, STRANGER));
+		assert_noop!(
+			LogisticsModule::shipment_received(RuntimeOrigin::signed(STRANGER), 0, coords()),
+			Error::<Test>::MissingRole
+		);
+
+		hand_off(0, SHIPPER, CARRIER);
+		hand_off(0, CARRIER, OPERATOR);
+		// A warehouse operator can scan the shipment in, but not take it out for delivery.
+		assert_ok!(LogisticsModule::update_status(
+			RuntimeOrigin::signed(OPERATOR),
+			0,
+			ShipmentStatus::AtFacility
+		));
+		assert_noop!(
+			LogisticsModule::update_status(
+				RuntimeOrigin::signed(OPERATOR),
+				0,
+				ShipmentStatus::OutForDelivery
+			),
+			Error::<Test>::MissingRole
+		);
+		assert_noop!(
+			LogisticsModule::shipment_delivered(
+				RuntimeOrigin::signed(OPERATOR),
+				0,
+				coords(),
+				None,
+				Default::default()
+			),
+			Error::<Test>::MissingRole
+		);
+	});
+}
+
+#[test]
+fn admin_manages_organizations() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			LogisticsModule::register_organization(
+				RuntimeOrigin::signed(SHIPPER),
+				[4; 32],
+				Roles::CUSTOMS
+			),
+			DispatchError::BadOrigin
+		);
+		let unknown_role: Roles = Decode::decode(&mut &[1 << 7][..]).unwrap();
+		assert_noop!(
+			LogisticsModule::register_organization(RuntimeOrigin::root(), [5; 32], unknown_role),
+			Error::<Test>::InvalidRoles
+		);
+
+		assert_ok!(LogisticsModule::register_organization(
+			RuntimeOrigin::root(),
+			[5; 32],
+			Roles::CUSTOMS
+		));
+		System::assert_last_event(
+			Event::OrganizationRegistered { organization_id: 4, roles: Roles::CUSTOMS }.into(),
+		);
+
+		assert_noop!(
+			LogisticsModule::add_member(RuntimeOrigin::root(), 5, STRANGER),
+			Error::<Test>::OrganizationDoesNotExist
+		);
+		assert_noop!(
+			LogisticsModule::add_member(RuntimeOrigin::root(), 4, SHIPPER),
+			Error::<Test>::AlreadyMember
+		);
+		assert_ok!(LogisticsModule::add_member(RuntimeOrigin::root(), 4, STRANGER));
+		System::assert_last_event(Event::MemberAdded { organization_id: 4, who: STRANGER }.into());
+		assert_eq!(LogisticsModule::roles_of(&STRANGER), Roles::CUSTOMS);
+
+		assert_ok!(LogisticsModule::set_organization_roles(
+			RuntimeOrigin::root(),
+			4,
+			Roles::CUSTOMS | Roles::CONSIGNEE
+		));
+		assert!(LogisticsModule::roles_of(&STRANGER).contains(Roles::CONSIGNEE));
+
+		assert_ok!(LogisticsModule::remove_member(RuntimeOrigin::root(), STRANGER));
+		System::assert_last_event(
+			Event::MemberRemoved { organization_id: 4, who: STRANGER }.into(),
+		);
+		assert_eq!(LogisticsModule::roles_of(&STRANGER), Roles::empty());
+		assert_noop!(
+			LogisticsModule::remove_member(RuntimeOrigin::root(), STRANGER),
+			Error::<Test>::NotMember
+		);
+	});
+}
+
+#[test]
+fn organization_without_roles_is_suspended() {
+	new_test_ext().execute_with(|| {
+		create(0);
+		let shippers = Members::<Test>::get(SHIPPER).unwrap();
+
+		assert_ok!(LogisticsModule::set_organization_roles(
+			RuntimeOrigin::root(),
+			shippers,
+			Roles::empty()
+		));
+		System::assert_last_event(
+			Event::OrganizationRolesChanged { organization_id: shippers, roles: Roles::empty() }
+				.into(),
+		);
+
+		assert_noop!(
+			LogisticsModule::offer_handoff(RuntimeOrigin::signed(SHIPPER), 0, CARRIER),
+			Error::<Test>::MissingRole
+		);
+	});
+}
+
+/// Key of the scanner the carrier organization registers in device tests.
+const DEVICE: u64 = 7;
+
+fn device_call(signer: u64, nonce: u64, submission: Submission) -> crate::Call<Test> {
+	let payload = DevicePayload { device: UintAuthorityId(DEVICE), nonce, submission };
+	let signature: TestSignature =
+		UintAuthorityId(signer).sign(&payload.message(&System::block_hash(0))).unwrap();
+	crate::Call::submit_from_device { payload, signature }
+}
+
+/// Submit a payload signed by [`DEVICE`] as it would be once included.
+fn submit(nonce: u64, submission: Submission) -> DispatchResultWithPostInfo {
+	match device_call(DEVICE, nonce, submission) {
+		crate::Call::submit_from_device { payload, signature } =>
+			LogisticsModule::submit_from_device(RuntimeOrigin::none(), payload, signature),
+		_ => unreachable!(),
+	}
+}
+
+fn validate(call: &crate::Call<Test>) -> TransactionValidity {
+	<LogisticsModule as ValidateUnsigned>::validate_unsigned(TransactionSource::External, call)
+}
+
+fn scan(shipment_id: u64) -> Submission {
+	Submission::Scan { shipment_id, status: ShipmentStatus::AtFacility }
+}
+
+#[test]
+fn devices_report_for_their_organization() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(LogisticsModule::register_device(
+			RuntimeOrigin::signed(CARRIER),
+			UintAuthorityId(DEVICE)
+		));
+		System::assert_last_event(
+			Event::DeviceRegistered { device: UintAuthorityId(DEVICE), organization_id: 1 }.into(),
+		);
+		create(0);
+		hand_off(0, SHIPPER, CARRIER);
+
+		assert_ok!(submit(0, scan(0)));
+		assert_eq!(shipment(0).status, ShipmentStatus::AtFacility);
+
+		// Any member of the organization holding the shipment will do.
+		hand_off(0, CARRIER, COURIER);
+		let reading = Reading { temperature: Some(500), ..Default::default() };
+		assert_ok!(submit(1, Submission::Reading { shipment_id: 0, reading: reading.clone() }));
+		System::assert_last_event(Event::ReadingRecorded { shipment_id: 0, reading }.into());
+		assert_eq!(Telemetry::<Test>::get(0).readings, 1);
+		assert_eq!(DeviceUsages::<Test>::get(UintAuthorityId(DEVICE)).nonce, 2);
+	});
+}
+
+#[test]
+fn failed_device_submissions_use_up_their_nonce() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(LogisticsModule::register_device(
+			RuntimeOrigin::signed(CARRIER),
+			UintAuthorityId(DEVICE)
+		));
+		create(0);
+
+		// The shipper still holds the shipment.
+		assert_ok!(submit(0, scan(0)));
+		System::assert_last_event(
+			Event::DeviceSubmissionFailed {
+				device: UintAuthorityId(DEVICE),
+				nonce: 0,
+				error: Error::<Test>::NotCurrentHolder.into(),
+			}
+			.into(),
+		);
+		assert_eq!(shipment(0).status, ShipmentStatus::Created);
+
+		assert_noop!(submit(0, scan(0)), Error::<Test>::InvalidNonce);
+		hand_off(0, SHIPPER, CARRIER);
+		assert_ok!(submit(1, scan(0)));
+		assert_eq!(shipment(0).status, ShipmentStatus::AtFacility);
+	});
+}
+
+#[test]
+fn device_submissions_are_rate_limited() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(LogisticsModule::register_device(
+			RuntimeOrigin::signed(CARRIER),
+			UintAuthorityId(DEVICE)
+		));
+
+		// Failed submissions count towards the limit as well.
+		for nonce in 0..3 {
+			assert_ok!(submit(nonce, scan(0)));
+		}
+		assert_noop!(submit(3, scan(0)), Error::<Test>::DeviceRateLimited);
+		assert_eq!(
+			validate(&device_call(DEVICE, 3, scan(0))),
+			Err(InvalidTransaction::ExhaustsResources.into())
+		);
+
+		// The next window starts at block 10.
+		System::set_block_number(10);
+		assert_ok!(submit(3, scan(0)));
+	});
+}
+
+#[test]
+fn device_payloads_are_validated_before_inclusion() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(
+			validate(&device_call(DEVICE, 0, scan(0))),
+			Err(UnknownTransaction::CannotLookup.into())
+		);
+		assert_ok!(LogisticsModule::register_device(
+			RuntimeOrigin::signed(CARRIER),
+			UintAuthorityId(DEVICE)
+		));
+		assert_eq!(
+			validate(&device_call(DEVICE + 1, 0, scan(0))),
+			Err(InvalidTransaction::BadProof.into())
+		);
+
+		let valid = validate(&device_call(DEVICE, 0, scan(0))).unwrap();
+		assert_eq!(valid.priority, 1_000);
+		assert!(valid.requires.is_empty());
+		let reading = Submission::Reading { shipment_id: 0, reading: Reading::default() };
+		assert_eq!(validate(&device_call(DEVICE, 0, reading)).unwrap().priority, 500);
+
+		// A later payload waits for the one before it, and is only included after it.
+		let next = validate(&device_call(DEVICE, 1, scan(0))).unwrap();
+		assert_eq!(next.requires, valid.provides);
+		assert_eq!(
+			<LogisticsModule as ValidateUnsigned>::pre_dispatch(&device_call(DEVICE, 1, scan(0))),
+			Err(InvalidTransaction::Future.into())
+		);
+		// Counting the payloads queued before it, a fourth would exceed the limit of three.
+		assert_eq!(
+			validate(&device_call(DEVICE, 3, scan(0))),
+			Err(InvalidTransaction::ExhaustsResources.into())
+		);
+
+		assert_ok!(submit(0, scan(0)));
+		assert_eq!(
+			validate(&device_call(DEVICE, 0, scan(0))),
+			Err(InvalidTransaction::Stale.into())
+		);
+		assert_eq!(
+			validate(&device_call(DEVICE, 1, scan(0))).map(|valid| valid.requires),
+			Ok(Vec::new())
+		);
+	});
+}
+
+#[test]
+fn device_signatures_only_hold_on_this_chain() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(LogisticsModule::register_device(
+			RuntimeOrigin::signed(CARRIER),
+			UintAuthorityId(DEVICE)
+		));
+		let payload =
+			DevicePayload { device: UintAuthorityId(DEVICE), nonce: 0, submission: scan(0) };
+
+		let bare = UintAuthorityId(DEVICE).sign(&payload.encode()).unwrap();
+		let other_chain = UintAuthorityId(DEVICE).sign(&payload.message(&[1u8; 32])).unwrap();
+		for signature in [bare, other_chain] {
+			let call = crate::Call::submit_from_device { payload: payload.clone(), signature };
+			assert_eq!(validate(&call), Err(InvalidTransaction::BadProof.into()));
+		}
+		assert_ok!(validate(&device_call(DEVICE, 0, scan(0))));
+	});
+}
+
+#[test]
+fn organizations_can_only_register_so_many_devices() {
+	new_test_ext().execute_with(|| {
+		let carriers = Members::<Test>::get(CARRIER).unwrap();
+		for device in [DEVICE, DEVICE + 1] {
+			assert_ok!(LogisticsModule::register_device(
+				RuntimeOrigin::signed(CARRIER),
+				UintAuthorityId(device)
+			));
+		}
+		assert_eq!(DeviceCounts::<Test>::get(carriers), 2);
+		// Counted for the organization, whichever member registers them.
+		assert_noop!(
+			LogisticsModule::register_device(
+				RuntimeOrigin::signed(COURIER),
+				UintAuthorityId(DEVICE + 2)
+			),
+			Error::<Test>::TooManyDevices
+		);
+		assert_ok!(LogisticsModule::register_device(
+			RuntimeOrigin::signed(SHIPPER),
+			UintAuthorityId(DEVICE + 2)
+		));
+
+		assert_ok!(LogisticsModule::deregister_device(
+			RuntimeOrigin::signed(CARRIER),
+			UintAuthorityId(DEVICE)
+		));
+		assert_eq!(DeviceCounts::<Test>::get(carriers), 1);
+		assert_ok!(LogisticsModule::register_device(
+			RuntimeOrigin::signed(COURIER),
+			UintAuthorityId(DEVICE + 3)
+		));
+
+		let shippers = Members::<Test>::get(SHIPPER).unwrap();
+		assert_ok!(LogisticsModule::deregister_device(
+			RuntimeOrigin::signed(SHIPPER),
+			UintAuthorityId(DEVICE + 2)
+		));
+		assert!(!DeviceCounts::<Test>::contains_key(shippers));
+	});
+}
 
 #[test]
-fn it_works_for_default_value() {
+fn device_registry_fails_for_invalid_requests() {
 	new_test_ext().execute_with(|| {
-		// Go past genesis block so events get deposited
-		System::set_block_number(1);
-		// Dispatch a signed extrinsic.
-		assert_ok!(LogisticsModule::do_something(RuntimeOrigin::signed(1), 42));
-		// Read pallet storage and assert an expected result.
-		assert_eq!(LogisticsModule::something(), Some(42));
-		// Assert that the correct event was deposited
-		System::assert_last_event(Event::SomethingStored { something: 42, who: 1 }.into());
+		let device = UintAuthorityId(DEVICE);
+		assert_noop!(
+			LogisticsModule::register_device(RuntimeOrigin::signed(STRANGER), device.clone()),
+			Error::<Test>::NotMember
+		);
+		assert_noop!(
+			LogisticsModule::register_device(RuntimeOrigin::signed(CONSIGNEE), device.clone()),
+			Error::<Test>::MissingRole
+		);
+		assert_noop!(
+			LogisticsModule::deregister_device(RuntimeOrigin::signed(CARRIER), device.clone()),
+			Error::<Test>::DeviceNotRegistered
+		);
+
+		assert_ok!(LogisticsModule::register_device(
+			RuntimeOrigin::signed(CARRIER),
+			device.clone()
+		));
+		assert_noop!(
+			LogisticsModule::register_device(RuntimeOrigin::signed(SHIPPER), device.clone()),
+			Error::<Test>::DeviceAlreadyRegistered
+		);
+		assert_noop!(
+			LogisticsModule::deregister_device(RuntimeOrigin::signed(SHIPPER), device.clone()),
+			Error::<Test>::NotDeviceOrganization
+		);
+		assert_ok!(submit(0, scan(0)));
+
+		assert_ok!(LogisticsModule::deregister_device(
+			RuntimeOrigin::signed(COURIER),
+			device.clone()
+		));
+		assert!(!Devices::<Test>::contains_key(&device));
+		assert_noop!(submit(1, scan(0)), Error::<Test>::DeviceNotRegistered);
+
+		// Registering it again does not make its old payloads valid again.
+		assert_ok!(LogisticsModule::register_device(
+			RuntimeOrigin::signed(SHIPPER),
+			device.clone()
+		));
+		assert_noop!(submit(0, scan(0)), Error::<Test>::InvalidNonce);
 	});
 }
 
 #[test]
-fn correct_error_for_none_value() {
+fn coords_are_validated() {
+	assert!(Coords::new(90_000_000, -180_000_000).is_some());
+	assert!(Coords::new(-90_000_001, 0).is_none());
+	assert!(Coords::new(0, 180_000_001).is_none());
+
+	let position = coords().with_altitude(-12).with_accuracy(30);
+	assert_eq!((position.lat(), position.lng()), (51_507_400, -127_800));
+	assert_eq!((position.altitude(), position.accuracy()), (Some(-12), Some(30)));
+}
+
+#[test]
+fn coords_measure_ground_distance() {
+	let london = coords();
+	let paris = Coords::new(48_856_600, 2_352_200).unwrap();
+	// 343.5 km along the great circle.
+	assert_eq!(london.distance_to(&paris), 343_470);
+	assert_eq!(paris.distance_to(&london), 343_470);
+	assert_eq!(london.distance_to(&london.clone().with_altitude(100)), 0);
+
+	// Across the antimeridian rather than around the globe.
+	let east = Coords::new(0, 179_999_000).unwrap();
+	let west = Coords::new(0, -179_999_000).unwrap();
+	assert_eq!(east.distance_to(&west), 222);
+}
+
+#[test]
+fn calls_reject_invalid_coordinates() {
 	new_test_ext().execute_with(|| {
-		// Ensure the expected error is thrown when no value is present.
+		// Out-of-range values can only arrive by decoding, as `Coords::new` rejects them.
+		let invalid: Coords =
+			Decode::decode(&mut &(91_000_000i32, 0i32, None::<i32>, None::<u32>).encode()[..])
+				.unwrap();
+		assert!(!invalid.is_valid());
+
+		assert_noop!(
+			LogisticsModule::begin_transit(
+				RuntimeOrigin::signed(SHIPPER),
+				0,
+				invalid.clone(),
+				None,
+				DESTINATION,
+				CONSIGNEE,
+				manifest(),
+				FEE,
+				None
+			),
+			Error::<Test>::InvalidCoordinates
+		);
 		assert_noop!(
-			LogisticsModule::cause_error(RuntimeOrigin::signed(1)),
-			Error::<Test>::NoneValue
+			LogisticsModule::register_facility(
+				RuntimeOrigin::signed(OPERATOR),
+				[0; 32],
+				invalid.clone(),
+				FacilityKind::Depot
+			),
+			Error::<Test>::InvalidCoordinates
+		);
+
+		create(0);
+		assert_ok!(LogisticsModule::offer_handoff(RuntimeOrigin::signed(SHIPPER), 0, CARRIER));
+		assert_noop!(
+			LogisticsModule::shipment_received(RuntimeOrigin::signed(CARRIER), 0, invalid.clone()),
+			Error::<Test>::InvalidCoordinates
+		);
+
+		assert_ok!(LogisticsModule::shipment_received(RuntimeOrigin::signed(CARRIER), 0, coords()));
+		assert_noop!(
+			LogisticsModule::shipment_delivered(
+				RuntimeOrigin::signed(CARRIER),
+				0,
+				invalid,
+				None,
+				Default::default()
+			),
+			Error::<Test>::InvalidCoordinates
+		);
+	});
+}
+
+#[test]
+fn migrations_bring_v0_shipments_up_to_date() {
+	new_test_ext().execute_with(|| {
+		StorageVersion::new(0).put::<LogisticsModule>();
+		for (shipment_id, delivered) in [(0u64, false), (1, true)] {
+			let old = (
+				shipment_id,
+				SHIPPER,
+				CARRIER,
+				(51_507_400u32, 127_800u32),
+				1u64,
+				DESTINATION,
+				delivered,
+			);
+			frame_support::storage::unhashed::put_raw(
+				&Shipments::<Test>::hashed_key_for(shipment_id),
+				&old.encode(),
+			);
+		}
+		Shipments::<Test>::initialize_counter();
+		let old_hop = (CARRIER, (u32::MAX, 127_800u32), 1u64, 1_000u64);
+		frame_support::storage::unhashed::put_raw(
+			&CustodyHistory::<Test>::hashed_key_for(1),
+			&vec![old_hop].encode(),
+		);
+		let old_temperature = Aggregate { min: 500, max: 2_000, sum: 2_500, count: 2 };
+		frame_support::storage::unhashed::put_raw(
+			&Telemetry::<Test>::hashed_key_for(0),
+			&migrations::v7::OldAggregates {
+				temperature: old_temperature,
+				humidity: Aggregate::default(),
+				shock: Aggregate::default(),
+				readings: 2,
+				breaches: 1,
+				last_reported_at: 1_000,
+			}
+			.encode(),
+		);
+		Devices::<Test>::insert(UintAuthorityId(DEVICE), 1);
+		Devices::<Test>::insert(UintAuthorityId(DEVICE + 1), 1);
+
+		<(
+			migrations::v1::MigrateToV1<Test>,
+			migrations::v2::MigrateToV2<Test>,
+			migrations::v3::MigrateToV3<Test>,
+			migrations::v4::MigrateToV4<Test>,
+			migrations::v5::MigrateToV5<Test>,
+			migrations::v6::MigrateToV6<Test>,
+			migrations::v7::MigrateToV7<Test>,
+			migrations::v8::MigrateToV8<Test>,
+		) as OnRuntimeUpgrade>::on_runtime_upgrade();
+
+		assert_eq!(shipment(0).status, ShipmentStatus::InTransit);
+		assert_eq!(shipment(1).status, ShipmentStatus::Delivered);
+		assert_eq!(shipment(1).received_by, CARRIER);
+		assert_eq!(shipment(1).origin, None);
+		assert!(!shipment(1).returning);
+		assert_eq!(shipment(1).consignee, None);
+		// Legacy positions had no sign and are read as north and east.
+		assert_eq!(shipment(1).received_at, Coords::new(51_507_400, 127_800).unwrap());
+		assert_eq!(Shipments::<Test>::count(), 2);
+		assert_eq!(
+			LogisticsModule::custody_history(1),
+			vec![CustodyRecord {
+				holder: CARRIER,
+				coords: Coords::new(90_000_000, 127_800).unwrap(),
+				block: 1,
+				timestamp: 1_000,
+			}]
 		);
+		let telemetry = Telemetry::<Test>::get(0);
+		assert_eq!(telemetry.temperature(), old_temperature);
+		assert_eq!((telemetry.readings, telemetry.breaches), (2, 1));
+		assert_eq!(telemetry.current.readings, 0);
+		assert_eq!(DeviceCounts::<Test>::get(1), 2);
+		assert_eq!(LogisticsModule::on_chain_storage_version(), 8);
 	});
 }
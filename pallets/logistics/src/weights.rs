@@ -0,0 +1,1110 @@
+//! Weights for pallet_logistics
+//!
+//! CONSERVATIVE PLACEHOLDERS: none of the weights below were measured. Until the
+//! `[pallet_logistics, LogisticsModule]` benchmarks are run on reference hardware, every weight is
+//! a deliberately generous upper bound, so that the runtime overcharges rather than undercharges:
+//!
+//! - execution time is ten times a hand-written estimate for the call,
+//! - every storage item the call can touch, as listed for each function, is counted as read and
+//!   written as often as it can be in the worst case,
+//! - proof sizes assume every item read is at its maximum encoded length.
+//!
+//! Replace this file with the output of the following, and again whenever the pallet's storage
+//! access patterns change:
+//!
+//! ```sh
+//! ./target/release/node-template benchmark pallet \
+//!     --chain=dev \
+//!     --steps=50 \
+//!     --repeat=20 \
+//!     --pallet=pallet_logistics \
+//!     --extrinsic='*' \
+//!     --execution=wasm \
+//!     --wasm-execution=compiled \
+//!     --output=pallets/logistics/src/weights.rs \
+//!     --template=../substrate/.maintain/frame-weight-template.hbs
+//! ```
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for pallet_logistics.
+pub trait WeightInfo {
+	fn begin_transit() -> Weight;
+	fn shipment_received(c: u32, ) -> Weight;
+	fn offer_handoff() -> Weight;
+	fn cancel_handoff() -> Weight;
+	fn update_status(c: u32, ) -> Weight;
+	fn shipment_delivered(h: u32, ) -> Weight;
+	fn prune_delivered(n: u32, ) -> Weight;
+	fn register_facility() -> Weight;
+	fn update_facility() -> Weight;
+	fn set_facility_status() -> Weight;
+	fn set_facility_geofence() -> Weight;
+	fn set_default_geofence() -> Weight;
+	fn register_organization() -> Weight;
+	fn set_organization_roles() -> Weight;
+	fn add_member() -> Weight;
+	fn remove_member() -> Weight;
+	fn amend_manifest() -> Weight;
+	fn reap_abandoned() -> Weight;
+	fn process_deadlines(n: u32, ) -> Weight;
+	fn file_claim() -> Weight;
+	fn respond_to_claim() -> Weight;
+	fn resolve_claim() -> Weight;
+	fn cancel_shipment() -> Weight;
+	fn return_to_sender() -> Weight;
+	fn reject_shipment() -> Weight;
+	fn confirm_receipt() -> Weight;
+	fn set_consignee_key() -> Weight;
+	fn load_shipments(n: u32, ) -> Weight;
+	fn unload_shipments(n: u32, ) -> Weight;
+	fn split_shipment(n: u32, ) -> Weight;
+	fn set_condition_ranges() -> Weight;
+	fn record_reading() -> Weight;
+	fn register_device() -> Weight;
+	fn deregister_device() -> Weight;
+	fn submit_scan(c: u32, ) -> Weight;
+	fn submit_reading() -> Weight;
+}
+
+/// Conservative weights for pallet_logistics until it is benchmarked, see the module docs.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	/// Conservative upper bound, not benchmarked.
+	/// Storage: LogisticsModule Members (r:2 w:0)
+	/// Storage: LogisticsModule Organizations (r:2 w:0)
+	/// Storage: LogisticsModule Shipments (r:1 w:1)
+	/// Storage: LogisticsModule Facilities (r:2 w:0)
+	/// Storage: LogisticsModule CounterForShipments (r:1 w:1)
+	/// Storage: LogisticsModule CustodyHistory (r:1 w:1)
+	/// Storage: LogisticsModule Manifests (r:0 w:1)
+	/// Storage: Timestamp Now (r:1 w:0)
+	/// Storage: System Account (r:1 w:1)
+	/// Storage: LogisticsModule Escrows (r:0 w:1)
+	/// Storage: LogisticsModule Deposits (r:1 w:1)
+	/// Storage: LogisticsModule DeadlineCursor (r:1 w:1)
+	/// Storage: LogisticsModule DeadlineAgenda (r:1 w:1)
+	/// Storage: LogisticsModule Deadlines (r:0 w:1)
+	fn begin_transit() -> Weight {
+		Weight::from_parts(590_000_000, 27900)
+			.saturating_add(T::DbWeight::get().reads(14_u64))
+			.saturating_add(T::DbWeight::get().writes(10_u64))
+	}
+	/// Conservative upper bound, not benchmarked.
+	/// Storage: LogisticsModule Members (r:1 w:0)
+	/// Storage: LogisticsModule Organizations (r:1 w:0)
+	/// Storage: LogisticsModule Shipments (r:65 w:65)
+	/// Storage: LogisticsModule PendingHandoffs (r:1 w:1)
+	/// Storage: LogisticsModule CustodyHistory (r:65 w:65)
+	/// Storage: Timestamp Now (r:1 w:0)
+	/// Storage: LogisticsModule Contents (r:1 w:0)
+	/// Storage: LogisticsModule FormerCustodians (r:65 w:65)
+	/// The range of component `c` is `[0, 64]`.
+	fn shipment_received(c: u32, ) -> Weight {
+		Weight::from_parts(380_000_000, 24776)
+			.saturating_add(Weight::from_parts(246_000_000, 0).saturating_mul(c.into()))
+			.saturating_add(T::DbWeight::get().reads(8_u64))
+			.saturating_add(T::DbWeight::get().reads((3_u64).saturating_mul(c.into())))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+			.saturating_add(T::DbWeight::get().writes((3_u64).saturating_mul(c.into())))
+			.saturating_add(Weight::from_parts(0, 6469).saturating_mul(c.into()))
+	}
+	/// Conservative upper bound, not benchmarked.
+	/// Storage: LogisticsModule Members (r:1 w:0)
+	/// Storage: LogisticsModule Organizations (r:1 w:0)
+	/// Storage: LogisticsModule ContainedIn (r:1 w:0)
+	/// Storage: LogisticsModule Shipments (r:1 w:0)
+	/// Storage: LogisticsModule PendingHandoffs (r:0 w:1)
+	fn offer_handoff() -> Weight {
+		Weight::from_parts(230_000_000, 10183)
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Conservative upper bound, not benchmarked.
+	/// Storage: LogisticsModule Members (r:1 w:0)
+	/// Storage: LogisticsModule Organizations (r:1 w:0)
+	/// Storage: LogisticsModule PendingHandoffs (r:1 w:1)
+	fn cancel_handoff() -> Weight {
+		Weight::from_parts(190_000_000, 7622)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Conservative upper bound, not benchmarked.
+	/// Storage: LogisticsModule Members (r:1 w:0)
+	/// Storage: LogisticsModule Organizations (r:1 w:0)
+	/// Storage: LogisticsModule ContainedIn (r:1 w:64)
+	/// Storage: LogisticsModule Shipments (r:65 w:65)
+	/// Storage: LogisticsModule LastScanned (r:0 w:65)
+	/// Storage: LogisticsModule PendingHandoffs (r:0 w:1)
+	/// Storage: LogisticsModule Escrows (r:65 w:65)
+	/// Storage: System Account (r:65 w:65)
+	/// Storage: LogisticsModule Contents (r:1 w:1)
+	/// Storage: LogisticsModule PruneCursor (r:1 w:1)
+	/// Storage: LogisticsModule PruneQueue (r:0 w:65)
+	/// The range of component `c` is `[0, 64]`.
+	fn update_status(c: u32, ) -> Weight {
+		Weight::from_parts(410_000_000, 18800)
+			.saturating_add(Weight::from_parts(241_000_000, 0).saturating_mul(c.into()))
+			.saturating_add(T::DbWeight::get().reads(8_u64))
+			.saturating_add(T::DbWeight::get().reads((3_u64).saturating_mul(c.into())))
+			.saturating_add(T::DbWeight::get().writes(8_u64))
+			.saturating_add(T::DbWeight::get().writes((6_u64).saturating_mul(c.into())))
+			.saturating_add(Weight::from_parts(0, 5230).saturating_mul(c.into()))
+	}
+	/// Conservative upper bound, not benchmarked.
+	/// Storage: LogisticsModule ContainedIn (r:1 w:0)
+	/// Storage: LogisticsModule Contents (r:1 w:0)
+	/// Storage: LogisticsModule Members (r:1 w:0)
+	/// Storage: LogisticsModule Organizations (r:1 w:0)
+	/// Storage: LogisticsModule Shipments (r:1 w:1)
+	/// Storage: LogisticsModule Facilities (r:1 w:0)
+	/// Storage: LogisticsModule FacilityGeofence (r:1 w:0)
+	/// Storage: LogisticsModule DefaultGeofenceRadius (r:1 w:0)
+	/// Storage: LogisticsModule CustodyHistory (r:1 w:1)
+	/// Storage: Timestamp Now (r:1 w:0)
+	/// Storage: LogisticsModule PruneCursor (r:1 w:1)
+	/// Storage: LogisticsModule PruneQueue (r:0 w:1)
+	/// Storage: LogisticsModule PendingHandoffs (r:0 w:1)
+	/// Storage: LogisticsModule Escrows (r:1 w:1)
+	/// Storage: LogisticsModule Deliveries (r:0 w:1)
+	/// Storage: LogisticsModule ConsigneeKeys (r:1 w:0)
+	/// Storage: LogisticsModule Deadlines (r:1 w:0)
+	/// Storage: LogisticsModule SlaBreaches (r:0 w:1)
+	/// Storage: System Account (r:65 w:65)
+	/// Storage: LogisticsModule FormerCustodians (r:1 w:1)
+	/// The range of component `h` is `[1, 62]`.
+	fn shipment_delivered(h: u32, ) -> Weight {
+		Weight::from_parts(1_060_000_000, 40953)
+			.saturating_add(Weight::from_parts(142_000_000, 0).saturating_mul(h.into()))
+			.saturating_add(T::DbWeight::get().reads(18_u64))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(h.into())))
+			.saturating_add(T::DbWeight::get().writes(12_u64))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(h.into())))
+			.saturating_add(Weight::from_parts(0, 2603).saturating_mul(h.into()))
+	}
+	/// Conservative upper bound, not benchmarked.
+	/// Storage: LogisticsModule PruneCursor (r:1 w:1)
+	/// Storage: LogisticsModule PruneQueue (r:1001 w:1000)
+	/// Storage: LogisticsModule CounterForShipments (r:1 w:1)
+	/// Storage: LogisticsModule Shipments (r:1000 w:1000)
+	/// Storage: LogisticsModule Deposits (r:1000 w:1000)
+	/// Storage: System Account (r:1000 w:1000)
+	/// Storage: LogisticsModule CustodyHistory (r:0 w:1000)
+	/// Storage: LogisticsModule Manifests (r:0 w:1000)
+	/// Storage: LogisticsModule Deadlines (r:0 w:1000)
+	/// Storage: LogisticsModule SlaBreaches (r:0 w:1000)
+	/// Storage: LogisticsModule LastScanned (r:0 w:1000)
+	/// Storage: LogisticsModule UpheldClaims (r:0 w:1000)
+	/// Storage: LogisticsModule Deliveries (r:0 w:1000)
+	/// Storage: LogisticsModule SplitFrom (r:0 w:1000)
+	/// Storage: LogisticsModule AcceptableRanges (r:0 w:1000)
+	/// Storage: LogisticsModule Telemetry (r:0 w:1000)
+	/// Storage: LogisticsModule FormerCustodians (r:0 w:1000)
+	/// The range of component `n` is `[0, 1000]`.
+	fn prune_delivered(n: u32, ) -> Weight {
+		Weight::from_parts(80_000_000, 3506)
+			.saturating_add(Weight::from_parts(240_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().reads((4_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+			.saturating_add(T::DbWeight::get().writes((15_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 10863).saturating_mul(n.into()))
+	}
+	/// Conservative upper bound, not benchmarked.
+	/// Storage: LogisticsModule Members (r:1 w:0)
+	/// Storage: LogisticsModule Organizations (r:1 w:0)
+	/// Storage: LogisticsModule NextFacilityId (r:1 w:1)
+	/// Storage: LogisticsModule Facilities (r:0 w:1)
+	fn register_facility() -> Weight {
+		Weight::from_parts(210_000_000, 5558)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Conservative upper bound, not benchmarked.
+	/// Storage: LogisticsModule Members (r:1 w:0)
+	/// Storage: LogisticsModule Organizations (r:1 w:0)
+	/// Storage: LogisticsModule Facilities (r:1 w:1)
+	fn update_facility() -> Weight {
+		Weight::from_parts(200_000_000, 7630)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Conservative upper bound, not benchmarked.
+	/// Storage: LogisticsModule Members (r:1 w:0)
+	/// Storage: LogisticsModule Organizations (r:1 w:0)
+	/// Storage: LogisticsModule Facilities (r:1 w:1)
+	fn set_facility_status() -> Weight {
+		Weight::from_parts(190_000_000, 7630)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Conservative upper bound, not benchmarked.
+	/// Storage: LogisticsModule Members (r:1 w:0)
+	/// Storage: LogisticsModule Organizations (r:1 w:0)
+	/// Storage: LogisticsModule Facilities (r:1 w:0)
+	/// Storage: LogisticsModule FacilityGeofence (r:0 w:1)
+	fn set_facility_geofence() -> Weight {
+		Weight::from_parts(180_000_000, 7630)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Conservative upper bound, not benchmarked.
+	/// Storage: LogisticsModule DefaultGeofenceRadius (r:0 w:1)
+	fn set_default_geofence() -> Weight {
+		Weight::from_parts(60_000_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Conservative upper bound, not benchmarked.
+	/// Storage: LogisticsModule NextOrganizationId (r:1 w:1)
+	/// Storage: LogisticsModule Organizations (r:0 w:1)
+	fn register_organization() -> Weight {
+		Weight::from_parts(120_000_000, 503)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Conservative upper bound, not benchmarked.
+	/// Storage: LogisticsModule Organizations (r:1 w:1)
+	fn set_organization_roles() -> Weight {
+		Weight::from_parts(120_000_000, 2524)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Conservative upper bound, not benchmarked.
+	/// Storage: LogisticsModule Organizations (r:1 w:0)
+	/// Storage: LogisticsModule Members (r:1 w:1)
+	fn add_member() -> Weight {
+		Weight::from_parts(140_000_000, 5055)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Conservative upper bound, not benchmarked.
+	/// Storage: LogisticsModule Members (r:1 w:1)
+	fn remove_member() -> Weight {
+		Weight::from_parts(130_000_000, 2531)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Conservative upper bound, not benchmarked.
+	/// Storage: LogisticsModule Members (r:1 w:0)
+	/// Storage: LogisticsModule Organizations (r:1 w:0)
+	/// Storage: LogisticsModule Shipments (r:1 w:0)
+	/// Storage: LogisticsModule PendingHandoffs (r:1 w:0)
+	/// Storage: LogisticsModule Deposits (r:1 w:1)
+	/// Storage: System Account (r:1 w:1)
+	/// Storage: LogisticsModule Manifests (r:0 w:1)
+	fn amend_manifest() -> Weight {
+		Weight::from_parts(310_000_000, 15385)
+			.saturating_add(T::DbWeight::get().reads(6_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	/// Conservative upper bound, not benchmarked.
+	/// Storage: LogisticsModule Contents (r:1 w:0)
+	/// Storage: LogisticsModule Claims (r:1 w:0)
+	/// Storage: LogisticsModule ContainedIn (r:1 w:1)
+	/// Storage: LogisticsModule Shipments (r:1 w:1)
+	/// Storage: LogisticsModule CounterForShipments (r:1 w:1)
+	/// Storage: LogisticsModule CustodyHistory (r:0 w:1)
+	/// Storage: LogisticsModule Manifests (r:0 w:1)
+	/// Storage: LogisticsModule PendingHandoffs (r:0 w:1)
+	/// Storage: LogisticsModule Deadlines (r:0 w:1)
+	/// Storage: LogisticsModule LastScanned (r:1 w:1)
+	/// Storage: LogisticsModule SplitFrom (r:0 w:1)
+	/// Storage: LogisticsModule AcceptableRanges (r:0 w:1)
+	/// Storage: LogisticsModule Telemetry (r:0 w:1)
+	/// Storage: LogisticsModule Escrows (r:1 w:1)
+	/// Storage: LogisticsModule Deposits (r:1 w:1)
+	/// Storage: System Account (r:1 w:1)
+	/// Storage: LogisticsModule FormerCustodians (r:0 w:1)
+	fn reap_abandoned() -> Weight {
+		Weight::from_parts(480_000_000, 21400)
+			.saturating_add(T::DbWeight::get().reads(9_u64))
+			.saturating_add(T::DbWeight::get().writes(15_u64))
+	}
+	/// Conservative upper bound, not benchmarked.
+	/// Storage: LogisticsModule DeadlineCursor (r:1 w:1)
+	/// Storage: Timestamp Now (r:1 w:0)
+	/// Storage: LogisticsModule DeadlineAgenda (r:2 w:2)
+	/// Storage: LogisticsModule Shipments (r:256 w:0)
+	/// Storage: LogisticsModule Deadlines (r:256 w:0)
+	/// The range of component `n` is `[0, 256]`.
+	fn process_deadlines(n: u32, ) -> Weight {
+		Weight::from_parts(90_000_000, 5064)
+			.saturating_add(Weight::from_parts(124_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().reads((2_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+			.saturating_add(Weight::from_parts(0, 5100).saturating_mul(n.into()))
+	}
+	/// Conservative upper bound, not benchmarked.
+	/// Storage: LogisticsModule Members (r:1 w:0)
+	/// Storage: LogisticsModule Organizations (r:1 w:0)
+	/// Storage: LogisticsModule ContainedIn (r:1 w:0)
+	/// Storage: LogisticsModule Contents (r:1 w:0)
+	/// Storage: LogisticsModule Shipments (r:1 w:0)
+	/// Storage: LogisticsModule UpheldClaims (r:1 w:0)
+	/// Storage: LogisticsModule Claims (r:1 w:1)
+	/// Storage: LogisticsModule CustodyHistory (r:1 w:0)
+	/// Storage: System Account (r:1 w:1)
+	/// Storage: LogisticsModule FormerCustodians (r:1 w:0)
+	fn file_claim() -> Weight {
+		Weight::from_parts(440_000_000, 31941)
+			.saturating_add(T::DbWeight::get().reads(10_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Conservative upper bound, not benchmarked.
+	/// Storage: LogisticsModule Members (r:1 w:0)
+	/// Storage: LogisticsModule Organizations (r:1 w:0)
+	/// Storage: LogisticsModule Claims (r:1 w:1)
+	/// Storage: System Account (r:1 w:1)
+	fn respond_to_claim() -> Weight {
+		Weight::from_parts(280_000_000, 10307)
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Conservative upper bound, not benchmarked.
+	/// Storage: LogisticsModule Claims (r:1 w:1)
+	/// Storage: System Account (r:3 w:3)
+	/// Storage: LogisticsModule Shipments (r:1 w:1)
+	/// Storage: LogisticsModule UpheldClaims (r:0 w:1)
+	/// Storage: LogisticsModule PendingHandoffs (r:0 w:1)
+	/// Storage: LogisticsModule Escrows (r:1 w:1)
+	/// Storage: LogisticsModule Deposits (r:1 w:1)
+	/// Storage: LogisticsModule PruneCursor (r:1 w:1)
+	/// Storage: LogisticsModule PruneQueue (r:0 w:1)
+	fn resolve_claim() -> Weight {
+		Weight::from_parts(590_000_000, 13438)
+			.saturating_add(T::DbWeight::get().reads(8_u64))
+			.saturating_add(T::DbWeight::get().writes(12_u64))
+	}
+	/// Conservative upper bound, not benchmarked.
+	/// Storage: LogisticsModule ContainedIn (r:1 w:0)
+	/// Storage: LogisticsModule Contents (r:1 w:0)
+	/// Storage: LogisticsModule Members (r:1 w:0)
+	/// Storage: LogisticsModule Organizations (r:1 w:0)
+	/// Storage: LogisticsModule Shipments (r:1 w:1)
+	/// Storage: LogisticsModule PruneCursor (r:1 w:1)
+	/// Storage: LogisticsModule PruneQueue (r:0 w:1)
+	/// Storage: LogisticsModule PendingHandoffs (r:0 w:1)
+	/// Storage: LogisticsModule Deadlines (r:0 w:1)
+	/// Storage: LogisticsModule Escrows (r:1 w:1)
+	/// Storage: System Account (r:1 w:1)
+	fn cancel_shipment() -> Weight {
+		Weight::from_parts(420_000_000, 18800)
+			.saturating_add(T::DbWeight::get().reads(8_u64))
+			.saturating_add(T::DbWeight::get().writes(7_u64))
+	}
+	/// Conservative upper bound, not benchmarked.
+	/// Storage: LogisticsModule Members (r:1 w:0)
+	/// Storage: LogisticsModule Organizations (r:1 w:0)
+	/// Storage: LogisticsModule Shipments (r:1 w:1)
+	/// Storage: LogisticsModule Deadlines (r:0 w:1)
+	fn return_to_sender() -> Weight {
+		Weight::from_parts(240_000_000, 7700)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Conservative upper bound, not benchmarked.
+	/// Storage: LogisticsModule Members (r:1 w:0)
+	/// Storage: LogisticsModule Organizations (r:1 w:0)
+	/// Storage: LogisticsModule Shipments (r:1 w:1)
+	/// Storage: LogisticsModule ContainedIn (r:1 w:0)
+	/// Storage: LogisticsModule Contents (r:1 w:0)
+	/// Storage: LogisticsModule PruneCursor (r:1 w:1)
+	/// Storage: LogisticsModule PruneQueue (r:0 w:1)
+	/// Storage: LogisticsModule PendingHandoffs (r:0 w:1)
+	/// Storage: LogisticsModule Deadlines (r:0 w:1)
+	/// Storage: LogisticsModule Escrows (r:1 w:1)
+	/// Storage: System Account (r:1 w:1)
+	/// Storage: LogisticsModule Deliveries (r:1 w:0)
+	fn reject_shipment() -> Weight {
+		Weight::from_parts(400_000_000, 21590)
+			.saturating_add(T::DbWeight::get().reads(9_u64))
+			.saturating_add(T::DbWeight::get().writes(7_u64))
+	}
+	/// Conservative upper bound, not benchmarked.
+	/// Storage: LogisticsModule Members (r:1 w:0)
+	/// Storage: LogisticsModule Organizations (r:1 w:0)
+	/// Storage: LogisticsModule Shipments (r:1 w:0)
+	/// Storage: LogisticsModule Deliveries (r:1 w:1)
+	fn confirm_receipt() -> Weight {
+		Weight::from_parts(210_000_000, 10490)
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Conservative upper bound, not benchmarked.
+	/// Storage: LogisticsModule Members (r:1 w:0)
+	/// Storage: LogisticsModule Organizations (r:1 w:0)
+	/// Storage: LogisticsModule ConsigneeKeys (r:0 w:1)
+	fn set_consignee_key() -> Weight {
+		Weight::from_parts(150_000_000, 5055)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Conservative upper bound, not benchmarked.
+	/// Storage: LogisticsModule Members (r:1 w:0)
+	/// Storage: LogisticsModule Organizations (r:1 w:0)
+	/// Storage: LogisticsModule Shipments (r:65 w:0)
+	/// Storage: LogisticsModule ContainedIn (r:65 w:64)
+	/// Storage: LogisticsModule Claims (r:65 w:0)
+	/// Storage: LogisticsModule Contents (r:65 w:1)
+	/// Storage: LogisticsModule PendingHandoffs (r:64 w:0)
+	/// The range of component `n` is `[1, 64]`.
+	fn load_shipments(n: u32, ) -> Weight {
+		Weight::from_parts(260_000_000, 18387)
+			.saturating_add(Weight::from_parts(152_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(6_u64))
+			.saturating_add(T::DbWeight::get().reads((5_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 2603).saturating_mul(n.into()))
+	}
+	/// Conservative upper bound, not benchmarked.
+	/// Storage: LogisticsModule Members (r:1 w:0)
+	/// Storage: LogisticsModule Organizations (r:1 w:0)
+	/// Storage: LogisticsModule Shipments (r:1 w:0)
+	/// Storage: LogisticsModule Contents (r:1 w:1)
+	/// Storage: LogisticsModule ContainedIn (r:0 w:64)
+	/// The range of component `n` is `[1, 64]`.
+	fn unload_shipments(n: u32, ) -> Weight {
+		Weight::from_parts(220_000_000, 10688)
+			.saturating_add(Weight::from_parts(41_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(n.into())))
+	}
+	/// Conservative upper bound, not benchmarked.
+	/// Storage: LogisticsModule Members (r:1 w:0)
+	/// Storage: LogisticsModule Organizations (r:1 w:0)
+	/// Storage: LogisticsModule ContainedIn (r:1 w:0)
+	/// Storage: LogisticsModule Contents (r:1 w:0)
+	/// Storage: LogisticsModule Shipments (r:17 w:17)
+	/// Storage: LogisticsModule CounterForShipments (r:1 w:1)
+	/// Storage: LogisticsModule PendingHandoffs (r:1 w:0)
+	/// Storage: LogisticsModule Claims (r:1 w:0)
+	/// Storage: LogisticsModule Facilities (r:16 w:0)
+	/// Storage: LogisticsModule CustodyHistory (r:1 w:16)
+	/// Storage: LogisticsModule Deadlines (r:1 w:17)
+	/// Storage: LogisticsModule Escrows (r:1 w:16)
+	/// Storage: LogisticsModule Deposits (r:1 w:17)
+	/// Storage: LogisticsModule DeadlineCursor (r:1 w:1)
+	/// Storage: LogisticsModule DeadlineAgenda (r:16 w:16)
+	/// Storage: LogisticsModule Manifests (r:0 w:16)
+	/// Storage: LogisticsModule SplitFrom (r:0 w:16)
+	/// Storage: LogisticsModule AcceptableRanges (r:1 w:16)
+	/// Storage: LogisticsModule Telemetry (r:1 w:16)
+	/// Storage: LogisticsModule PruneCursor (r:1 w:1)
+	/// Storage: LogisticsModule PruneQueue (r:0 w:1)
+	/// Storage: LogisticsModule FormerCustodians (r:1 w:16)
+	/// The range of component `n` is `[2, 16]`.
+	fn split_shipment(n: u32, ) -> Weight {
+		Weight::from_parts(480_000_000, 50673)
+			.saturating_add(Weight::from_parts(341_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(17_u64))
+			.saturating_add(T::DbWeight::get().reads((3_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes(6_u64))
+			.saturating_add(T::DbWeight::get().writes((12_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 6469).saturating_mul(n.into()))
+	}
+	/// Conservative upper bound, not benchmarked.
+	/// Storage: LogisticsModule Members (r:1 w:0)
+	/// Storage: LogisticsModule Organizations (r:1 w:0)
+	/// Storage: LogisticsModule Shipments (r:1 w:0)
+	/// Storage: LogisticsModule AcceptableRanges (r:0 w:1)
+	fn set_condition_ranges() -> Weight {
+		Weight::from_parts(210_000_000, 7700)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Conservative upper bound, not benchmarked.
+	/// Storage: LogisticsModule Members (r:1 w:0)
+	/// Storage: LogisticsModule Organizations (r:1 w:0)
+	/// Storage: LogisticsModule Shipments (r:1 w:0)
+	/// Storage: LogisticsModule AcceptableRanges (r:1 w:0)
+	/// Storage: LogisticsModule Telemetry (r:1 w:1)
+	/// Storage: Timestamp Now (r:1 w:0)
+	fn record_reading() -> Weight {
+		Weight::from_parts(290_000_000, 13312)
+			.saturating_add(T::DbWeight::get().reads(6_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Conservative upper bound, not benchmarked.
+	/// Storage: LogisticsModule Members (r:1 w:0)
+	/// Storage: LogisticsModule Organizations (r:1 w:0)
+	/// Storage: LogisticsModule Devices (r:1 w:1)
+	/// Storage: LogisticsModule DeviceCounts (r:1 w:1)
+	fn register_device() -> Weight {
+		Weight::from_parts(190_000_000, 10081)
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Conservative upper bound, not benchmarked.
+	/// Storage: LogisticsModule Members (r:1 w:0)
+	/// Storage: LogisticsModule Devices (r:1 w:1)
+	/// Storage: LogisticsModule DeviceCounts (r:1 w:1)
+	fn deregister_device() -> Weight {
+		Weight::from_parts(180_000_000, 7557)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Conservative upper bound, not benchmarked.
+	/// Storage: LogisticsModule Devices (r:1 w:0)
+	/// Storage: LogisticsModule DeviceUsages (r:1 w:1)
+	/// Storage: LogisticsModule Organizations (r:1 w:0)
+	/// Storage: LogisticsModule ContainedIn (r:1 w:64)
+	/// Storage: LogisticsModule Shipments (r:65 w:65)
+	/// Storage: LogisticsModule LastScanned (r:0 w:65)
+	/// Storage: LogisticsModule Members (r:1 w:0)
+	/// Storage: LogisticsModule PendingHandoffs (r:0 w:1)
+	/// Storage: LogisticsModule Escrows (r:65 w:65)
+	/// Storage: System Account (r:65 w:65)
+	/// Storage: LogisticsModule Contents (r:1 w:1)
+	/// Storage: LogisticsModule PruneCursor (r:1 w:1)
+	/// Storage: LogisticsModule PruneQueue (r:0 w:65)
+	/// The range of component `c` is `[0, 64]`.
+	fn submit_scan(c: u32, ) -> Weight {
+		Weight::from_parts(450_000_000, 23870)
+			.saturating_add(Weight::from_parts(241_000_000, 0).saturating_mul(c.into()))
+			.saturating_add(T::DbWeight::get().reads(10_u64))
+			.saturating_add(T::DbWeight::get().reads((3_u64).saturating_mul(c.into())))
+			.saturating_add(T::DbWeight::get().writes(9_u64))
+			.saturating_add(T::DbWeight::get().writes((6_u64).saturating_mul(c.into())))
+			.saturating_add(Weight::from_parts(0, 5230).saturating_mul(c.into()))
+	}
+	/// Conservative upper bound, not benchmarked.
+	/// Storage: LogisticsModule Devices (r:1 w:0)
+	/// Storage: LogisticsModule DeviceUsages (r:1 w:1)
+	/// Storage: LogisticsModule Organizations (r:1 w:0)
+	/// Storage: LogisticsModule Shipments (r:1 w:0)
+	/// Storage: LogisticsModule Members (r:1 w:0)
+	/// Storage: LogisticsModule AcceptableRanges (r:1 w:0)
+	/// Storage: LogisticsModule Telemetry (r:1 w:1)
+	/// Storage: Timestamp Now (r:1 w:0)
+	fn submit_reading() -> Weight {
+		Weight::from_parts(330_000_000, 18382)
+			.saturating_add(T::DbWeight::get().reads(8_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	/// Conservative upper bound, not benchmarked.
+	/// Storage: LogisticsModule Members (r:2 w:0)
+	/// Storage: LogisticsModule Organizations (r:2 w:0)
+	/// Storage: LogisticsModule Shipments (r:1 w:1)
+	/// Storage: LogisticsModule Facilities (r:2 w:0)
+	/// Storage: LogisticsModule CounterForShipments (r:1 w:1)
+	/// Storage: LogisticsModule CustodyHistory (r:1 w:1)
+	/// Storage: LogisticsModule Manifests (r:0 w:1)
+	/// Storage: Timestamp Now (r:1 w:0)
+	/// Storage: System Account (r:1 w:1)
+	/// Storage: LogisticsModule Escrows (r:0 w:1)
+	/// Storage: LogisticsModule Deposits (r:1 w:1)
+	/// Storage: LogisticsModule DeadlineCursor (r:1 w:1)
+	/// Storage: LogisticsModule DeadlineAgenda (r:1 w:1)
+	/// Storage: LogisticsModule Deadlines (r:0 w:1)
+	fn begin_transit() -> Weight {
+		Weight::from_parts(590_000_000, 27900)
+			.saturating_add(RocksDbWeight::get().reads(14_u64))
+			.saturating_add(RocksDbWeight::get().writes(10_u64))
+	}
+	/// Conservative upper bound, not benchmarked.
+	/// Storage: LogisticsModule Members (r:1 w:0)
+	/// Storage: LogisticsModule Organizations (r:1 w:0)
+	/// Storage: LogisticsModule Shipments (r:65 w:65)
+	/// Storage: LogisticsModule PendingHandoffs (r:1 w:1)
+	/// Storage: LogisticsModule CustodyHistory (r:65 w:65)
+	/// Storage: Timestamp Now (r:1 w:0)
+	/// Storage: LogisticsModule Contents (r:1 w:0)
+	/// Storage: LogisticsModule FormerCustodians (r:65 w:65)
+	/// The range of component `c` is `[0, 64]`.
+	fn shipment_received(c: u32, ) -> Weight {
+		Weight::from_parts(380_000_000, 24776)
+			.saturating_add(Weight::from_parts(246_000_000, 0).saturating_mul(c.into()))
+			.saturating_add(RocksDbWeight::get().reads(8_u64))
+			.saturating_add(RocksDbWeight::get().reads((3_u64).saturating_mul(c.into())))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
+			.saturating_add(RocksDbWeight::get().writes((3_u64).saturating_mul(c.into())))
+			.saturating_add(Weight::from_parts(0, 6469).saturating_mul(c.into()))
+	}
+	/// Conservative upper bound, not benchmarked.
+	/// Storage: LogisticsModule Members (r:1 w:0)
+	/// Storage: LogisticsModule Organizations (r:1 w:0)
+	/// Storage: LogisticsModule ContainedIn (r:1 w:0)
+	/// Storage: LogisticsModule Shipments (r:1 w:0)
+	/// Storage: LogisticsModule PendingHandoffs (r:0 w:1)
+	fn offer_handoff() -> Weight {
+		Weight::from_parts(230_000_000, 10183)
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Conservative upper bound, not benchmarked.
+	/// Storage: LogisticsModule Members (r:1 w:0)
+	/// Storage: LogisticsModule Organizations (r:1 w:0)
+	/// Storage: LogisticsModule PendingHandoffs (r:1 w:1)
+	fn cancel_handoff() -> Weight {
+		Weight::from_parts(190_000_000, 7622)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Conservative upper bound, not benchmarked.
+	/// Storage: LogisticsModule Members (r:1 w:0)
+	/// Storage: LogisticsModule Organizations (r:1 w:0)
+	/// Storage: LogisticsModule ContainedIn (r:1 w:64)
+	/// Storage: LogisticsModule Shipments (r:65 w:65)
+	/// Storage: LogisticsModule LastScanned (r:0 w:65)
+	/// Storage: LogisticsModule PendingHandoffs (r:0 w:1)
+	/// Storage: LogisticsModule Escrows (r:65 w:65)
+	/// Storage: System Account (r:65 w:65)
+	/// Storage: LogisticsModule Contents (r:1 w:1)
+	/// Storage: LogisticsModule PruneCursor (r:1 w:1)
+	/// Storage: LogisticsModule PruneQueue (r:0 w:65)
+	/// The range of component `c` is `[0, 64]`.
+	fn update_status(c: u32, ) -> Weight {
+		Weight::from_parts(410_000_000, 18800)
+			.saturating_add(Weight::from_parts(241_000_000, 0).saturating_mul(c.into()))
+			.saturating_add(RocksDbWeight::get().reads(8_u64))
+			.saturating_add(RocksDbWeight::get().reads((3_u64).saturating_mul(c.into())))
+			.saturating_add(RocksDbWeight::get().writes(8_u64))
+			.saturating_add(RocksDbWeight::get().writes((6_u64).saturating_mul(c.into())))
+			.saturating_add(Weight::from_parts(0, 5230).saturating_mul(c.into()))
+	}
+	/// Conservative upper bound, not benchmarked.
+	/// Storage: LogisticsModule ContainedIn (r:1 w:0)
+	/// Storage: LogisticsModule Contents (r:1 w:0)
+	/// Storage: LogisticsModule Members (r:1 w:0)
+	/// Storage: LogisticsModule Organizations (r:1 w:0)
+	/// Storage: LogisticsModule Shipments (r:1 w:1)
+	/// Storage: LogisticsModule Facilities (r:1 w:0)
+	/// Storage: LogisticsModule FacilityGeofence (r:1 w:0)
+	/// Storage: LogisticsModule DefaultGeofenceRadius (r:1 w:0)
+	/// Storage: LogisticsModule CustodyHistory (r:1 w:1)
+	/// Storage: Timestamp Now (r:1 w:0)
+	/// Storage: LogisticsModule PruneCursor (r:1 w:1)
+	/// Storage: LogisticsModule PruneQueue (r:0 w:1)
+	/// Storage: LogisticsModule PendingHandoffs (r:0 w:1)
+	/// Storage: LogisticsModule Escrows (r:1 w:1)
+	/// Storage: LogisticsModule Deliveries (r:0 w:1)
+	/// Storage: LogisticsModule ConsigneeKeys (r:1 w:0)
+	/// Storage: LogisticsModule Deadlines (r:1 w:0)
+	/// Storage: LogisticsModule SlaBreaches (r:0 w:1)
+	/// Storage: System Account (r:65 w:65)
+	/// Storage: LogisticsModule FormerCustodians (r:1 w:1)
+	/// The range of component `h` is `[1, 62]`.
+	fn shipment_delivered(h: u32, ) -> Weight {
+		Weight::from_parts(1_060_000_000, 40953)
+			.saturating_add(Weight::from_parts(142_000_000, 0).saturating_mul(h.into()))
+			.saturating_add(RocksDbWeight::get().reads(18_u64))
+			.saturating_add(RocksDbWeight::get().reads((1_u64).saturating_mul(h.into())))
+			.saturating_add(RocksDbWeight::get().writes(12_u64))
+			.saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(h.into())))
+			.saturating_add(Weight::from_parts(0, 2603).saturating_mul(h.into()))
+	}
+	/// Conservative upper bound, not benchmarked.
+	/// Storage: LogisticsModule PruneCursor (r:1 w:1)
+	/// Storage: LogisticsModule PruneQueue (r:1001 w:1000)
+	/// Storage: LogisticsModule CounterForShipments (r:1 w:1)
+	/// Storage: LogisticsModule Shipments (r:1000 w:1000)
+	/// Storage: LogisticsModule Deposits (r:1000 w:1000)
+	/// Storage: System Account (r:1000 w:1000)
+	/// Storage: LogisticsModule CustodyHistory (r:0 w:1000)
+	/// Storage: LogisticsModule Manifests (r:0 w:1000)
+	/// Storage: LogisticsModule Deadlines (r:0 w:1000)
+	/// Storage: LogisticsModule SlaBreaches (r:0 w:1000)
+	/// Storage: LogisticsModule LastScanned (r:0 w:1000)
+	/// Storage: LogisticsModule UpheldClaims (r:0 w:1000)
+	/// Storage: LogisticsModule Deliveries (r:0 w:1000)
+	/// Storage: LogisticsModule SplitFrom (r:0 w:1000)
+	/// Storage: LogisticsModule AcceptableRanges (r:0 w:1000)
+	/// Storage: LogisticsModule Telemetry (r:0 w:1000)
+	/// Storage: LogisticsModule FormerCustodians (r:0 w:1000)
+	/// The range of component `n` is `[0, 1000]`.
+	fn prune_delivered(n: u32, ) -> Weight {
+		Weight::from_parts(80_000_000, 3506)
+			.saturating_add(Weight::from_parts(240_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().reads((4_u64).saturating_mul(n.into())))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+			.saturating_add(RocksDbWeight::get().writes((15_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 10863).saturating_mul(n.into()))
+	}
+	/// Conservative upper bound, not benchmarked.
+	/// Storage: LogisticsModule Members (r:1 w:0)
+	/// Storage: LogisticsModule Organizations (r:1 w:0)
+	/// Storage: LogisticsModule NextFacilityId (r:1 w:1)
+	/// Storage: LogisticsModule Facilities (r:0 w:1)
+	fn register_facility() -> Weight {
+		Weight::from_parts(210_000_000, 5558)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Conservative upper bound, not benchmarked.
+	/// Storage: LogisticsModule Members (r:1 w:0)
+	/// Storage: LogisticsModule Organizations (r:1 w:0)
+	/// Storage: LogisticsModule Facilities (r:1 w:1)
+	fn update_facility() -> Weight {
+		Weight::from_parts(200_000_000, 7630)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Conservative upper bound, not benchmarked.
+	/// Storage: LogisticsModule Members (r:1 w:0)
+	/// Storage: LogisticsModule Organizations (r:1 w:0)
+	/// Storage: LogisticsModule Facilities (r:1 w:1)
+	fn set_facility_status() -> Weight {
+		Weight::from_parts(190_000_000, 7630)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Conservative upper bound, not benchmarked.
+	/// Storage: LogisticsModule Members (r:1 w:0)
+	/// Storage: LogisticsModule Organizations (r:1 w:0)
+	/// Storage: LogisticsModule Facilities (r:1 w:0)
+	/// Storage: LogisticsModule FacilityGeofence (r:0 w:1)
+	fn set_facility_geofence() -> Weight {
+		Weight::from_parts(180_000_000, 7630)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Conservative upper bound, not benchmarked.
+	/// Storage: LogisticsModule DefaultGeofenceRadius (r:0 w:1)
+	fn set_default_geofence() -> Weight {
+		Weight::from_parts(60_000_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Conservative upper bound, not benchmarked.
+	/// Storage: LogisticsModule NextOrganizationId (r:1 w:1)
+	/// Storage: LogisticsModule Organizations (r:0 w:1)
+	fn register_organization() -> Weight {
+		Weight::from_parts(120_000_000, 503)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Conservative upper bound, not benchmarked.
+	/// Storage: LogisticsModule Organizations (r:1 w:1)
+	fn set_organization_roles() -> Weight {
+		Weight::from_parts(120_000_000, 2524)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Conservative upper bound, not benchmarked.
+	/// Storage: LogisticsModule Organizations (r:1 w:0)
+	/// Storage: LogisticsModule Members (r:1 w:1)
+	fn add_member() -> Weight {
+		Weight::from_parts(140_000_000, 5055)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Conservative upper bound, not benchmarked.
+	/// Storage: LogisticsModule Members (r:1 w:1)
+	fn remove_member() -> Weight {
+		Weight::from_parts(130_000_000, 2531)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Conservative upper bound, not benchmarked.
+	/// Storage: LogisticsModule Members (r:1 w:0)
+	/// Storage: LogisticsModule Organizations (r:1 w:0)
+	/// Storage: LogisticsModule Shipments (r:1 w:0)
+	/// Storage: LogisticsModule PendingHandoffs (r:1 w:0)
+	/// Storage: LogisticsModule Deposits (r:1 w:1)
+	/// Storage: System Account (r:1 w:1)
+	/// Storage: LogisticsModule Manifests (r:0 w:1)
+	fn amend_manifest() -> Weight {
+		Weight::from_parts(310_000_000, 15385)
+			.saturating_add(RocksDbWeight::get().reads(6_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+	/// Conservative upper bound, not benchmarked.
+	/// Storage: LogisticsModule Contents (r:1 w:0)
+	/// Storage: LogisticsModule Claims (r:1 w:0)
+	/// Storage: LogisticsModule ContainedIn (r:1 w:1)
+	/// Storage: LogisticsModule Shipments (r:1 w:1)
+	/// Storage: LogisticsModule CounterForShipments (r:1 w:1)
+	/// Storage: LogisticsModule CustodyHistory (r:0 w:1)
+	/// Storage: LogisticsModule Manifests (r:0 w:1)
+	/// Storage: LogisticsModule PendingHandoffs (r:0 w:1)
+	/// Storage: LogisticsModule Deadlines (r:0 w:1)
+	/// Storage: LogisticsModule LastScanned (r:1 w:1)
+	/// Storage: LogisticsModule SplitFrom (r:0 w:1)
+	/// Storage: LogisticsModule AcceptableRanges (r:0 w:1)
+	/// Storage: LogisticsModule Telemetry (r:0 w:1)
+	/// Storage: LogisticsModule Escrows (r:1 w:1)
+	/// Storage: LogisticsModule Deposits (r:1 w:1)
+	/// Storage: System Account (r:1 w:1)
+	/// Storage: LogisticsModule FormerCustodians (r:0 w:1)
+	fn reap_abandoned() -> Weight {
+		Weight::from_parts(480_000_000, 21400)
+			.saturating_add(RocksDbWeight::get().reads(9_u64))
+			.saturating_add(RocksDbWeight::get().writes(15_u64))
+	}
+	/// Conservative upper bound, not benchmarked.
+	/// Storage: LogisticsModule DeadlineCursor (r:1 w:1)
+	/// Storage: Timestamp Now (r:1 w:0)
+	/// Storage: LogisticsModule DeadlineAgenda (r:2 w:2)
+	/// Storage: LogisticsModule Shipments (r:256 w:0)
+	/// Storage: LogisticsModule Deadlines (r:256 w:0)
+	/// The range of component `n` is `[0, 256]`.
+	fn process_deadlines(n: u32, ) -> Weight {
+		Weight::from_parts(90_000_000, 5064)
+			.saturating_add(Weight::from_parts(124_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().reads((2_u64).saturating_mul(n.into())))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+			.saturating_add(Weight::from_parts(0, 5100).saturating_mul(n.into()))
+	}
+	/// Conservative upper bound, not benchmarked.
+	/// Storage: LogisticsModule Members (r:1 w:0)
+	/// Storage: LogisticsModule Organizations (r:1 w:0)
+	/// Storage: LogisticsModule ContainedIn (r:1 w:0)
+	/// Storage: LogisticsModule Contents (r:1 w:0)
+	/// Storage: LogisticsModule Shipments (r:1 w:0)
+	/// Storage: LogisticsModule UpheldClaims (r:1 w:0)
+	/// Storage: LogisticsModule Claims (r:1 w:1)
+	/// Storage: LogisticsModule CustodyHistory (r:1 w:0)
+	/// Storage: System Account (r:1 w:1)
+	/// Storage: LogisticsModule FormerCustodians (r:1 w:0)
+	fn file_claim() -> Weight {
+		Weight::from_parts(440_000_000, 31941)
+			.saturating_add(RocksDbWeight::get().reads(10_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Conservative upper bound, not benchmarked.
+	/// Storage: LogisticsModule Members (r:1 w:0)
+	/// Storage: LogisticsModule Organizations (r:1 w:0)
+	/// Storage: LogisticsModule Claims (r:1 w:1)
+	/// Storage: System Account (r:1 w:1)
+	fn respond_to_claim() -> Weight {
+		Weight::from_parts(280_000_000, 10307)
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Conservative upper bound, not benchmarked.
+	/// Storage: LogisticsModule Claims (r:1 w:1)
+	/// Storage: System Account (r:3 w:3)
+	/// Storage: LogisticsModule Shipments (r:1 w:1)
+	/// Storage: LogisticsModule UpheldClaims (r:0 w:1)
+	/// Storage: LogisticsModule PendingHandoffs (r:0 w:1)
+	/// Storage: LogisticsModule Escrows (r:1 w:1)
+	/// Storage: LogisticsModule Deposits (r:1 w:1)
+	/// Storage: LogisticsModule PruneCursor (r:1 w:1)
+	/// Storage: LogisticsModule PruneQueue (r:0 w:1)
+	fn resolve_claim() -> Weight {
+		Weight::from_parts(590_000_000, 13438)
+			.saturating_add(RocksDbWeight::get().reads(8_u64))
+			.saturating_add(RocksDbWeight::get().writes(12_u64))
+	}
+	/// Conservative upper bound, not benchmarked.
+	/// Storage: LogisticsModule ContainedIn (r:1 w:0)
+	/// Storage: LogisticsModule Contents (r:1 w:0)
+	/// Storage: LogisticsModule Members (r:1 w:0)
+	/// Storage: LogisticsModule Organizations (r:1 w:0)
+	/// Storage: LogisticsModule Shipments (r:1 w:1)
+	/// Storage: LogisticsModule PruneCursor (r:1 w:1)
+	/// Storage: LogisticsModule PruneQueue (r:0 w:1)
+	/// Storage: LogisticsModule PendingHandoffs (r:0 w:1)
+	/// Storage: LogisticsModule Deadlines (r:0 w:1)
+	/// Storage: LogisticsModule Escrows (r:1 w:1)
+	/// Storage: System Account (r:1 w:1)
+	fn cancel_shipment() -> Weight {
+		Weight::from_parts(420_000_000, 18800)
+			.saturating_add(RocksDbWeight::get().reads(8_u64))
+			.saturating_add(RocksDbWeight::get().writes(7_u64))
+	}
+	/// Conservative upper bound, not benchmarked.
+	/// Storage: LogisticsModule Members (r:1 w:0)
+	/// Storage: LogisticsModule Organizations (r:1 w:0)
+	/// Storage: LogisticsModule Shipments (r:1 w:1)
+	/// Storage: LogisticsModule Deadlines (r:0 w:1)
+	fn return_to_sender() -> Weight {
+		Weight::from_parts(240_000_000, 7700)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Conservative upper bound, not benchmarked.
+	/// Storage: LogisticsModule Members (r:1 w:0)
+	/// Storage: LogisticsModule Organizations (r:1 w:0)
+	/// Storage: LogisticsModule Shipments (r:1 w:1)
+	/// Storage: LogisticsModule ContainedIn (r:1 w:0)
+	/// Storage: LogisticsModule Contents (r:1 w:0)
+	/// Storage: LogisticsModule PruneCursor (r:1 w:1)
+	/// Storage: LogisticsModule PruneQueue (r:0 w:1)
+	/// Storage: LogisticsModule PendingHandoffs (r:0 w:1)
+	/// Storage: LogisticsModule Deadlines (r:0 w:1)
+	/// Storage: LogisticsModule Escrows (r:1 w:1)
+	/// Storage: System Account (r:1 w:1)
+	/// Storage: LogisticsModule Deliveries (r:1 w:0)
+	fn reject_shipment() -> Weight {
+		Weight::from_parts(400_000_000, 21590)
+			.saturating_add(RocksDbWeight::get().reads(9_u64))
+			.saturating_add(RocksDbWeight::get().writes(7_u64))
+	}
+	/// Conservative upper bound, not benchmarked.
+	/// Storage: LogisticsModule Members (r:1 w:0)
+	/// Storage: LogisticsModule Organizations (r:1 w:0)
+	/// Storage: LogisticsModule Shipments (r:1 w:0)
+	/// Storage: LogisticsModule Deliveries (r:1 w:1)
+	fn confirm_receipt() -> Weight {
+		Weight::from_parts(210_000_000, 10490)
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Conservative upper bound, not benchmarked.
+	/// Storage: LogisticsModule Members (r:1 w:0)
+	/// Storage: LogisticsModule Organizations (r:1 w:0)
+	/// Storage: LogisticsModule ConsigneeKeys (r:0 w:1)
+	fn set_consignee_key() -> Weight {
+		Weight::from_parts(150_000_000, 5055)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Conservative upper bound, not benchmarked.
+	/// Storage: LogisticsModule Members (r:1 w:0)
+	/// Storage: LogisticsModule Organizations (r:1 w:0)
+	/// Storage: LogisticsModule Shipments (r:65 w:0)
+	/// Storage: LogisticsModule ContainedIn (r:65 w:64)
+	/// Storage: LogisticsModule Claims (r:65 w:0)
+	/// Storage: LogisticsModule Contents (r:65 w:1)
+	/// Storage: LogisticsModule PendingHandoffs (r:64 w:0)
+	/// The range of component `n` is `[1, 64]`.
+	fn load_shipments(n: u32, ) -> Weight {
+		Weight::from_parts(260_000_000, 18387)
+			.saturating_add(Weight::from_parts(152_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(6_u64))
+			.saturating_add(RocksDbWeight::get().reads((5_u64).saturating_mul(n.into())))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+			.saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 2603).saturating_mul(n.into()))
+	}
+	/// Conservative upper bound, not benchmarked.
+	/// Storage: LogisticsModule Members (r:1 w:0)
+	/// Storage: LogisticsModule Organizations (r:1 w:0)
+	/// Storage: LogisticsModule Shipments (r:1 w:0)
+	/// Storage: LogisticsModule Contents (r:1 w:1)
+	/// Storage: LogisticsModule ContainedIn (r:0 w:64)
+	/// The range of component `n` is `[1, 64]`.
+	fn unload_shipments(n: u32, ) -> Weight {
+		Weight::from_parts(220_000_000, 10688)
+			.saturating_add(Weight::from_parts(41_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+			.saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(n.into())))
+	}
+	/// Conservative upper bound, not benchmarked.
+	/// Storage: LogisticsModule Members (r:1 w:0)
+	/// Storage: LogisticsModule Organizations (r:1 w:0)
+	/// Storage: LogisticsModule ContainedIn (r:1 w:0)
+	/// Storage: LogisticsModule Contents (r:1 w:0)
+	/// Storage: LogisticsModule Shipments (r:17 w:17)
+	/// Storage: LogisticsModule CounterForShipments (r:1 w:1)
+	/// Storage: LogisticsModule PendingHandoffs (r:1 w:0)
+	/// Storage: LogisticsModule Claims (r:1 w:0)
+	/// Storage: LogisticsModule Facilities (r:16 w:0)
+	/// Storage: LogisticsModule CustodyHistory (r:1 w:16)
+	/// Storage: LogisticsModule Deadlines (r:1 w:17)
+	/// Storage: LogisticsModule Escrows (r:1 w:16)
+	/// Storage: LogisticsModule Deposits (r:1 w:17)
+	/// Storage: LogisticsModule DeadlineCursor (r:1 w:1)
+	/// Storage: LogisticsModule DeadlineAgenda (r:16 w:16)
+	/// Storage: LogisticsModule Manifests (r:0 w:16)
+	/// Storage: LogisticsModule SplitFrom (r:0 w:16)
+	/// Storage: LogisticsModule AcceptableRanges (r:1 w:16)
+	/// Storage: LogisticsModule Telemetry (r:1 w:16)
+	/// Storage: LogisticsModule PruneCursor (r:1 w:1)
+	/// Storage: LogisticsModule PruneQueue (r:0 w:1)
+	/// Storage: LogisticsModule FormerCustodians (r:1 w:16)
+	/// The range of component `n` is `[2, 16]`.
+	fn split_shipment(n: u32, ) -> Weight {
+		Weight::from_parts(480_000_000, 50673)
+			.saturating_add(Weight::from_parts(341_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(17_u64))
+			.saturating_add(RocksDbWeight::get().reads((3_u64).saturating_mul(n.into())))
+			.saturating_add(RocksDbWeight::get().writes(6_u64))
+			.saturating_add(RocksDbWeight::get().writes((12_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 6469).saturating_mul(n.into()))
+	}
+	/// Conservative upper bound, not benchmarked.
+	/// Storage: LogisticsModule Members (r:1 w:0)
+	/// Storage: LogisticsModule Organizations (r:1 w:0)
+	/// Storage: LogisticsModule Shipments (r:1 w:0)
+	/// Storage: LogisticsModule AcceptableRanges (r:0 w:1)
+	fn set_condition_ranges() -> Weight {
+		Weight::from_parts(210_000_000, 7700)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Conservative upper bound, not benchmarked.
+	/// Storage: LogisticsModule Members (r:1 w:0)
+	/// Storage: LogisticsModule Organizations (r:1 w:0)
+	/// Storage: LogisticsModule Shipments (r:1 w:0)
+	/// Storage: LogisticsModule AcceptableRanges (r:1 w:0)
+	/// Storage: LogisticsModule Telemetry (r:1 w:1)
+	/// Storage: Timestamp Now (r:1 w:0)
+	fn record_reading() -> Weight {
+		Weight::from_parts(290_000_000, 13312)
+			.saturating_add(RocksDbWeight::get().reads(6_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Conservative upper bound, not benchmarked.
+	/// Storage: LogisticsModule Members (r:1 w:0)
+	/// Storage: LogisticsModule Organizations (r:1 w:0)
+	/// Storage: LogisticsModule Devices (r:1 w:1)
+	/// Storage: LogisticsModule DeviceCounts (r:1 w:1)
+	fn register_device() -> Weight {
+		Weight::from_parts(190_000_000, 10081)
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Conservative upper bound, not benchmarked.
+	/// Storage: LogisticsModule Members (r:1 w:0)
+	/// Storage: LogisticsModule Devices (r:1 w:1)
+	/// Storage: LogisticsModule DeviceCounts (r:1 w:1)
+	fn deregister_device() -> Weight {
+		Weight::from_parts(180_000_000, 7557)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Conservative upper bound, not benchmarked.
+	/// Storage: LogisticsModule Devices (r:1 w:0)
+	/// Storage: LogisticsModule DeviceUsages (r:1 w:1)
+	/// Storage: LogisticsModule Organizations (r:1 w:0)
+	/// Storage: LogisticsModule ContainedIn (r:1 w:64)
+	/// Storage: LogisticsModule Shipments (r:65 w:65)
+	/// Storage: LogisticsModule LastScanned (r:0 w:65)
+	/// Storage: LogisticsModule Members (r:1 w:0)
+	/// Storage: LogisticsModule PendingHandoffs (r:0 w:1)
+	/// Storage: LogisticsModule Escrows (r:65 w:65)
+	/// Storage: System Account (r:65 w:65)
+	/// Storage: LogisticsModule Contents (r:1 w:1)
+	/// Storage: LogisticsModule PruneCursor (r:1 w:1)
+	/// Storage: LogisticsModule PruneQueue (r:0 w:65)
+	/// The range of component `c` is `[0, 64]`.
+	fn submit_scan(c: u32, ) -> Weight {
+		Weight::from_parts(450_000_000, 23870)
+			.saturating_add(Weight::from_parts(241_000_000, 0).saturating_mul(c.into()))
+			.saturating_add(RocksDbWeight::get().reads(10_u64))
+			.saturating_add(RocksDbWeight::get().reads((3_u64).saturating_mul(c.into())))
+			.saturating_add(RocksDbWeight::get().writes(9_u64))
+			.saturating_add(RocksDbWeight::get().writes((6_u64).saturating_mul(c.into())))
+			.saturating_add(Weight::from_parts(0, 5230).saturating_mul(c.into()))
+	}
+	/// Conservative upper bound, not benchmarked.
+	/// Storage: LogisticsModule Devices (r:1 w:0)
+	/// Storage: LogisticsModule DeviceUsages (r:1 w:1)
+	/// Storage: LogisticsModule Organizations (r:1 w:0)
+	/// Storage: LogisticsModule Shipments (r:1 w:0)
+	/// Storage: LogisticsModule Members (r:1 w:0)
+	/// Storage: LogisticsModule AcceptableRanges (r:1 w:0)
+	/// Storage: LogisticsModule Telemetry (r:1 w:1)
+	/// Storage: Timestamp Now (r:1 w:0)
+	fn submit_reading() -> Weight {
+		Weight::from_parts(330_000_000, 18382)
+			.saturating_add(RocksDbWeight::get().reads(8_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+}
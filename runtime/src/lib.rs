@@ -15,7 +15,7 @@ use sp_runtime::{
 	traits::{
 		AccountIdLookup, BlakeTwo256, Block as BlockT, IdentifyAccount, NumberFor, One, Verify,
 	},
-	transaction_validity::{TransactionSource, TransactionValidity},
+	transaction_validity::{TransactionPriority, TransactionSource, TransactionValidity},
 	ApplyExtrinsicResult, MultiSignature,
 };
 use sp_std::prelude::*;
@@ -103,10 +103,10 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
 	//   `spec_version`, and `authoring_version` are the same between Wasm and native.
 	// This value is set to 100 to notify Polkadot-JS App (https://polkadot.js.org/apps) to use
 	//   the compatible custom types.
-	spec_version: 100,
+	spec_version: 101,
 	impl_version: 1,
 	apis: RUNTIME_API_VERSIONS,
-	transaction_version: 1,
+	transaction_version: 2,
 	state_version: 1,
 };
 
@@ -263,9 +263,42 @@ impl pallet_sudo::Config for Runtime {
 	type RuntimeCall = RuntimeCall;
 }
 
+parameter_types! {
+	pub const FeeDistribution: pallet_logistics::FeeDistribution =
+		pallet_logistics::FeeDistribution::PerLeg;
+	pub const DeviceSubmissionPriority: TransactionPriority = TransactionPriority::max_value() / 2;
+}
+
 /// Configure the pallet-template in pallets/template.
 impl pallet_logistics::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
+	// Deliberately conservative until the pallet is benchmarked, see its `weights` module.
+	type WeightInfo = pallet_logistics::weights::SubstrateWeight<Runtime>;
+	type HandoffTimeout = ConstU32<HOURS>;
+	type TimeProvider = Timestamp;
+	type MaxCustodyHops = ConstU32<64>;
+	type MaxContainerSize = ConstU32<64>;
+	type MaxSplitParts = ConstU32<16>;
+	type MaxEvidence = ConstU32<8>;
+	type DeliveredRetention = ConstU32<{ 28 * DAYS }>;
+	type AdminOrigin = frame_system::EnsureRoot<AccountId>;
+	type MaxManifestItems = ConstU32<64>;
+	type Currency = Balances;
+	type FeeDistribution = FeeDistribution;
+	type DepositBase = ConstU128<{ 10 * EXISTENTIAL_DEPOSIT }>;
+	type DepositPerByte = ConstU128<{ EXISTENTIAL_DEPOSIT / 100 }>;
+	type AbandonmentTimeout = ConstU32<{ 90 * DAYS }>;
+	type Slash = ();
+	type MaxDeadlinesPerBlock = ConstU32<256>;
+	type ExpectedBlockTime = ConstU64<MILLISECS_PER_BLOCK>;
+	type ArbiterOrigin = frame_system::EnsureRoot<AccountId>;
+	type ClaimBond = ConstU128<{ 100 * EXISTENTIAL_DEPOSIT }>;
+	type DeviceId = pallet_logistics::devices::sr25519::DeviceId;
+	type MaxDeviceSubmissions = ConstU32<60>;
+	type MaxDevicesPerOrganization = ConstU32<100>;
+	type DeviceRateWindow = ConstU32<HOURS>;
+	type DeviceSubmissionPriority = DeviceSubmissionPriority;
+	type TelemetryWindow = ConstU32<60>;
 }
 
 // Create the runtime by composing the FRAME pallets that were previously configured.
@@ -311,6 +344,17 @@ pub type UncheckedExtrinsic =
 	generic::UncheckedExtrinsic<Address, RuntimeCall, Signature, SignedExtra>;
 /// The payload being signed in transactions.
 pub type SignedPayload = generic::SignedPayload<RuntimeCall, SignedExtra>;
+/// Storage migrations run on the next runtime upgrade.
+pub type Migrations = (
+	pallet_logistics::migrations::v1::MigrateToV1<Runtime>,
+	pallet_logistics::migrations::v2::MigrateToV2<Runtime>,
+	pallet_logistics::migrations::v3::MigrateToV3<Runtime>,
+	pallet_logistics::migrations::v4::MigrateToV4<Runtime>,
+	pallet_logistics::migrations::v5::MigrateToV5<Runtime>,
+	pallet_logistics::migrations::v6::MigrateToV6<Runtime>,
+	pallet_logistics::migrations::v7::MigrateToV7<Runtime>,
+	pallet_logistics::migrations::v8::MigrateToV8<Runtime>,
+);
 /// Executive: handles dispatch to the various modules.
 pub type Executive = frame_executive::Executive<
 	Runtime,
@@ -318,6 +362,7 @@ pub type Executive = frame_executive::Executive<
 	frame_system::ChainContext<Runtime>,
 	Runtime,
 	AllPalletsWithSystem,
+	Migrations,
 >;
 
 #[cfg(feature = "runtime-benchmarks")]
@@ -495,6 +540,20 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl pallet_logistics_runtime_api::LogisticsApi<Block, AccountId, BlockNumber> for Runtime {
+		fn custody_history(
+			shipment_id: u64,
+		) -> Vec<pallet_logistics::CustodyRecord<AccountId, BlockNumber>> {
+			LogisticsModule::custody_history(shipment_id)
+		}
+
+		fn facility(
+			facility_id: pallet_logistics::FacilityId,
+		) -> Option<pallet_logistics::Facility<AccountId>> {
+			LogisticsModule::facility(facility_id)
+		}
+	}
+
 	#[cfg(feature = "runtime-benchmarks")]
 	impl frame_benchmarking::Benchmark<Block> for Runtime {
 		fn benchmark_metadata(extra: bool) -> (